@@ -16,9 +16,16 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use aoc_common::parsing::LineParser;
 use aoc_solution::Aoc;
+use std::num::ParseIntError;
+use std::str::FromStr;
 
 #[derive(Aoc)]
+#[aoc(input = Vec<Module>)]
+#[aoc(parser = LineParser)]
+#[aoc(part1(output = u64, runner = part1))]
+#[aoc(part2(output = u64, runner = part2))]
 pub struct Day01;
 
 struct FuelCalculator {}
@@ -37,6 +44,7 @@ trait Fuelable {
     fn calculate_total_required_fuel(&self) -> u64;
 }
 
+#[derive(Clone)]
 pub struct Module {
     mass: u64,
 }
@@ -76,27 +84,42 @@ impl FuelUpper {
     }
 }
 
-pub fn input_to_modules(inputs: Vec<String>) -> Vec<Module> {
-    inputs
-        .iter()
-        .map(|i| i.parse::<u64>().unwrap())
-        .map(Module::new)
-        .collect()
+impl FromStr for Module {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Module::new)
+    }
 }
 
-pub fn do_part1(inputs_modules: &[Module]) {
-    let required_base_fuel = FuelUpper::determine_total_required_base_fuel(inputs_modules);
-    println!("Part 1 answer: {}", required_base_fuel);
+pub fn part1(input: Vec<Module>) -> u64 {
+    FuelUpper::determine_total_required_base_fuel(&input)
 }
 
-pub fn do_part2(input_modules: &[Module]) {
-    let required_total_fuel = FuelUpper::determine_total_required_fuel(input_modules);
-    println!("Part 2 answer: {}", required_total_fuel);
+pub fn part2(input: Vec<Module>) -> u64 {
+    FuelUpper::determine_total_required_fuel(&input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_solution::parser::AocInputParser;
+
+    fn sample_input() -> Vec<Module> {
+        LineParser::parse_input("12\n14\n1969\n100756").unwrap()
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let expected = 34241;
+        assert_eq!(expected, part1(sample_input()))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let expected = 51316;
+        assert_eq!(expected, part2(sample_input()))
+    }
 
     #[test]
     fn module_calculates_correct_base_fuel_for_mass_of_12() {
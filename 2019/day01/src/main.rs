@@ -12,29 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// legacy code
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use day01_2019::Day01;
 
-use day01_2019::{do_part1, do_part2, input_to_modules};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn read_input_file(path: &str) -> Vec<String> {
-    let file = File::open(path).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut inputs = vec![];
-    for line in reader.lines() {
-        inputs.push(line.unwrap());
-    }
-
-    inputs
-}
-
-fn main() {
-    let day1_input = read_input_file("inputs/2019/day01");
-    let modules = input_to_modules(day1_input);
-    do_part1(&modules);
-    do_part2(&modules);
+#[cfg(not(tarpaulin_include))]
+fn main() -> anyhow::Result<()> {
+    Day01::try_solve_from_file(input_path(2019, 1)?);
+    Ok(())
 }
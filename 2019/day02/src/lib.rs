@@ -16,9 +16,14 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use aoc_common::parsing::CommaSeparatedParser;
 use aoc_solution::Aoc;
 
 #[derive(Aoc)]
+#[aoc(input = Vec<usize>)]
+#[aoc(parser = CommaSeparatedParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2))]
 pub struct Day02;
 
 // The below code could be made slightly nicer by introducing Tape type and defining methods on it.
@@ -107,31 +112,25 @@ fn prepare_tape(input_tape: Vec<usize>, subs: (usize, usize)) -> Vec<usize> {
         .collect()
 }
 
-pub fn do_part1(input: Vec<usize>) {
+pub fn part1(input: Vec<usize>) -> usize {
     let prepared_tape = prepare_tape(input, (12, 2));
-    println!(
-        "Part 1 answer: {}",
-        IntcodeMachine::new(prepared_tape).run()
-    );
+    IntcodeMachine::new(prepared_tape).run()
 }
 
-pub fn do_part2(input: Vec<usize>) {
+pub fn part2(input: Vec<usize>) -> usize {
     // bruteforce possible noun, verb pairs
     // an alternative would be to reverse engineer the machine execution
     // or implement something like SAT solver
     // But even puzzle authors imply you should just try to bruteforce
-    let part2_answer_vec: Vec<usize> = (0..99)
-        .flat_map(|noun| (0..99).map(move |verb| (noun, verb)))
+    (0..=99)
+        .flat_map(|noun| (0..=99).map(move |verb| (noun, verb)))
         .map(|noun_verb_pair| {
             let machine_input = prepare_tape(input.clone(), noun_verb_pair);
             (noun_verb_pair, IntcodeMachine::new(machine_input).run())
         })
-        .skip_while(|(_, output)| *output != 19_690_720)
+        .find(|(_, output)| *output == 19_690_720)
         .map(|(noun_verb_pair, _)| 100 * noun_verb_pair.0 + noun_verb_pair.1)
-        .take(1)
-        .collect();
-
-    println!("Part 2 answer: {:?}", part2_answer_vec.first().unwrap());
+        .expect("none of the noun and verb pairs produced the expected output")
 }
 
 #[cfg(test)]
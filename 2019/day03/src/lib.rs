@@ -16,11 +16,18 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use aoc_common::parsing::LineParser;
 use aoc_solution::Aoc;
 use itertools::Itertools;
 use std::cmp::{max, min};
+use std::convert::Infallible;
+use std::str::FromStr;
 
 #[derive(Aoc)]
+#[aoc(input = Vec<Wire>)]
+#[aoc(parser = LineParser)]
+#[aoc(part1(output = i64, runner = part1))]
+#[aoc(part2(output = i64, runner = part2))]
 pub struct Day03;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -235,18 +242,24 @@ impl Wire {
     }
 }
 
-pub fn do_part1(input_wires: Vec<Wire>) {
+impl FromStr for Wire {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Wire::new_from_raw(s))
+    }
+}
+
+pub fn part1(input_wires: Vec<Wire>) -> i64 {
     assert_eq!(2, input_wires.len()); // as per specs
-    let closest_intersection_dist = input_wires[0]
+    input_wires[0]
         .closest_intersection_to_origin(&input_wires[1])
-        .manhattan_distance_to_origin();
-    println!("Part 1 answer: {}", closest_intersection_dist);
+        .manhattan_distance_to_origin()
 }
 
-pub fn do_part2(input_wires: Vec<Wire>) {
+pub fn part2(input_wires: Vec<Wire>) -> i64 {
     assert_eq!(2, input_wires.len()); // as per specs
-    let least_steps = input_wires[0].least_step_intersection_distance(&input_wires[1]);
-    println!("Part 2 answer: {}", least_steps);
+    input_wires[0].least_step_intersection_distance(&input_wires[1])
 }
 
 #[cfg(test)]
@@ -342,8 +355,8 @@ mod tests {
         }
 
         #[test]
-        fn it_correctly_detects_no_intersection_outside_segments_even_if_infinite_lines_would_have_intersected(
-        ) {
+        fn it_correctly_detects_no_intersection_outside_segments_even_if_infinite_lines_would_have_intersected()
+         {
             let l1 = WireSegment::new(Point::new(0, 0), Point::new(1, 1));
             let l2 = WireSegment::new(Point::new(2, 3), Point::new(3, 2));
             assert_eq!(None, l1.intersection(&l2))
@@ -388,12 +401,8 @@ mod tests {
 
         #[test]
         fn it_returns_none_for_invalid_translations() {
-            if let Some(_) = PointAxisTranslation::from_str("Z10") {
-                panic!("expected nothing!")
-            }
-            if let Some(_) = PointAxisTranslation::from_str("Z1Y0") {
-                panic!("expected nothing!")
-            }
+            assert!(PointAxisTranslation::from_str("Z10").is_none());
+            assert!(PointAxisTranslation::from_str("Z1Y0").is_none());
         }
     }
 }
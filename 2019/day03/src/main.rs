@@ -12,23 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// legacy code
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use day03_2019::Day03;
 
-use day03_2019::{do_part1, do_part2, Wire};
-use std::fs;
-
-fn read_input_file(path: &str) -> Vec<Wire> {
-    fs::read_to_string(path)
-        .unwrap()
-        .split('\n')
-        .map(Wire::new_from_raw)
-        .collect()
-}
-
-fn main() {
-    let wires = read_input_file("inputs/2019/day03");
-    do_part1(wires.clone());
-    do_part2(wires);
+#[cfg(not(tarpaulin_include))]
+fn main() -> anyhow::Result<()> {
+    Day03::try_solve_from_file(input_path(2019, 3)?);
+    Ok(())
 }
@@ -16,16 +16,23 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use aoc_common::parsing::FromStrParser;
 use aoc_solution::Aoc;
 use itertools::Itertools;
+use std::convert::Infallible;
+use std::str::FromStr;
 
 #[derive(Aoc)]
+#[aoc(input = PasswordRange)]
+#[aoc(parser = FromStrParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2))]
 pub struct Day04;
 
 // this solution is again, not the most optimal, but that's not the point
 // the aim of those exercises is to improve my Rust understanding and playing with iterators.
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PasswordRange {
     min: usize,
     max: usize,
@@ -45,6 +52,14 @@ impl PasswordRange {
     }
 }
 
+impl FromStr for PasswordRange {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PasswordRange::new(s.trim()))
+    }
+}
+
 struct Password {
     val: usize,
     val_digits: Vec<usize>,
@@ -99,7 +114,7 @@ impl Password {
                 }
             })
             .any(|(_, count)| count == 2) // check if there are any groups of size 2
-                                          // that was ambiguous. Initially I was looking for group of even length because that's what I understood from the question
+        // that was ambiguous. Initially I was looking for group of even length because that's what I understood from the question
     }
 
     fn is_not_decreasing(&self) -> bool {
@@ -137,14 +152,12 @@ impl PasswordCombinations {
     }
 }
 
-pub fn do_part1(pwrange: &PasswordRange) {
-    let valid_pass_count = PasswordCombinations::part1_determine(pwrange);
-    println!("Part 1 answer: {}", valid_pass_count);
+pub fn part1(pwrange: PasswordRange) -> usize {
+    PasswordCombinations::part1_determine(&pwrange)
 }
 
-pub fn do_part2(pwrange: &PasswordRange) {
-    let valid_pass_count = PasswordCombinations::part2_determine(pwrange);
-    println!("Part 2 answer: {}", valid_pass_count);
+pub fn part2(pwrange: PasswordRange) -> usize {
+    PasswordCombinations::part2_determine(&pwrange)
 }
 
 #[cfg(test)]
@@ -12,17 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// legacy code
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use day04_2019::Day04;
 
-use day04_2019::{do_part1, do_part2, PasswordRange};
-
-const DAY4_INPUT: &str = "402328-864247";
-
-fn main() {
-    let input = DAY4_INPUT;
-    let pwrange = PasswordRange::new(input);
-    do_part1(&pwrange);
-    do_part2(&pwrange)
+#[cfg(not(tarpaulin_include))]
+fn main() -> anyhow::Result<()> {
+    Day04::try_solve_from_file(input_path(2019, 4)?);
+    Ok(())
 }
@@ -16,11 +16,18 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use aoc_common::parsing::{FromStrParser, parse_comma_separated_values};
 use aoc_solution::Aoc;
+use std::collections::VecDeque;
+use std::str::FromStr;
 
 pub mod utils;
 
 #[derive(Aoc)]
+#[aoc(input = Tape)]
+#[aoc(parser = FromStrParser)]
+#[aoc(part1(output = isize, runner = part1))]
+#[aoc(part2(output = isize, runner = part2))]
 pub struct Day05;
 
 const ADD_OP_CODE: isize = 1;
@@ -36,8 +43,8 @@ const HALT_OP_CODE: isize = 99;
 const POSITION_MODE: usize = 0;
 const IMMEDIATE_MODE: usize = 1;
 
-// TODO: in hindsight stdout and stdio should have been injected with dependency injection to be
-// able to actually test OP3 and OP4
+const AIR_CONDITIONER_ID: isize = 1;
+const THERMAL_RADIATOR_CONTROLLER_ID: isize = 5;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ParamMode {
@@ -61,12 +68,20 @@ impl TryFrom<usize> for ParamMode {
 
 type HeadPositionUpdate = usize;
 
+/// Values consumed by the input instructions and produced by the output ones.
+#[derive(Debug, Default)]
+struct MachineIo {
+    inputs: VecDeque<isize>,
+    outputs: Vec<isize>,
+}
+
 #[derive(Debug)]
 pub enum OpCodeExecutionError {
     TapeError,
     InvalidOpArguments,
     ExecutionFailure,
     ExecutionFinished,
+    MissingInput,
 }
 
 impl From<TapeError> for OpCodeExecutionError {
@@ -94,6 +109,7 @@ impl OpCode {
         &self,
         tape: &mut Tape,
         head_position: usize,
+        io: &mut MachineIo,
     ) -> Result<HeadPositionUpdate, OpCodeExecutionError> {
         use OpCode::*;
         match self {
@@ -104,8 +120,8 @@ impl OpCode {
             Lt(param_modes) => self.execute_less_than(tape, head_position, param_modes.clone()),
             Eq(param_modes) => self.execute_equals(tape, head_position, param_modes.clone()),
 
-            In => self.execute_input(tape, head_position),
-            Out(param_modes) => self.execute_output(tape, head_position, param_modes.clone()),
+            In => self.execute_input(tape, head_position, io),
+            Out(param_modes) => self.execute_output(tape, head_position, param_modes.clone(), io),
 
             Halt => Err(OpCodeExecutionError::ExecutionFinished),
             Er(_) => Err(OpCodeExecutionError::ExecutionFailure),
@@ -235,14 +251,13 @@ impl OpCode {
         &self,
         tape: &mut Tape,
         head_position: usize,
+        io: &mut MachineIo,
     ) -> Result<HeadPositionUpdate, OpCodeExecutionError> {
         let output_idx = tape.read(head_position + 1)?;
-
-        // Read the user input
-        println!("Provide the system required input...");
-        let mut buffer = String::new();
-        std::io::stdin().read_line(&mut buffer).unwrap();
-        let input_value = buffer.trim().parse::<isize>().unwrap();
+        let input_value = io
+            .inputs
+            .pop_front()
+            .ok_or(OpCodeExecutionError::MissingInput)?;
 
         tape.write(output_idx as usize, input_value)?;
 
@@ -254,9 +269,10 @@ impl OpCode {
         tape: &mut Tape,
         head_position: usize,
         param_modes: Vec<ParamMode>,
+        io: &mut MachineIo,
     ) -> Result<HeadPositionUpdate, OpCodeExecutionError> {
         let output_val = self.mode_tape_read(tape, head_position + 1, param_modes[0])?;
-        println!("Test result: {}", output_val);
+        io.outputs.push(output_val);
         Ok(head_position + 2)
     }
 }
@@ -322,6 +338,7 @@ pub enum TapeError {
     ReadOutOfRangeError,
 }
 
+#[derive(Clone)]
 pub struct Tape(Vec<isize>);
 
 impl Tape {
@@ -351,6 +368,14 @@ impl Tape {
     }
 }
 
+impl FromStr for Tape {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_comma_separated_values(s).map(Tape::new)
+    }
+}
+
 #[derive(Debug)]
 pub enum IntcodeMachineError {
     TapeOutOfBoundsError,
@@ -373,6 +398,7 @@ pub struct IntcodeMachine {
     tape: Tape,
     head_position: usize,
     output: isize,
+    io: MachineIo,
 }
 
 impl IntcodeMachine {
@@ -381,9 +407,19 @@ impl IntcodeMachine {
             tape,
             head_position: 0,
             output: 0,
+            io: MachineIo::default(),
         }
     }
 
+    pub fn with_inputs(mut self, inputs: Vec<isize>) -> Self {
+        self.io.inputs = inputs.into();
+        self
+    }
+
+    pub fn outputs(&self) -> &[isize] {
+        &self.io.outputs
+    }
+
     fn update_head(&mut self, val: HeadPositionUpdate) -> Result<(), IntcodeMachineError> {
         // check if new head is within 0..tape.len()
         if !(0..self.tape.len()).contains(&val) {
@@ -397,7 +433,7 @@ impl IntcodeMachine {
     pub fn run(&mut self) -> Result<isize, IntcodeMachineError> {
         loop {
             let op = OpCode::from(self.tape.read(self.head_position)?);
-            let head_update = match op.execute(&mut self.tape, self.head_position) {
+            let head_update = match op.execute(&mut self.tape, self.head_position, &mut self.io) {
                 Err(err) => {
                     return match err {
                         OpCodeExecutionError::ExecutionFinished => {
@@ -405,7 +441,7 @@ impl IntcodeMachine {
                             Ok(self.output)
                         }
                         _ => Err(IntcodeMachineError::ExecutionFailure),
-                    }
+                    };
                 }
                 Ok(head_update) => head_update,
             };
@@ -415,6 +451,25 @@ impl IntcodeMachine {
     }
 }
 
+fn run_diagnostic_program(tape: Tape, system_id: isize) -> isize {
+    let mut machine = IntcodeMachine::new(tape).with_inputs(vec![system_id]);
+    machine.run().expect("the diagnostic program has failed");
+
+    // all the outputs but the last one are the results of the tests, which should have been 0
+    *machine
+        .outputs()
+        .last()
+        .expect("the diagnostic program did not produce any output")
+}
+
+pub fn part1(tape: Tape) -> isize {
+    run_diagnostic_program(tape, AIR_CONDITIONER_ID)
+}
+
+pub fn part2(tape: Tape) -> isize {
+    run_diagnostic_program(tape, THERMAL_RADIATOR_CONTROLLER_ID)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -429,6 +484,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn machine_outputs_its_input() {
+        let mut machine =
+            IntcodeMachine::new(Tape::new(vec![3, 0, 4, 0, 99])).with_inputs(vec![42]);
+        machine.run().unwrap();
+        assert_eq!(&[42], machine.outputs());
+    }
+
+    #[test]
+    fn machine_compares_input_to_8() {
+        let tape: Tape = "3,9,8,9,10,9,4,9,99,-1,8".parse().unwrap();
+        assert_eq!(1, run_diagnostic_program(tape.clone(), 8));
+        assert_eq!(0, run_diagnostic_program(tape, 5));
+    }
+
+    #[test]
+    fn machine_fails_without_input() {
+        assert!(
+            IntcodeMachine::new(Tape::new(vec![3, 0, 99]))
+                .run()
+                .is_err()
+        );
+    }
+
     #[cfg(test)]
     mod day2_intcode_machine_reimplementation {
         use super::*;
@@ -16,10 +16,17 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use anyhow::bail;
+use aoc_common::parsing::LineParser;
 use aoc_solution::Aoc;
 use std::collections::HashMap;
+use std::str::FromStr;
 
 #[derive(Aoc)]
+#[aoc(input = Vec<Orbit>)]
+#[aoc(parser = LineParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2))]
 pub struct Day06;
 
 #[derive(Debug)]
@@ -85,7 +92,7 @@ impl OrbitalMap {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Orbit {
     center_of_mass_name: String,
     orbiting_objects: Vec<Orbit>,
@@ -99,6 +106,18 @@ impl Orbit {
         }
     }
 
+    fn from_raw(raw_orbit: &str) -> anyhow::Result<Self> {
+        let object_names: Vec<_> = raw_orbit.split(')').collect();
+        if object_names.len() != 2 {
+            bail!("'{raw_orbit}' is not a valid orbit")
+        }
+        let mut main_orbit = Orbit::new(String::from(object_names[0]));
+        let orbiting_object = Orbit::new(String::from(object_names[1]));
+        main_orbit.add_orbiting_object(orbiting_object);
+
+        Ok(main_orbit)
+    }
+
     fn orbit_count(&self, chain_length: usize) -> usize {
         self.orbiting_objects
             .iter()
@@ -154,32 +173,50 @@ impl Orbit {
     }
 }
 
-pub fn parse_orbits(raw_orbits: Vec<String>) -> Vec<Orbit> {
+impl FromStr for Orbit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Orbit::from_raw(s)
+    }
+}
+
+pub fn parse_orbits(raw_orbits: Vec<String>) -> anyhow::Result<Vec<Orbit>> {
     raw_orbits
-        .into_iter()
-        .map(|raw_orbit| {
-            let object_names: Vec<_> = raw_orbit.split(')').collect();
-            assert_eq!(2, object_names.len());
-            let mut main_orbit = Orbit::new(String::from(object_names[0]));
-            let orbiting_object = Orbit::new(String::from(object_names[1]));
-            main_orbit.add_orbiting_object(orbiting_object);
-
-            main_orbit
-        })
+        .iter()
+        .map(|raw_orbit| Orbit::from_raw(raw_orbit))
         .collect()
 }
 
-pub fn do_part1(orbital_map: &OrbitalMap) {
-    println!("Part 1 answer: {}", orbital_map.total_orbit_count());
+pub fn part1(orbits: Vec<Orbit>) -> usize {
+    OrbitalMap::new(orbits).total_orbit_count()
 }
 
-pub fn do_part2(orbital_map: &OrbitalMap) {
-    println!("Part 1 answer: {}", orbital_map.num_transfers("YOU", "SAN"));
+pub fn part2(orbits: Vec<Orbit>) -> usize {
+    OrbitalMap::new(orbits).num_transfers("YOU", "SAN")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_solution::parser::AocInputParser;
+
+    #[test]
+    fn part1_sample_input() {
+        let orbits =
+            LineParser::parse_input("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L")
+                .unwrap();
+        assert_eq!(42, part1(orbits))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let orbits = LineParser::parse_input(
+            "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN",
+        )
+        .unwrap();
+        assert_eq!(4, part2(orbits))
+    }
 
     #[test]
     fn sample_orbital_map_returns_correct_number_of_orbits() {
@@ -196,7 +233,7 @@ mod tests {
             String::from("J)K"),
             String::from("K)L"),
         ];
-        let raw_orbits = parse_orbits(sample_orbits);
+        let raw_orbits = parse_orbits(sample_orbits).unwrap();
         let orbital_map = OrbitalMap::new(raw_orbits);
 
         assert_eq!(42, orbital_map.total_orbit_count());
@@ -219,7 +256,7 @@ mod tests {
             String::from("K)YOU"),
             String::from("I)SAN"),
         ];
-        let raw_orbits = parse_orbits(sample_orbits);
+        let raw_orbits = parse_orbits(sample_orbits).unwrap();
         let orbital_map = OrbitalMap::new(raw_orbits);
 
         assert_eq!(4, orbital_map.num_transfers("YOU", "SAN"));
@@ -235,9 +272,16 @@ mod tests {
             String::from("A)YOU"),
             String::from("D)SAN"),
         ];
-        let raw_orbits = parse_orbits(sample_orbits);
+        let raw_orbits = parse_orbits(sample_orbits).unwrap();
         let orbital_map = OrbitalMap::new(raw_orbits);
 
         assert_eq!(3, orbital_map.num_transfers("YOU", "SAN"));
     }
+
+    #[test]
+    fn malformed_orbits_are_rejected() {
+        assert!("COM)B".parse::<Orbit>().is_ok());
+        assert!("COM".parse::<Orbit>().is_err());
+        assert!("COM)B)C".parse::<Orbit>().is_err());
+    }
 }
@@ -12,31 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// legacy code
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use day06_2019::Day06;
 
-use day06_2019::{do_part1, do_part2, parse_orbits, OrbitalMap};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn read_input_file(path: &str) -> Vec<String> {
-    let file = File::open(path).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut inputs = vec![];
-    for line in reader.lines() {
-        inputs.push(line.unwrap());
-    }
-
-    inputs
-}
-
-fn main() {
-    let raw_day6_input = read_input_file("inputs/2019/day06");
-    let raw_orbits = parse_orbits(raw_day6_input);
-    let orbital_map = OrbitalMap::new(raw_orbits);
-
-    do_part1(&orbital_map);
-    do_part2(&orbital_map);
+#[cfg(not(tarpaulin_include))]
+fn main() -> anyhow::Result<()> {
+    Day06::try_solve_from_file(input_path(2019, 6)?);
+    Ok(())
 }
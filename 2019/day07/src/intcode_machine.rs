@@ -1,7 +1,9 @@
 use std::convert::TryFrom;
 use std::io::{BufRead, Write};
+use std::str::FromStr;
 
 use crate::utils;
+use aoc_common::parsing::parse_comma_separated_values;
 
 const ADD_OP_CODE: isize = 1;
 const MUL_OP_CODE: isize = 2;
@@ -347,6 +349,14 @@ impl Tape {
     }
 }
 
+impl FromStr for Tape {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_comma_separated_values(s).map(Tape::new)
+    }
+}
+
 #[derive(Debug)]
 pub enum IntcodeMachineError {
     TapeOutOfBoundsError,
@@ -455,7 +465,7 @@ where
                         return Ok(self.tape.read(0)?);
                     }
                     OpCodeExecutionError::InputFailure => {
-                        return Err(IntcodeMachineError::InputFailure(self.dump_state()))
+                        return Err(IntcodeMachineError::InputFailure(self.dump_state()));
                     }
                     _ => {
                         return Err(IntcodeMachineError::ExecutionFailure);
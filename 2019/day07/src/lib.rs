@@ -17,6 +17,7 @@
 #![allow(clippy::expect_used)]
 
 use crate::intcode_machine::{IntcodeMachine, IntcodeMachineError, State, Tape};
+use aoc_common::parsing::FromStrParser;
 use aoc_solution::Aoc;
 use itertools::enumerate;
 use permutohedron::LexicalPermutation;
@@ -25,6 +26,10 @@ pub mod intcode_machine;
 pub mod utils;
 
 #[derive(Aoc)]
+#[aoc(input = Tape)]
+#[aoc(parser = FromStrParser)]
+#[aoc(part1(output = isize, runner = part1))]
+#[aoc(part2(output = isize, runner = part2))]
 pub struct Day07;
 
 struct AmplifierPhaseSequence(Vec<usize>);
@@ -101,7 +106,7 @@ impl AmplifierPhaseSequence {
     }
 }
 
-pub fn do_part1(tape: Tape) {
+pub fn part1(tape: Tape) -> isize {
     let mut highest_signal = 0;
 
     let mut data = [0, 1, 2, 3, 4];
@@ -114,7 +119,6 @@ pub fn do_part1(tape: Tape) {
     }
 
     for perm in permutations {
-        println!("testing permutation: {:?}", perm);
         let amp_seq = AmplifierPhaseSequence::new(perm);
         let amp_out = amp_seq.test_sequence(tape.clone());
         if amp_out > highest_signal {
@@ -122,10 +126,10 @@ pub fn do_part1(tape: Tape) {
         }
     }
 
-    println!("Part 1 answer: {}", highest_signal);
+    highest_signal
 }
 
-pub fn do_part2(tape: Tape) {
+pub fn part2(tape: Tape) -> isize {
     let mut highest_signal = 0;
 
     let mut data = [5, 6, 7, 8, 9];
@@ -138,7 +142,6 @@ pub fn do_part2(tape: Tape) {
     }
 
     for perm in permutations {
-        println!("testing permutation: {:?}", perm);
         let amp_seq = AmplifierPhaseSequence::new(perm);
         let amp_out = amp_seq.test_feedback_sequence(tape.clone());
         if amp_out > highest_signal {
@@ -146,7 +149,7 @@ pub fn do_part2(tape: Tape) {
         }
     }
 
-    println!("Part 2 answer: {}", highest_signal);
+    highest_signal
 }
 
 #[cfg(test)]
@@ -16,14 +16,23 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use anyhow::bail;
+use aoc_common::parsing::FromStrParser;
 use aoc_solution::Aoc;
 use core::fmt;
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Aoc)]
+#[aoc(input = Image)]
+#[aoc(parser = FromStrParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = String, runner = part2))]
 pub struct Day08;
 
 const TRANSPARENT_PIXEL: usize = 2;
+const IMAGE_HEIGHT: usize = 6;
+const IMAGE_WIDTH: usize = 25;
 
 #[derive(Clone)]
 pub struct Image {
@@ -76,6 +85,31 @@ impl Image {
     }
 }
 
+impl FromStr for Image {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut image = Image::new(IMAGE_HEIGHT, IMAGE_WIDTH);
+        let mut data = Vec::new();
+        for c in s.trim().chars() {
+            match c.to_digit(10) {
+                Some(digit) => data.push(digit as usize),
+                None => bail!("'{c}' is not a valid pixel"),
+            }
+        }
+        if data.is_empty() || data.len() % image.area() != 0 {
+            bail!(
+                "the image data can't be split into layers of {IMAGE_HEIGHT}x{IMAGE_WIDTH} pixels"
+            )
+        }
+
+        for layer_data in data.chunks(image.area()) {
+            image.add_layer(layer_data.to_vec());
+        }
+        Ok(image)
+    }
+}
+
 // for each height there's a vec of width data
 #[derive(Debug, PartialEq, Clone)]
 struct Layer(Vec<Vec<usize>>);
@@ -139,17 +173,14 @@ impl Layer {
     }
 }
 
-pub fn do_part2(image: Image) {
-    let visible_data = image.cover_all_layers();
-    println!("Part 2 answer: \n{}", visible_data);
-}
-
-pub fn do_part1(image: Image) {
+pub fn part1(image: Image) -> usize {
     let layer_id = image.layer_id_with_fewest_zeroes();
     let layer = image.layer_by_id(layer_id);
-    let x = layer.digit_count(1) * layer.digit_count(2);
+    layer.digit_count(1) * layer.digit_count(2)
+}
 
-    println!("Part 1 answer: {}", x);
+pub fn part2(image: Image) -> String {
+    image.cover_all_layers().to_string()
 }
 
 #[cfg(test)]
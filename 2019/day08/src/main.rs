@@ -12,31 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// legacy code
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use day08_2019::Day08;
 
-use day08_2019::{do_part1, do_part2, Image};
-use std::fs;
-
-fn read_input_file(path: &str) -> Vec<usize> {
-    fs::read_to_string(path)
-        .unwrap()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as usize)
-        .collect()
-}
-
-fn main() {
-    let mut image = Image::new(6, 25);
-
-    let input = read_input_file("inputs/2019/day08");
-    let layer_chunks = input.chunks(image.area());
-
-    for layer_data in layer_chunks {
-        image.add_layer(layer_data.to_vec());
-    }
-
-    do_part1(image.clone());
-    do_part2(image);
+#[cfg(not(tarpaulin_include))]
+fn main() -> anyhow::Result<()> {
+    Day08::try_solve_from_file(input_path(2019, 8)?);
+    Ok(())
 }
@@ -1,7 +1,9 @@
 use std::convert::TryFrom;
 use std::io::{BufRead, Write};
+use std::str::FromStr;
 
 use crate::utils;
+use aoc_common::parsing::parse_comma_separated_values;
 
 const ADD_OP_CODE: isize = 1;
 const MUL_OP_CODE: isize = 2;
@@ -224,6 +226,14 @@ impl Tape {
     }
 }
 
+impl FromStr for Tape {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_comma_separated_values(s).map(Tape::new)
+    }
+}
+
 #[derive(Debug)]
 pub enum IntcodeMachineError {
     TapeOutOfBoundsError,
@@ -17,42 +17,55 @@
 #![allow(clippy::expect_used)]
 
 use crate::intcode_machine::{IntcodeMachine, Tape};
+use aoc_common::parsing::FromStrParser;
 use aoc_solution::Aoc;
 
 pub mod intcode_machine;
 pub mod utils;
 
 #[derive(Aoc)]
+#[aoc(input = Tape)]
+#[aoc(parser = FromStrParser)]
+#[aoc(part1(output = isize, runner = part1))]
+#[aoc(part2(output = isize, runner = part2))]
 pub struct Day09;
 
-pub fn do_part1(tape: Tape) {
-    let fake_input = b"1";
+fn run_boost_program(tape: Tape, mode: &[u8]) -> isize {
     let mut output = Vec::new();
 
-    IntcodeMachine::new(tape, &fake_input[..], &mut output)
-        .run()
-        .unwrap();
+    IntcodeMachine::new(tape, mode, &mut output).run().unwrap();
 
-    let parsed_output = utils::parse_multiple_utf8_num_repr_lns(&output)
+    utils::parse_multiple_utf8_num_repr_lns(&output)
         .last()
         .unwrap()
-        .to_owned();
+        .to_owned()
+}
 
-    println!("{:?}", parsed_output);
+pub fn part1(tape: Tape) -> isize {
+    // test mode
+    run_boost_program(tape, b"1")
 }
 
-pub fn do_part2(tape: Tape) {
-    let fake_input = b"2";
-    let mut output = Vec::new();
+pub fn part2(tape: Tape) -> isize {
+    // sensor boost mode
+    run_boost_program(tape, b"2")
+}
 
-    IntcodeMachine::new(tape, &fake_input[..], &mut output)
-        .run()
-        .unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let parsed_output = utils::parse_multiple_utf8_num_repr_lns(&output)
-        .last()
-        .unwrap()
-        .to_owned();
+    #[test]
+    fn part1_large_number_output() {
+        let tape: Tape = "104,1125899906842624,99".parse().unwrap();
+        assert_eq!(1_125_899_906_842_624, part1(tape))
+    }
 
-    println!("{:?}", parsed_output);
+    #[test]
+    fn part1_quine_output() {
+        let tape: Tape = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99"
+            .parse()
+            .unwrap();
+        assert_eq!(99, part1(tape))
+    }
 }
@@ -12,16 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// legacy code
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use day09_2019::Day09;
 
-use day09_2019::intcode_machine::Tape;
-use day09_2019::{do_part1, do_part2, utils};
-
-fn main() {
-    let tape = Tape::new(utils::read_input_file("inputs/2019/day09"));
-
-    do_part1(tape.clone());
-    do_part2(tape);
+#[cfg(not(tarpaulin_include))]
+fn main() -> anyhow::Result<()> {
+    Day09::try_solve_from_file(input_path(2019, 9)?);
+    Ok(())
 }
@@ -83,11 +83,7 @@ fn egcd(a: isize, b: isize) -> (isize, isize, isize) {
 // code was originally adapted from https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust
 fn mod_inv(x: isize, n: isize) -> Option<isize> {
     let (g, x, _) = egcd(x, n);
-    if g == 1 {
-        Some((x % n + n) % n)
-    } else {
-        None
-    }
+    if g == 1 { Some((x % n + n) % n) } else { None }
 }
 
 // code was originally adapted from https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust
@@ -16,12 +16,17 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use aoc_common::parsing::LineParser;
 use aoc_solution::Aoc;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::ops::{Add, AddAssign};
 
 #[derive(Aoc)]
+#[aoc(input = Vec<String>)]
+#[aoc(parser = LineParser)]
+#[aoc(part1(output = usize, runner = part1, alt = [part1_par]))]
+#[aoc(part2(output = usize, runner = part2, alt = [part2_sequential]))]
 pub struct Day17;
 
 // Point contains list of values for each dimension
@@ -174,24 +179,30 @@ fn simulate_step(active_points: &mut HashSet<Point>) {
     }
 }
 
-pub fn part1(input: Vec<String>) -> usize {
-    let mut active_points = parse_initial_data(&input, 3);
+fn simulate(input: &[String], dims: usize, step: fn(&mut HashSet<Point>)) -> usize {
+    let mut active_points = parse_initial_data(input, dims);
 
     for _ in 0..NUM_CYCLES {
-        simulate_step(&mut active_points);
+        step(&mut active_points);
     }
 
     active_points.len()
 }
 
-pub fn part2(input: Vec<String>) -> usize {
-    let mut active_points = parse_initial_data(&input, 4);
+pub fn part1(input: Vec<String>) -> usize {
+    simulate(&input, 3, simulate_step)
+}
 
-    for _ in 0..NUM_CYCLES {
-        simulate_step_par(&mut active_points);
-    }
+pub fn part1_par(input: Vec<String>) -> usize {
+    simulate(&input, 3, simulate_step_par)
+}
 
-    active_points.len()
+pub fn part2(input: Vec<String>) -> usize {
+    simulate(&input, 4, simulate_step_par)
+}
+
+pub fn part2_sequential(input: Vec<String>) -> usize {
+    simulate(&input, 4, simulate_step)
 }
 
 #[cfg(test)]
@@ -204,7 +215,8 @@ mod tests {
 
         let expected = 112;
 
-        assert_eq!(expected, part1(input))
+        assert_eq!(expected, part1(input.clone()));
+        assert_eq!(expected, part1_par(input))
     }
 
     #[test]
@@ -213,6 +225,7 @@ mod tests {
 
         let expected = 848;
 
-        assert_eq!(expected, part2(input))
+        assert_eq!(expected, part2(input.clone()));
+        assert_eq!(expected, part2_sequential(input))
     }
 }
@@ -12,21 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// legacy code
-#![allow(clippy::unwrap_used)]
-#![allow(clippy::expect_used)]
-
-use aoc_common::legacy::input_read;
-use day17_2020::{part1, part2};
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use day17_2020::Day17;
 
 #[cfg(not(tarpaulin_include))]
-fn main() {
-    let input =
-        input_read::read_line_input("inputs/2020/day17").expect("failed to read input file");
-
-    let part1_result = part1(input.clone());
-    println!("Part 1 result is {}", part1_result);
-
-    let part2_result = part2(input);
-    println!("Part 2 result is {}", part2_result);
+fn main() -> anyhow::Result<()> {
+    Day17::try_solve_from_file(input_path(2020, 17)?);
+    Ok(())
 }
@@ -266,11 +266,7 @@ impl CrabGame {
 
     fn sub_one(&self, val: usize) -> usize {
         let res = val - 1;
-        if res == 0 {
-            self.buf.size
-        } else {
-            res
-        }
+        if res == 0 { self.buf.size } else { res }
     }
 
     fn select_destination_cup(&self) -> usize {
@@ -16,12 +16,17 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+use aoc_common::parsing::LineParser;
 use aoc_solution::Aoc;
 use day17_2020::Point;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 
 #[derive(Aoc)]
+#[aoc(input = Vec<String>)]
+#[aoc(parser = LineParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2, alt = [part2_sequential]))]
 pub struct Day24;
 
 const EAST: char = 'e';
@@ -98,7 +103,7 @@ impl Hexagon {
     }
 }
 
-pub fn part1(input: Vec<String>) -> usize {
+fn initial_black_tiles(input: &[String]) -> HashSet<Hexagon> {
     let mut active = HashSet::new();
     input.iter().map(Hexagon::from).for_each(|hex| {
         if active.contains(&hex) {
@@ -108,7 +113,11 @@ pub fn part1(input: Vec<String>) -> usize {
         }
     });
 
-    active.len()
+    active
+}
+
+pub fn part1(input: Vec<String>) -> usize {
+    initial_black_tiles(&input).len()
 }
 
 struct SimulatedHexagon {
@@ -153,23 +162,52 @@ fn simulate_step_par(active_hexes: &mut HashSet<Hexagon>) {
     }
 }
 
-pub fn part2(input: Vec<String>) -> usize {
-    let mut active = HashSet::new();
-    input.iter().map(Hexagon::from).for_each(|hex| {
-        if active.contains(&hex) {
-            active.remove(&hex);
-        } else {
-            active.insert(hex);
+fn simulate_step(active_hexes: &mut HashSet<Hexagon>) {
+    let mut adjacent_hexes = HashMap::new();
+    let mut hexes_to_deactivate = Vec::new();
+
+    for active_hexagon in active_hexes.iter() {
+        let mut active_neighbours = 0;
+        for neighbour in active_hexagon.adjacent_hexes() {
+            if active_hexes.contains(&neighbour) {
+                active_neighbours += 1;
+            }
+            *adjacent_hexes.entry(neighbour).or_insert(0) += 1;
         }
-    });
+        if active_neighbours == 0 || active_neighbours > 2 {
+            hexes_to_deactivate.push(active_hexagon.clone());
+        }
+    }
+
+    for deactivate in hexes_to_deactivate {
+        active_hexes.remove(&deactivate);
+    }
+
+    for (adjacent, count) in adjacent_hexes.into_iter() {
+        if count == 2 {
+            active_hexes.insert(adjacent);
+        }
+    }
+}
+
+fn simulate(input: &[String], step: fn(&mut HashSet<Hexagon>)) -> usize {
+    let mut active = initial_black_tiles(input);
 
     for _ in 0..DAYS_TO_SIMULATE {
-        simulate_step_par(&mut active);
+        step(&mut active);
     }
 
     active.len()
 }
 
+pub fn part2(input: Vec<String>) -> usize {
+    simulate(&input, simulate_step_par)
+}
+
+pub fn part2_sequential(input: Vec<String>) -> usize {
+    simulate(&input, simulate_step)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,6 +269,7 @@ mod tests {
 
         let expected = 2208;
 
-        assert_eq!(expected, part2(input))
+        assert_eq!(expected, part2(input.clone()));
+        assert_eq!(expected, part2_sequential(input))
     }
 }
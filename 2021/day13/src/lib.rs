@@ -16,7 +16,7 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
-use anyhow::{bail, Context};
+use anyhow::{Context, bail};
 use aoc_common::parsing::FromStrParser;
 use aoc_solution::Aoc;
 use std::collections::{BTreeSet, VecDeque};
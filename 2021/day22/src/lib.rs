@@ -21,7 +21,10 @@ use anyhow::Error;
 use aoc_common::legacy::parse_raw_range;
 use aoc_common::parsing::LineParser;
 use aoc_solution::Aoc;
+use aoc_solution::generator::InputGenerator;
 use itertools::iproduct;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use std::fmt::{Display, Formatter};
 use std::ops::RangeInclusive;
 use std::str::FromStr;
@@ -31,8 +34,41 @@ use std::str::FromStr;
 #[aoc(parser = LineParser)]
 #[aoc(part1(output = usize, runner = part1))]
 #[aoc(part2(output = usize, runner = part2))]
+#[aoc(generator)]
 pub struct Day22;
 
+impl InputGenerator for Day22 {
+    const REAL_SIZE: usize = 420;
+
+    // the size is the number of reboot steps. similarly to the real input,
+    // the first 20 are within the initialization area and the rest span the whole reactor
+    fn generate_input(size: usize, seed: u64) -> String {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let range = |rng: &mut ChaCha8Rng, i: usize| {
+            let (start, len) = if i < 20 {
+                (rng.random_range(-50..=40), rng.random_range(5..=50))
+            } else {
+                (
+                    rng.random_range(-100000..=80000),
+                    rng.random_range(5000..=60000),
+                )
+            };
+            format!("{start}..{}", start + len)
+        };
+
+        let mut steps = Vec::with_capacity(size);
+        for i in 0..size {
+            let x = range(&mut rng, i);
+            let y = range(&mut rng, i);
+            let z = range(&mut rng, i);
+            let on = i == 0 || rng.random_bool(0.75);
+            let state = if on { "on" } else { "off" };
+            steps.push(format!("{state} x={x},y={y},z={z}"))
+        }
+        steps.join("\n")
+    }
+}
+
 mod intersection;
 
 #[derive(Debug, Clone)]
@@ -233,6 +269,7 @@ pub fn part2(input: Vec<Step>) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_solution::AocSolution;
 
     #[test]
     fn cuboid_size() {
@@ -483,4 +520,14 @@ mod tests {
         let expected = 2758514936282235;
         assert_eq!(expected, part2(input))
     }
+
+    #[test]
+    fn generated_input() {
+        let raw = Day22::generate_input(50, 42);
+        assert_eq!(50, raw.lines().count());
+        assert_eq!(raw, Day22::generate_input(50, 42));
+
+        let input = Day22::parse_input(&raw).unwrap();
+        assert!(part1(input.clone()) <= part2(input));
+    }
 }
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day01_2022::Day01;
 
-define_aoc_benchmark!("inputs/2022/day01", Day01);
+define_aoc_benchmark!(2022, 1, Day01);
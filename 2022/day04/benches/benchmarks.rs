@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day04_2022::Day04;
 
-define_aoc_benchmark!("inputs/2022/day04", Day04);
+define_aoc_benchmark!(2022, 4, Day04);
@@ -176,7 +176,9 @@ impl FromStr for Supplies {
 
         let groups = split_to_string_groups(s);
         if groups.len() != 2 {
-            bail!("the provided input does not have exactly one stack of crates and one set of procedures")
+            bail!(
+                "the provided input does not have exactly one stack of crates and one set of procedures"
+            )
         }
 
         let stack_group = groups[0].lines().collect::<Vec<_>>();
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day06_2022::Day06;
 
-define_aoc_benchmark!("inputs/2022/day06", Day06);
+define_aoc_benchmark!(2022, 6, Day06);
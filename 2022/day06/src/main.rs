@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use aoc_common::helpers::root_path;
+use aoc_common::helpers::input_path;
 use aoc_solution::AocSolutionSolver;
 use day06_2022::Day06;
 
 #[cfg(not(tarpaulin_include))]
-fn main() {
-    Day06::try_solve_from_file(root_path("inputs/2022/day06"))
+fn main() -> anyhow::Result<()> {
+    Day06::try_solve_from_file(input_path(2022, 6)?);
+    Ok(())
 }
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day11_2022::Day11;
 
-define_aoc_benchmark!("inputs/2022/day11", Day11);
+define_aoc_benchmark!(2022, 11, Day11);
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day01_2023::Day01;
 
-define_aoc_benchmark!("inputs/2023/day01", Day01);
+define_aoc_benchmark!(2023, 1, Day01);
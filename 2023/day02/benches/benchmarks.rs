@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day02_2023::Day02;
 
-define_aoc_benchmark!("inputs/2023/day02", Day02);
+define_aoc_benchmark!(2023, 2, Day02);
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::types::{score, Scratchcard};
+use crate::types::{Scratchcard, score};
 use aoc_common::parsing::LineParser;
 use aoc_solution::Aoc;
 
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day05_2023::Day05;
 
-define_aoc_benchmark!("input/2023/day05", Day05);
+define_aoc_benchmark!(2023, 5, Day05);
@@ -14,8 +14,8 @@
 
 use anyhow::{anyhow, bail};
 use aoc_common::parsing::split_to_string_groups;
-use futures::stream::FuturesUnordered;
 use futures::StreamExt;
+use futures::stream::FuturesUnordered;
 use itertools::Itertools;
 use rayon::prelude::*;
 use std::ops::Range;
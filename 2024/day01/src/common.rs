@@ -16,9 +16,9 @@ use aoc_common::parsing::combinators::parse_number;
 use aoc_common::parsing::parse_input_lines;
 use std::collections::HashMap;
 use std::str::FromStr;
+use winnow::Parser;
 use winnow::ascii::multispace1;
 use winnow::combinator::separated_pair;
-use winnow::Parser;
 
 #[derive(Debug, Clone)]
 pub struct LocationLists {
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day02_2024::Day02;
 
-define_aoc_benchmark!("inputs/2024/day02", Day02);
+define_aoc_benchmark!(2024, 2, Day02);
@@ -15,9 +15,9 @@
 use aoc_common::parsing::combinators::parse_number;
 use itertools::Itertools;
 use std::str::FromStr;
+use winnow::Parser;
 use winnow::ascii::multispace1;
 use winnow::combinator::separated;
-use winnow::Parser;
 
 #[derive(Debug, Clone)]
 pub struct Report {
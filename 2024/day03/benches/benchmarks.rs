@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day03_2024::Day03;
 
-define_aoc_benchmark!("inputs/2024/day03", Day03);
+define_aoc_benchmark!(2024, 3, Day03);
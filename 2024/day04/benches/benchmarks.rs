@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day04_2024::Day04;
 
-define_aoc_benchmark!("inputs/2024/day04", Day04);
+define_aoc_benchmark!(2024, 4, Day04);
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day05_2024::Day05;
 
-define_aoc_benchmark!("inputs/2024/day05", Day05);
+define_aoc_benchmark!(2024, 5, Day05);
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day06_2024::Day06;
 
-define_aoc_benchmark!("inputs/2024/day06", Day06);
+define_aoc_benchmark!(2024, 6, Day06);
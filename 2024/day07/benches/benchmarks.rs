@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day07_2024::Day07;
 
-define_aoc_benchmark!("inputs/2024/day07", Day07);
+define_aoc_benchmark!(2024, 7, Day07);
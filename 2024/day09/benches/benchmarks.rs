@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day09_2024::Day09;
 
-define_aoc_benchmark!("inputs/2024/day09", Day09);
+define_aoc_benchmark!(2024, 9, Day09);
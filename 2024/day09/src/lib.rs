@@ -15,6 +15,9 @@
 use crate::common::DiskMap;
 use aoc_common::parsing::FromStrParser;
 use aoc_solution::Aoc;
+use aoc_solution::generator::InputGenerator;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 mod common;
 
@@ -23,8 +26,24 @@ mod common;
 #[aoc(parser = FromStrParser)]
 #[aoc(part1(output = usize, runner = part1))]
 #[aoc(part2(output = usize, runner = part2))]
+#[aoc(generator)]
 pub struct Day09;
 
+impl InputGenerator for Day09 {
+    const REAL_SIZE: usize = 19999;
+
+    // the size is the number of digits in the disk map, files are never empty
+    fn generate_input(size: usize, seed: u64) -> String {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (0..size)
+            .map(|i| {
+                let min = if i % 2 == 0 { 1 } else { 0 };
+                char::from(b'0' + rng.random_range(min..=9))
+            })
+            .collect()
+    }
+}
+
 pub fn part1(mut input: DiskMap) -> usize {
     input.defragment_blocks();
     input.checksum()
@@ -39,6 +58,7 @@ pub fn part2(mut input: DiskMap) -> usize {
 mod tests {
     use super::*;
     use crate::common::DiskMap;
+    use aoc_solution::AocSolution;
 
     fn sample_input() -> DiskMap {
         "2333133121414131402".parse().unwrap()
@@ -55,4 +75,17 @@ mod tests {
         let expected = 2858;
         assert_eq!(expected, part2(sample_input()));
     }
+
+    #[test]
+    fn generated_input() {
+        let raw = Day09::generate_input(101, 42);
+        assert_eq!(101, raw.len());
+        assert_eq!(raw, Day09::generate_input(101, 42));
+        // the seeded inputs must not change between the versions of the dependencies
+        assert_eq!("3629744672613328", Day09::generate_input(16, 42));
+
+        let input = Day09::parse_input(&raw).unwrap();
+        assert!(part1(input.clone()) > 0);
+        assert!(part2(input) > 0);
+    }
 }
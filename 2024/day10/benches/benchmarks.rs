@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day10_2024::Day10;
 
-define_aoc_benchmark!("inputs/2024/day10", Day10);
+define_aoc_benchmark!(2024, 10, Day10);
@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use aoc_common::helpers::root_path;
+use aoc_common::helpers::input_path;
 use aoc_solution::AocSolutionSolver;
 use day10_2024::Day10;
 
 #[cfg(not(tarpaulin_include))]
-fn main() {
-    Day10::try_solve_from_file(root_path("inputs/2024/day10"))
+fn main() -> anyhow::Result<()> {
+    Day10::try_solve_from_file(input_path(2024, 10)?);
+    Ok(())
 }
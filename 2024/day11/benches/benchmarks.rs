@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day11_2024::Day11;
 
-define_aoc_benchmark!("inputs/2024/day11", Day11);
+define_aoc_benchmark!(2024, 11, Day11);
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day01_2025::Day01;
 
-define_aoc_benchmark!("inputs/2025/day01", Day01);
+define_aoc_benchmark!(2025, 1, Day01);
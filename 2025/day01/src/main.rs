@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use aoc_common::helpers::root_path;
+use aoc_common::helpers::input_path;
 use aoc_solution::AocSolutionSolver;
 use day01_2025::Day01;
 
 #[cfg(not(tarpaulin_include))]
-fn main() {
-    Day01::try_solve_from_file(root_path("inputs/2025/day01"))
+fn main() -> anyhow::Result<()> {
+    Day01::try_solve_from_file(input_path(2025, 1)?);
+    Ok(())
 }
@@ -15,4 +15,4 @@
 use aoc_common::define_aoc_benchmark;
 use day04_2025::Day04;
 
-define_aoc_benchmark!("inputs/2025/day04", Day04);
+define_aoc_benchmark!(2025, 4, Day04);
@@ -15,6 +15,9 @@
 use crate::common::{PaperGrid, PaperGridExt};
 use aoc_common::parsing::FromStrParser;
 use aoc_solution::Aoc;
+use aoc_solution::generator::InputGenerator;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 mod common;
 
@@ -23,8 +26,28 @@ mod common;
 #[aoc(parser = FromStrParser)]
 #[aoc(part1(output = usize, runner = part1))]
 #[aoc(part2(output = usize, runner = part2))]
+#[aoc(generator)]
 pub struct Day04;
 
+impl InputGenerator for Day04 {
+    const REAL_SIZE: usize = 137;
+
+    // the size is the side length of the square grid
+    fn generate_input(size: usize, seed: u64) -> String {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut out = String::with_capacity(size * (size + 1));
+        for y in 0..size {
+            if y != 0 {
+                out.push('\n');
+            }
+            for _ in 0..size {
+                out.push(if rng.random_bool(0.65) { '@' } else { '.' });
+            }
+        }
+        out
+    }
+}
+
 pub fn part1(grid: PaperGrid) -> usize {
     grid.accessible_count()
 }
@@ -45,6 +68,7 @@ pub fn part2(mut grid: PaperGrid) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_solution::AocSolution;
 
     fn sample_input() -> PaperGrid {
         r#"..@@.@@@@.
@@ -72,4 +96,14 @@ mod tests {
         let expected = 43;
         assert_eq!(expected, part2(sample_input()))
     }
+
+    #[test]
+    fn generated_input() {
+        let raw = Day04::generate_input(20, 42);
+        assert_eq!(20, raw.lines().count());
+        assert_eq!(raw, Day04::generate_input(20, 42));
+
+        let grid = Day04::parse_input(&raw).unwrap();
+        assert!(part1(grid.clone()) <= part2(grid));
+    }
 }
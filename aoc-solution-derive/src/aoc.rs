@@ -16,7 +16,8 @@ use crate::ResultExt;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{ToTokens, quote};
 use syn::parse::{Parse, ParseStream};
-use syn::{Attribute, Error, Token, parenthesized};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Token, bracketed, parenthesized};
 
 fn unimplemented_inner(custom_err: bool, name: &str) -> TokenStream {
     // we're going to default to anyhow
@@ -47,6 +48,17 @@ impl AocContainer {
         }
     }
 
+    fn alternatives_impl(&self, part: Option<&AocPart>) -> TokenStream {
+        let alternatives = part.map(|p| p.alternatives.as_slice()).unwrap_or_default();
+        let entries = alternatives.iter().map(|alt| {
+            let name = alt.to_string();
+            let runner = self.wrap_runner(alt);
+            quote! { ::aoc_solution::NamedRunner::new(#name, |input| #runner) }
+        });
+
+        quote! { vec![#(#entries),*] }
+    }
+
     fn part1_impl(&self) -> TokenStream {
         if let Some(p1) = &self.attributes.part1
             && let Some(runner) = &p1.runner
@@ -133,6 +145,13 @@ impl ToTokens for AocContainer {
         let p2_ty = self.part2_output();
         let p1_impl = self.part1_impl();
         let p2_impl = self.part2_impl();
+        let p1_alternatives = self.alternatives_impl(self.attributes.part1.as_ref());
+        let p2_alternatives = self.alternatives_impl(self.attributes.part2.as_ref());
+        let generator_impl = if self.attributes.generator {
+            quote! { Some(::aoc_solution::generator::Generator::new::<Self>()) }
+        } else {
+            quote! { None }
+        };
 
         tokens.extend(quote! {
             impl ::aoc_solution::AocSolution for #ident {
@@ -152,6 +171,46 @@ impl ToTokens for AocContainer {
                 fn part2(input: Self::Input) -> Result<Self::Part2Output, Self::Error> {
                     #p2_impl
                 }
+
+                fn part1_alternatives() -> Vec<::aoc_solution::NamedRunner<Self::Input, Self::Part1Output, Self::Error>> {
+                    #p1_alternatives
+                }
+
+                fn part2_alternatives() -> Vec<::aoc_solution::NamedRunner<Self::Input, Self::Part2Output, Self::Error>> {
+                    #p2_alternatives
+                }
+
+                fn input_generator() -> Option<::aoc_solution::generator::Generator> {
+                    #generator_impl
+                }
+            }
+
+            // entry points used when the crate is built as a standalone `cdylib` plugin
+            #[cfg(aoc_plugin)]
+            #[unsafe(no_mangle)]
+            pub extern "C" fn aoc_plugin_abi_version() -> u32 {
+                ::aoc_solution::plugin::PLUGIN_ABI_VERSION
+            }
+
+            /// # Safety
+            ///
+            /// `input` must point to `len` bytes of valid UTF-8.
+            #[cfg(aoc_plugin)]
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn aoc_plugin_run(
+                input: *const u8,
+                len: usize,
+            ) -> ::aoc_solution::plugin::FfiDayResult {
+                unsafe { ::aoc_solution::plugin::plugin_run::<#ident>(input, len) }
+            }
+
+            /// # Safety
+            ///
+            /// The result must have been created by `aoc_plugin_run` and not yet freed.
+            #[cfg(aoc_plugin)]
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn aoc_plugin_free(result: ::aoc_solution::plugin::FfiDayResult) {
+                unsafe { ::aoc_solution::plugin::plugin_free(result) }
             }
         })
     }
@@ -160,7 +219,7 @@ impl ToTokens for AocContainer {
 impl Parse for AocAttr {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         const EXPECTED_ATTRIBUTE: &str =
-            "unexpected attribute, expected any of: parser, part1, part2";
+            "unexpected attribute, expected any of: input, parser, part1, part2, error, generator";
 
         let mut aocttr = AocAttr::default();
 
@@ -185,6 +244,7 @@ impl Parse for AocAttr {
                     input.parse::<Token![=]>()?;
                     aocttr.error_ty = Some(input.parse()?);
                 }
+                "generator" => aocttr.generator = true,
                 _ => {
                     return Err(Error::new(ident.span(), EXPECTED_ATTRIBUTE));
                 }
@@ -208,19 +268,24 @@ pub struct AocAttr {
 
     part1: Option<AocPart>,
     part2: Option<AocPart>,
+
+    // whether the solution implements `InputGenerator`
+    generator: bool,
 }
 
 #[derive(Debug, Clone, Default)]
 struct AocPart {
     output_ty: Option<syn::Type>,
     runner: Option<syn::Ident>,
+    alternatives: Vec<syn::Ident>,
 }
 
 impl Parse for AocPart {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let content;
         parenthesized!(content in input);
-        const EXPECTED_ATTRIBUTE: &str = "unexpected attribute. expected one of: output, runner";
+        const EXPECTED_ATTRIBUTE: &str =
+            "unexpected attribute. expected one of: output, runner, alt";
 
         let mut aoc_part = AocPart::default();
 
@@ -237,6 +302,14 @@ impl Parse for AocPart {
             match attribute {
                 "output" => aoc_part.output_ty = Some(content.parse()?),
                 "runner" => aoc_part.runner = Some(content.parse()?),
+                "alt" => {
+                    let alternatives;
+                    bracketed!(alternatives in content);
+                    aoc_part.alternatives =
+                        Punctuated::<Ident, Token![,]>::parse_terminated(&alternatives)?
+                            .into_iter()
+                            .collect();
+                }
                 _ => return Err(syn::Error::new(ident.span(), EXPECTED_ATTRIBUTE)),
             }
             if !content.is_empty() {
@@ -251,6 +324,13 @@ impl Parse for AocPart {
             ));
         }
 
+        if !aoc_part.alternatives.is_empty() && aoc_part.runner.is_none() {
+            return Err(Error::new(
+                Span::call_site(),
+                "could not use alternative runners without specifying the main runner",
+            ));
+        }
+
         Ok(aoc_part)
     }
 }
@@ -272,6 +352,7 @@ impl AocAttr {
         if other.error_ty.is_some() {
             self.error_ty = other.error_ty
         }
+        self.generator |= other.generator;
 
         self
     }
@@ -0,0 +1,49 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::AocSolution;
+
+/// Produces synthetic, but valid, puzzle inputs, so that solutions could be exercised without the private inputs.
+///
+/// To make it available to the runner and benchmarks, the solution has to be marked with `#[aoc(generator)]`.
+pub trait InputGenerator: AocSolution {
+    /// Size of the input that resembles the actual puzzle input.
+    const REAL_SIZE: usize;
+
+    /// Generates an input of the requested size, whose exact meaning depends on the puzzle,
+    /// e.g. number of lines or length of a grid side.
+    /// The same seed always results in the same input, so the random numbers should come from an algorithm
+    /// that is stable across releases, such as `rand_chacha::ChaCha8Rng`, rather than `rand::rngs::StdRng`.
+    fn generate_input(size: usize, seed: u64) -> String;
+}
+
+/// Type-erased `InputGenerator` of a particular solution.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub real_size: usize,
+    pub generate: fn(usize, u64) -> String,
+}
+
+impl Generator {
+    pub fn new<T: InputGenerator + ?Sized>() -> Self {
+        Generator {
+            real_size: T::REAL_SIZE,
+            generate: T::generate_input,
+        }
+    }
+
+    pub fn generate_real_size(&self, seed: u64) -> String {
+        (self.generate)(self.real_size, seed)
+    }
+}
@@ -12,18 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use humantime::format_duration;
+use crate::generator::Generator;
+use crate::summary::{DaySummary, PartSummary};
 use std::any::type_name;
 use std::fmt::{Display, Formatter};
 use std::path::Path;
 use std::time::{Duration, Instant};
 
+pub mod generator;
 pub mod parser;
+pub mod plugin;
+pub mod summary;
 
 extern crate aoc_derive;
 
 pub use aoc_derive::Aoc;
 
+/// Alternative implementation of a solution part, for example a naive version kept alongside
+/// an optimised one, that is expected to produce exactly the same answer as the main runner.
+pub struct NamedRunner<I, O, E> {
+    pub name: &'static str,
+    pub runner: fn(I) -> Result<O, E>,
+}
+
+impl<I, O, E> NamedRunner<I, O, E> {
+    pub fn new(name: &'static str, runner: fn(I) -> Result<O, E>) -> Self {
+        NamedRunner { name, runner }
+    }
+}
+
 pub trait AocSolution {
     type Input: Clone;
     type Error: Display;
@@ -33,12 +50,25 @@ pub trait AocSolution {
     fn parse_input(_raw: &str) -> Result<Self::Input, Self::Error>;
     fn part1(_input: Self::Input) -> Result<Self::Part1Output, Self::Error>;
     fn part2(_input: Self::Input) -> Result<Self::Part2Output, Self::Error>;
+
+    fn part1_alternatives() -> Vec<NamedRunner<Self::Input, Self::Part1Output, Self::Error>> {
+        Vec::new()
+    }
+
+    fn part2_alternatives() -> Vec<NamedRunner<Self::Input, Self::Part2Output, Self::Error>> {
+        Vec::new()
+    }
+
+    /// Returns the synthetic input generator, if the solution has one.
+    fn input_generator() -> Option<Generator> {
+        None
+    }
 }
 
 pub trait AocSolutionSolver: AocSolution {
     fn try_solve(raw_input: &str) {
         match run::<Self>(raw_input) {
-            Ok(result) => println!("{result}"),
+            Ok(result) => result.report(),
             Err(err) => eprintln!("failed to solve aoc for '{}': {err}", type_name::<Self>()),
         }
     }
@@ -48,7 +78,7 @@ pub trait AocSolutionSolver: AocSolution {
         P: AsRef<Path>,
     {
         match run_from_file::<Self, _>(path) {
-            Ok(result) => println!("{result}"),
+            Ok(result) => result.report(),
             Err(err) => eprintln!("failed to solve aoc for '{}': {err}", type_name::<Self>()),
         }
     }
@@ -60,35 +90,58 @@ pub struct DayResult<T: AocSolution + ?Sized> {
     parsing: Duration,
     part1: TimedResult<Result<T::Part1Output, T::Error>>,
     part2: TimedResult<Result<T::Part2Output, T::Error>>,
+    part1_alternatives: Vec<AlternativeResult<Result<T::Part1Output, T::Error>>>,
+    part2_alternatives: Vec<AlternativeResult<Result<T::Part2Output, T::Error>>>,
+}
+
+impl<T: AocSolution + ?Sized> DayResult<T> {
+    /// Formats all the answers, so that the result could be handled independently of the solution type.
+    pub fn summary(&self) -> DaySummary {
+        DaySummary {
+            parsing: self.parsing,
+            part1: PartSummary::new(&self.part1, &self.part1_alternatives),
+            part2: PartSummary::new(&self.part2, &self.part2_alternatives),
+        }
+    }
+
+    /// Prints the results, exiting with an error if any alternative runner disagrees with its main one,
+    /// as they're all supposed to compute exactly the same answers.
+    fn report(&self) {
+        let summary = self.summary();
+        summary.report(type_name::<T>());
+        if !summary.disagreements().is_empty() {
+            std::process::exit(1)
+        }
+    }
 }
 
 impl<T: AocSolution + ?Sized> Display for DayResult<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "# TIMING #")?;
-        writeln!(f, "PARSING:\t{}", format_duration(self.parsing))?;
-        writeln!(f, "PART 1:\t\t{}", format_duration(self.part1.taken))?;
-        writeln!(f, "PART 2:\t\t{}", format_duration(self.part2.taken))?;
-        writeln!(f)?;
-        writeln!(f, "# RESULTS #")?;
-        let display_p1 = match &self.part1.value {
-            Ok(res) => res.to_string(),
-            Err(err) => format!("failed to solve: {err}"),
-        };
-
-        let display_p2 = match &self.part2.value {
-            Ok(res) => res.to_string(),
-            Err(err) => format!("failed to solve: {err}"),
-        };
-
-        writeln!(f, "PART 1:\n{display_p1}")?;
-        writeln!(f)?;
-        writeln!(f, "PART 2:\n{display_p2}")
+        self.summary().fmt(f)
     }
 }
 
-struct TimedResult<T> {
-    taken: Duration,
-    value: T,
+pub(crate) struct TimedResult<T> {
+    pub(crate) taken: Duration,
+    pub(crate) value: T,
+}
+
+pub(crate) struct AlternativeResult<T> {
+    pub(crate) name: &'static str,
+    pub(crate) result: TimedResult<T>,
+}
+
+fn timed_alternatives<I: Clone, O, E>(
+    alternatives: Vec<NamedRunner<I, O, E>>,
+    input: &I,
+) -> Vec<AlternativeResult<Result<O, E>>> {
+    alternatives
+        .into_iter()
+        .map(|alt| AlternativeResult {
+            name: alt.name,
+            result: timed(alt.runner, input.clone()),
+        })
+        .collect()
 }
 
 impl<T, E> TimedResult<Result<T, E>> {
@@ -122,6 +175,17 @@ where
     run(&read_input)
 }
 
+/// Runs the solution and formats its results, so that they could be handled uniformly
+/// regardless of the underlying solution type.
+pub fn run_summarised<T>(input: &str) -> Result<DaySummary, String>
+where
+    T: AocSolution + ?Sized,
+{
+    run::<T>(input)
+        .map(|result| result.summary())
+        .map_err(|err| err.to_string())
+}
+
 pub fn run<T>(input: &str) -> Result<DayResult<T>, T::Error>
 where
     T: AocSolution + ?Sized,
@@ -129,11 +193,15 @@ where
     let parsed_input = timed(T::parse_input, input).transpose()?;
 
     let part1 = timed(T::part1, parsed_input.value.clone());
-    let part2 = timed(T::part2, parsed_input.value);
+    let part1_alternatives = timed_alternatives(T::part1_alternatives(), &parsed_input.value);
+    let part2 = timed(T::part2, parsed_input.value.clone());
+    let part2_alternatives = timed_alternatives(T::part2_alternatives(), &parsed_input.value);
 
     Ok(DayResult {
         parsing: parsed_input.taken,
         part1,
         part2,
+        part1_alternatives,
+        part2_alternatives,
     })
 }
@@ -0,0 +1,278 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stable C-ABI used for running solutions compiled as standalone `cdylib` plugins.
+//!
+//! The `Aoc` derive emits the exported entry points whenever the crate is compiled with
+//! `--cfg aoc_plugin`, for example:
+//!
+//! ```shell
+//! cargo rustc --release -p day05_2024 --lib --crate-type cdylib -- --cfg aoc_plugin
+//! ```
+
+use crate::summary::{AlternativeSummary, DaySummary, PartSummary};
+use crate::{AocSolution, run};
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+use std::time::Duration;
+
+/// Version of the plugin interface. Plugins reporting a different version are rejected by the runner.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+pub const ABI_VERSION_SYMBOL: &[u8] = b"aoc_plugin_abi_version\0";
+pub const RUN_SYMBOL: &[u8] = b"aoc_plugin_run\0";
+pub const FREE_SYMBOL: &[u8] = b"aoc_plugin_free\0";
+
+pub type AbiVersionFn = unsafe extern "C" fn() -> u32;
+pub type RunFn = unsafe extern "C" fn(input: *const u8, len: usize) -> FfiDayResult;
+pub type FreeFn = unsafe extern "C" fn(result: FfiDayResult);
+
+#[repr(C)]
+pub struct FfiAnswer {
+    pub taken_nanos: u64,
+    pub is_ok: bool,
+    pub value: *mut c_char,
+}
+
+#[repr(C)]
+pub struct FfiAlternative {
+    pub name: *mut c_char,
+    pub answer: FfiAnswer,
+}
+
+#[repr(C)]
+pub struct FfiPart {
+    pub main: FfiAnswer,
+    pub alternatives: *mut FfiAlternative,
+    pub alternatives_len: usize,
+}
+
+#[repr(C)]
+pub struct FfiDayResult {
+    pub parsing_nanos: u64,
+    /// Null if the input got parsed successfully.
+    pub error: *mut c_char,
+    pub part1: FfiPart,
+    pub part2: FfiPart,
+}
+
+fn into_raw_string(value: &str) -> *mut c_char {
+    // interior nul bytes would have truncated the string anyway
+    CString::new(value.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// # Safety
+///
+/// `value` must either be null or have been created by `into_raw_string`
+unsafe fn free_raw_string(value: *mut c_char) {
+    if !value.is_null() {
+        drop(unsafe { CString::from_raw(value) })
+    }
+}
+
+/// # Safety
+///
+/// `value` must be a valid, nul-terminated string
+unsafe fn copy_raw_string(value: *const c_char) -> String {
+    if value.is_null() {
+        return String::new();
+    }
+    unsafe { CStr::from_ptr(value) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn duration_nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl FfiAnswer {
+    fn new(taken: Duration, answer: &Result<String, String>) -> Self {
+        let (is_ok, value) = match answer {
+            Ok(value) => (true, value),
+            Err(err) => (false, err),
+        };
+        FfiAnswer {
+            taken_nanos: duration_nanos(taken),
+            is_ok,
+            value: into_raw_string(value),
+        }
+    }
+
+    unsafe fn to_answer(&self) -> Result<String, String> {
+        let value = unsafe { copy_raw_string(self.value) };
+        if self.is_ok { Ok(value) } else { Err(value) }
+    }
+
+    unsafe fn free(self) {
+        unsafe { free_raw_string(self.value) }
+    }
+}
+
+impl FfiPart {
+    fn new(part: &PartSummary) -> Self {
+        let alternatives = part
+            .alternatives
+            .iter()
+            .map(|alt| FfiAlternative {
+                name: into_raw_string(&alt.name),
+                answer: FfiAnswer::new(alt.taken, &alt.answer),
+            })
+            .collect::<Box<[_]>>();
+        let alternatives_len = alternatives.len();
+
+        FfiPart {
+            main: FfiAnswer::new(part.taken, &part.answer),
+            alternatives: Box::into_raw(alternatives).cast(),
+            alternatives_len,
+        }
+    }
+
+    fn empty(error: &str) -> Self {
+        FfiPart::new(&PartSummary {
+            taken: Duration::ZERO,
+            answer: Err(error.to_string()),
+            alternatives: Vec::new(),
+        })
+    }
+
+    unsafe fn alternatives(&self) -> &[FfiAlternative] {
+        if self.alternatives.is_null() {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.alternatives, self.alternatives_len) }
+    }
+
+    unsafe fn to_summary(&self) -> PartSummary {
+        unsafe {
+            PartSummary {
+                taken: Duration::from_nanos(self.main.taken_nanos),
+                answer: self.main.to_answer(),
+                alternatives: self
+                    .alternatives()
+                    .iter()
+                    .map(|alt| AlternativeSummary {
+                        name: copy_raw_string(alt.name),
+                        taken: Duration::from_nanos(alt.answer.taken_nanos),
+                        answer: alt.answer.to_answer(),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    unsafe fn free(self) {
+        unsafe {
+            self.main.free();
+            if self.alternatives.is_null() {
+                return;
+            }
+            let alternatives = Box::from_raw(ptr::slice_from_raw_parts_mut(
+                self.alternatives,
+                self.alternatives_len,
+            ));
+            for alt in alternatives.into_vec() {
+                free_raw_string(alt.name);
+                alt.answer.free();
+            }
+        }
+    }
+}
+
+impl FfiDayResult {
+    fn new(summary: &DaySummary) -> Self {
+        FfiDayResult {
+            parsing_nanos: duration_nanos(summary.parsing),
+            error: ptr::null_mut(),
+            part1: FfiPart::new(&summary.part1),
+            part2: FfiPart::new(&summary.part2),
+        }
+    }
+
+    fn failure(error: &str) -> Self {
+        FfiDayResult {
+            parsing_nanos: 0,
+            error: into_raw_string(error),
+            part1: FfiPart::empty(error),
+            part2: FfiPart::empty(error),
+        }
+    }
+
+    /// Copies the result into owned memory, so that the plugin could release its own allocations.
+    ///
+    /// # Safety
+    ///
+    /// The result must have been created by `plugin_run` and not yet freed.
+    pub unsafe fn to_summary(&self) -> Result<DaySummary, String> {
+        unsafe {
+            if !self.error.is_null() {
+                return Err(copy_raw_string(self.error));
+            }
+
+            Ok(DaySummary {
+                parsing: Duration::from_nanos(self.parsing_nanos),
+                part1: self.part1.to_summary(),
+                part2: self.part2.to_summary(),
+            })
+        }
+    }
+}
+
+/// Extracts the message the code has panicked with, as long as it's a string.
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs the solution against the provided input. Used by the `aoc_plugin_run` entry point.
+///
+/// # Safety
+///
+/// `input` must point to `len` bytes of valid UTF-8.
+pub unsafe fn plugin_run<T: AocSolution + ?Sized>(input: *const u8, len: usize) -> FfiDayResult {
+    let raw = unsafe { std::slice::from_raw_parts(input, len) };
+    let Ok(raw) = std::str::from_utf8(raw) else {
+        return FfiDayResult::failure("the provided input is not valid UTF-8");
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| run::<T>(raw))) {
+        Ok(Ok(result)) => FfiDayResult::new(&result.summary()),
+        Ok(Err(err)) => FfiDayResult::failure(&format!("failed to parse the input: {err}")),
+        Err(payload) => FfiDayResult::failure(&format!(
+            "the solution has panicked: {}",
+            panic_message(payload.as_ref())
+        )),
+    }
+}
+
+/// Releases all memory associated with the result. Used by the `aoc_plugin_free` entry point.
+///
+/// # Safety
+///
+/// The result must have been created by `plugin_run` within the same plugin and not yet freed.
+pub unsafe fn plugin_free(result: FfiDayResult) {
+    unsafe {
+        free_raw_string(result.error);
+        result.part1.free();
+        result.part2.free();
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{AlternativeResult, TimedResult};
+use humantime::format_duration;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Result of running a solution with all of its answers already formatted,
+/// so that it no longer depends on the concrete `AocSolution` type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySummary {
+    pub parsing: Duration,
+    pub part1: PartSummary,
+    pub part2: PartSummary,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartSummary {
+    pub taken: Duration,
+    pub answer: Result<String, String>,
+    pub alternatives: Vec<AlternativeSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlternativeSummary {
+    pub name: String,
+    pub taken: Duration,
+    pub answer: Result<String, String>,
+}
+
+fn format_answer<T: Display, E: Display>(result: &Result<T, E>) -> Result<String, String> {
+    match result {
+        Ok(res) => Ok(res.to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn display_answer(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(res) => res.clone(),
+        Err(err) => format!("failed to solve: {err}"),
+    }
+}
+
+impl PartSummary {
+    pub(crate) fn new<T: Display, E: Display>(
+        main: &TimedResult<Result<T, E>>,
+        alternatives: &[AlternativeResult<Result<T, E>>],
+    ) -> Self {
+        PartSummary {
+            taken: main.taken,
+            answer: format_answer(&main.value),
+            alternatives: alternatives
+                .iter()
+                .map(|alt| AlternativeSummary {
+                    name: alt.name.to_string(),
+                    taken: alt.result.taken,
+                    answer: format_answer(&alt.result.value),
+                })
+                .collect(),
+        }
+    }
+
+    fn disagreements(&self, part: u8) -> impl Iterator<Item = String> + '_ {
+        self.alternatives
+            .iter()
+            .filter(|alt| alt.answer != self.answer)
+            .map(move |alt| {
+                format!(
+                    "part {part} runner '{}' returned '{}' instead of '{}'",
+                    alt.name,
+                    display_answer(&alt.answer),
+                    display_answer(&self.answer)
+                )
+            })
+    }
+}
+
+impl DaySummary {
+    /// Returns descriptions of all alternative runners whose answers differ from their main runner.
+    pub fn disagreements(&self) -> Vec<String> {
+        self.part1
+            .disagreements(1)
+            .chain(self.part2.disagreements(2))
+            .collect()
+    }
+
+    /// Prints the results alongside any disagreements between the part runners.
+    pub fn report(&self, solver: &str) {
+        println!("{self}");
+        for disagreement in self.disagreements() {
+            eprintln!("'{solver}' variants disagree: {disagreement}")
+        }
+    }
+}
+
+impl Display for DaySummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# TIMING #")?;
+        writeln!(f, "PARSING:\t{}", format_duration(self.parsing))?;
+        writeln!(f, "PART 1:\t\t{}", format_duration(self.part1.taken))?;
+        for alt in &self.part1.alternatives {
+            writeln!(f, "  {}:\t{}", alt.name, format_duration(alt.taken))?;
+        }
+        writeln!(f, "PART 2:\t\t{}", format_duration(self.part2.taken))?;
+        for alt in &self.part2.alternatives {
+            writeln!(f, "  {}:\t{}", alt.name, format_duration(alt.taken))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "# RESULTS #")?;
+        writeln!(f, "PART 1:\n{}", display_answer(&self.part1.answer))?;
+        writeln!(f)?;
+        writeln!(f, "PART 2:\n{}", display_answer(&self.part2.answer))
+    }
+}
@@ -0,0 +1,350 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            other => anyhow::bail!("{other} is not a valid puzzle part"),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Response of the site to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl FromStr for Feedback {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Feedback::Correct),
+            "wrong" => Ok(Feedback::Wrong),
+            "too-high" => Ok(Feedback::TooHigh),
+            "too-low" => Ok(Feedback::TooLow),
+            other => bail!(
+                "'{other}' is not a valid feedback, expected 'correct', 'wrong', 'too-high' or 'too-low'"
+            ),
+        }
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Feedback::Correct => write!(f, "correct"),
+            Feedback::Wrong => write!(f, "wrong"),
+            Feedback::TooHigh => write!(f, "too high"),
+            Feedback::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+/// Reason for which a computed answer is known to be incorrect, before ever submitting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswerWarning {
+    KnownWrong,
+    TooHigh { bound: i128 },
+    TooLow { bound: i128 },
+}
+
+impl Display for AnswerWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerWarning::KnownWrong => write!(f, "it has already been rejected"),
+            AnswerWarning::TooHigh { bound } => {
+                write!(f, "it's not lower than {bound}, which was too high")
+            }
+            AnswerWarning::TooLow { bound } => {
+                write!(f, "it's not higher than {bound}, which was too low")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartAnswers {
+    /// The accepted answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correct: Option<String>,
+
+    /// All the rejected answers
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub wrong: BTreeSet<String>,
+
+    /// The lowest answer that was too high
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub too_high: Option<i128>,
+
+    /// The highest answer that was too low
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub too_low: Option<i128>,
+}
+
+impl PartAnswers {
+    fn is_empty(&self) -> bool {
+        self.correct.is_none()
+            && self.wrong.is_empty()
+            && self.too_high.is_none()
+            && self.too_low.is_none()
+    }
+
+    /// Updates the known answers with the response to the submitted answer.
+    pub fn record(&mut self, answer: &str, feedback: Feedback) -> anyhow::Result<()> {
+        let answer = answer.trim();
+        let numeric = || {
+            answer
+                .parse::<i128>()
+                .with_context(|| format!("'{answer}' is not a number, so it can't be {feedback}"))
+        };
+
+        match feedback {
+            Feedback::Correct => {
+                self.correct = Some(answer.to_string());
+                return Ok(());
+            }
+            Feedback::Wrong => {}
+            Feedback::TooHigh => {
+                let value = numeric()?;
+                self.too_high = Some(self.too_high.map_or(value, |bound| bound.min(value)));
+            }
+            Feedback::TooLow => {
+                let value = numeric()?;
+                self.too_low = Some(self.too_low.map_or(value, |bound| bound.max(value)));
+            }
+        }
+        self.wrong.insert(answer.to_string());
+        Ok(())
+    }
+
+    /// Checks the answer against the rejected ones and the bounds learned from them.
+    pub fn check(&self, answer: &str) -> Option<AnswerWarning> {
+        let answer = answer.trim();
+        if self.wrong.contains(answer) {
+            return Some(AnswerWarning::KnownWrong);
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        if let Some(bound) = self.too_high
+            && value >= bound
+        {
+            return Some(AnswerWarning::TooHigh { bound });
+        }
+        if let Some(bound) = self.too_low
+            && value <= bound
+        {
+            return Some(AnswerWarning::TooLow { bound });
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(default, skip_serializing_if = "PartAnswers::is_empty")]
+    pub part1: PartAnswers,
+    #[serde(default, skip_serializing_if = "PartAnswers::is_empty")]
+    pub part2: PartAnswers,
+}
+
+impl DayAnswers {
+    pub fn part(&self, part: Part) -> &PartAnswers {
+        match part {
+            Part::One => &self.part1,
+            Part::Two => &self.part2,
+        }
+    }
+
+    pub fn part_mut(&mut self, part: Part) -> &mut PartAnswers {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+/// Answers of every profile, i.e. AoC account, keyed by the profile name, year and day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnswersStore {
+    profiles: BTreeMap<String, BTreeMap<u16, BTreeMap<u8, DayAnswers>>>,
+}
+
+impl AnswersStore {
+    /// Loads the store from the provided file, or creates an empty one if the file doesn't exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(AnswersStore::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("{} is not a valid answers store", path.display()))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    pub fn day(&self, profile: &str, year: u16, day: u8) -> Option<&DayAnswers> {
+        self.profiles.get(profile)?.get(&year)?.get(&day)
+    }
+
+    pub fn day_mut(&mut self, profile: &str, year: u16, day: u8) -> &mut DayAnswers {
+        self.profiles
+            .entry(profile.to_string())
+            .or_default()
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default()
+    }
+
+    pub fn correct_answer(&self, profile: &str, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.day(profile, year, day)?.part(part).correct.as_deref()
+    }
+
+    pub fn record_correct(&mut self, profile: &str, year: u16, day: u8, part: Part, answer: &str) {
+        self.day_mut(profile, year, day).part_mut(part).correct = Some(answer.to_string())
+    }
+
+    pub fn record_feedback(
+        &mut self,
+        profile: &str,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        feedback: Feedback,
+    ) -> anyhow::Result<()> {
+        self.day_mut(profile, year, day)
+            .part_mut(part)
+            .record(answer, feedback)
+    }
+
+    pub fn check_answer(
+        &self,
+        profile: &str,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Option<AnswerWarning> {
+        self.day(profile, year, day)?.part(part).check(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_roundtrip() {
+        let mut store = AnswersStore::default();
+        store.record_correct(DEFAULT_PROFILE, 2024, 5, Part::One, "143");
+        store.record_correct("other", 2024, 5, Part::Two, "123");
+
+        let serialised = serde_json::to_string(&store).unwrap();
+        assert_eq!(
+            r#"{"default":{"2024":{"5":{"part1":{"correct":"143"}}}},"other":{"2024":{"5":{"part2":{"correct":"123"}}}}}"#,
+            serialised
+        );
+
+        let recovered: AnswersStore = serde_json::from_str(&serialised).unwrap();
+        assert_eq!(store, recovered);
+        assert_eq!(
+            Some("143"),
+            recovered.correct_answer(DEFAULT_PROFILE, 2024, 5, Part::One)
+        );
+        assert_eq!(
+            None,
+            recovered.correct_answer(DEFAULT_PROFILE, 2024, 5, Part::Two)
+        );
+        assert_eq!(
+            Some("123"),
+            recovered.correct_answer("other", 2024, 5, Part::Two)
+        );
+    }
+
+    #[test]
+    fn learning_from_rejections() {
+        let mut answers = PartAnswers::default();
+        answers.record("1000", Feedback::TooHigh).unwrap();
+        answers.record("800", Feedback::TooHigh).unwrap();
+        answers.record("1200", Feedback::TooHigh).unwrap();
+        answers.record("100", Feedback::TooLow).unwrap();
+        answers.record("555", Feedback::Wrong).unwrap();
+        assert!(answers.record("abc", Feedback::TooLow).is_err());
+
+        assert_eq!(Some(800), answers.too_high);
+        assert_eq!(Some(100), answers.too_low);
+        assert_eq!(Some(AnswerWarning::KnownWrong), answers.check("555"));
+        assert_eq!(Some(AnswerWarning::KnownWrong), answers.check("1200"));
+        assert_eq!(
+            Some(AnswerWarning::TooHigh { bound: 800 }),
+            answers.check("900")
+        );
+        assert_eq!(
+            Some(AnswerWarning::TooLow { bound: 100 }),
+            answers.check("50")
+        );
+        assert_eq!(None, answers.check("556"));
+        assert_eq!(None, answers.check("ABCDEF"));
+
+        let serialised = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            r#"{"wrong":["100","1000","1200","555","800"],"too_high":800,"too_low":100}"#,
+            serialised
+        );
+    }
+}
@@ -12,22 +12,64 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::helpers::input_path;
+use anyhow::Context;
+use aoc_solution::generator::Generator;
+use std::fs;
+
+/// Environmental variable that, when set to a size, makes the benchmarks use generated inputs
+/// instead of the configured ones.
+pub const GENERATED_SIZE_ENV: &str = "AOC_BENCH_GENERATED";
+/// Optional seed of the generated benchmark inputs.
+pub const GENERATED_SEED_ENV: &str = "AOC_BENCH_SEED";
+
+/// Reads the raw benchmark input, either from the configured input of the day or from the generator if requested.
+pub fn benchmark_input(year: u16, day: u8, generator: Option<Generator>) -> anyhow::Result<String> {
+    let Ok(size) = std::env::var(GENERATED_SIZE_ENV) else {
+        let path = input_path(year, day)?;
+        return fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()));
+    };
+
+    let Some(generator) = generator else {
+        anyhow::bail!(
+            "{GENERATED_SIZE_ENV} is set, but the solution does not have an input generator"
+        )
+    };
+    let size = if size.is_empty() || size == "real" {
+        generator.real_size
+    } else {
+        size.parse()?
+    };
+    let seed = match std::env::var(GENERATED_SEED_ENV) {
+        Ok(seed) => seed.parse()?,
+        Err(_) => 0,
+    };
+    Ok((generator.generate)(size, seed))
+}
+
 #[macro_export]
 macro_rules! define_aoc_benchmark {
-    ($input: literal, $typ: ty) => {
+    ($year: literal, $day: literal, $typ: ty) => {
         use ::aoc_solution::AocSolution;
 
-        use aoc_common::helpers::root_path;
-        use aoc_common::input_read::read_input;
-        use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
-        use std::fs;
+        use criterion::{BatchSize, Criterion, criterion_group};
+
+        fn raw_input() -> String {
+            aoc_common::benchmark::benchmark_input(
+                $year,
+                $day,
+                <$typ as AocSolution>::input_generator(),
+            )
+            .unwrap()
+        }
 
         fn get_input() -> <$typ as AocSolution>::Input {
-            read_input(root_path($input), <$typ as AocSolution>::parse_input).unwrap()
+            <$typ as AocSolution>::parse_input(&raw_input()).unwrap()
         }
 
         fn input_parse_benchmark(c: &mut Criterion) {
-            let input = fs::read_to_string(root_path($input)).unwrap();
+            let input = raw_input();
             let bench_name = format!("{}_input_parse", env!("CARGO_PKG_NAME"));
             c.bench_function(&bench_name, move |b| {
                 b.iter(|| {
@@ -39,25 +81,47 @@ macro_rules! define_aoc_benchmark {
         fn part1_benchmark(c: &mut Criterion) {
             let input = get_input();
             let bench_name = format!("{}_part1", env!("CARGO_PKG_NAME"));
-            c.bench_function(&bench_name, move |b| {
+            c.bench_function(&bench_name, |b| {
                 b.iter_batched(
                     || input.clone(),
                     |input| <$typ as AocSolution>::part1((std::hint::black_box(input))),
                     BatchSize::SmallInput,
                 )
             });
+
+            for alt in <$typ as AocSolution>::part1_alternatives() {
+                let bench_name = format!("{}_part1_{}", env!("CARGO_PKG_NAME"), alt.name);
+                c.bench_function(&bench_name, |b| {
+                    b.iter_batched(
+                        || input.clone(),
+                        |input| (alt.runner)(std::hint::black_box(input)),
+                        BatchSize::SmallInput,
+                    )
+                });
+            }
         }
 
         fn part2_benchmark(c: &mut Criterion) {
             let input = get_input();
             let bench_name = format!("{}_part2", env!("CARGO_PKG_NAME"));
-            c.bench_function(&bench_name, move |b| {
+            c.bench_function(&bench_name, |b| {
                 b.iter_batched(
                     || input.clone(),
                     |input| <$typ as AocSolution>::part2((std::hint::black_box(input))),
                     BatchSize::SmallInput,
                 )
             });
+
+            for alt in <$typ as AocSolution>::part2_alternatives() {
+                let bench_name = format!("{}_part2_{}", env!("CARGO_PKG_NAME"), alt.name);
+                c.bench_function(&bench_name, |b| {
+                    b.iter_batched(
+                        || input.clone(),
+                        |input| (alt.runner)(std::hint::black_box(input)),
+                        BatchSize::SmallInput,
+                    )
+                });
+            }
         }
 
         criterion_group!(
@@ -66,6 +130,16 @@ macro_rules! define_aoc_benchmark {
             part1_benchmark,
             part2_benchmark
         );
-        criterion_main!(benches);
+
+        // equivalent of `criterion_main!`, unless the scaling mode has been requested
+        fn main() {
+            if let Ok(sizes) = std::env::var(aoc_common::scaling::SCALING_ENV) {
+                aoc_common::scaling::run_scaling::<$typ>(&sizes).unwrap();
+                return;
+            }
+
+            benches();
+            Criterion::default().configure_from_args().final_summary();
+        }
     };
 }
@@ -0,0 +1,291 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime configuration shared by the runner and the tools, loaded from `aoc.toml`.
+//! The file is either pointed to by `AOC_CONFIG` or discovered by walking up from the current directory.
+//! All relative paths are resolved against the directory containing the file.
+
+use crate::answers::DEFAULT_PROFILE;
+use crate::helpers::COMMON_ROOT;
+use crate::profiles::{
+    self, ANSWERS_FILE, EXAMPLES_DIRECTORY, INPUTS_DIRECTORY, PUZZLES_DIRECTORY,
+};
+use anyhow::{Context, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            other => bail!("'{other}' is not a valid output format, expected 'text' or 'json'"),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    /// Directory with the inputs of the profile
+    pub inputs: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Directory with the inputs of the default profile, and of all other profiles that are not configured explicitly
+    pub inputs: PathBuf,
+
+    /// Store of the answers of all the profiles. Defaults to `answers.json` in the inputs directory.
+    pub answers: Option<PathBuf>,
+
+    /// Year used whenever it's not explicitly specified
+    pub default_year: Option<u16>,
+
+    pub output: OutputFormat,
+
+    pub profiles: BTreeMap<String, ProfileConfig>,
+
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            inputs: PathBuf::from(INPUTS_DIRECTORY),
+            answers: None,
+            default_year: None,
+            output: OutputFormat::default(),
+            profiles: BTreeMap::new(),
+            root: fallback_root(),
+        }
+    }
+}
+
+// used whenever there's no config file, which keeps the binaries working in the checkout they were built in
+fn fallback_root() -> PathBuf {
+    let compile_time_root = Path::new(COMMON_ROOT).join("..");
+    if compile_time_root.is_dir() {
+        compile_time_root
+    } else {
+        std::env::current_dir().unwrap_or_default()
+    }
+}
+
+/// Finds the closest config file in the provided directory or any of its ancestors.
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Location of the config file, either from `AOC_CONFIG` or discovered from the current directory.
+pub fn config_file() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(CONFIG_ENV) {
+        return Some(std::path::absolute(&path).unwrap_or_else(|_| PathBuf::from(path)));
+    }
+    discover(&std::env::current_dir().ok()?)
+}
+
+/// The project root, i.e. the directory containing the config file.
+pub fn project_root() -> &'static Path {
+    static ROOT: OnceLock<PathBuf> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        config_file()
+            .and_then(|file| file.parent().map(Path::to_path_buf))
+            .unwrap_or_else(fallback_root)
+    })
+}
+
+impl Config {
+    /// Loads the config from `AOC_CONFIG` or the closest `aoc.toml`, or uses the defaults if there's none.
+    pub fn load() -> anyhow::Result<Self> {
+        match config_file() {
+            Some(path) => Config::from_file(path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Loads the config closest to the provided directory, or uses the defaults rooted at it if there's none.
+    pub fn load_from(start: &Path) -> anyhow::Result<Self> {
+        match discover(start) {
+            Some(path) => Config::from_file(path),
+            None => Ok(Config {
+                root: start.to_path_buf(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let root = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        Config::parse(&content, root)
+            .with_context(|| format!("{} is not a valid config file", path.display()))
+    }
+
+    pub fn parse(content: &str, root: PathBuf) -> anyhow::Result<Self> {
+        let config: Config = toml::from_str(content)?;
+        Ok(Config { root, ..config })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolves the path relative to the directory of the config file
+    pub fn resolve<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.root.join(path)
+    }
+
+    pub fn inputs_dir(&self) -> PathBuf {
+        self.resolve(&self.inputs)
+    }
+
+    pub fn profile_inputs_dir(&self, profile: &str) -> PathBuf {
+        if let Some(configured) = self.profiles.get(profile) {
+            return self.resolve(&configured.inputs);
+        }
+        if profile == DEFAULT_PROFILE {
+            self.inputs_dir()
+        } else {
+            self.inputs_dir().join(profile)
+        }
+    }
+
+    pub fn input_path(&self, profile: &str, year: u16, day: u8) -> PathBuf {
+        profiles::day_path(self.profile_inputs_dir(profile), year, day)
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        match &self.answers {
+            Some(answers) => self.resolve(answers),
+            None => self.inputs_dir().join(ANSWERS_FILE),
+        }
+    }
+
+    /// Directory with the example inputs of the particular day, i.e. `examples/<year>/dayXX`
+    pub fn examples_path(&self, year: u16, day: u8) -> PathBuf {
+        profiles::day_path(self.resolve(EXAMPLES_DIRECTORY), year, day)
+    }
+
+    /// Archived description of the particular puzzle, i.e. `puzzles/<year>/dayXX.md`
+    pub fn puzzle_path(&self, year: u16, day: u8) -> PathBuf {
+        profiles::day_path(self.resolve(PUZZLES_DIRECTORY), year, day).with_extension("md")
+    }
+
+    /// Lists all profiles with inputs, including the explicitly configured ones.
+    pub fn discover_profiles(&self) -> Vec<String> {
+        let mut discovered = profiles::discover_profiles(&self.inputs_dir());
+        for (name, profile) in &self.profiles {
+            if !discovered.contains(name) && self.resolve(&profile.inputs).is_dir() {
+                discovered.push(name.clone())
+            }
+        }
+        discovered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_config() {
+        let content = r#"
+inputs = "../private/inputs"
+default_year = 2025
+output = "json"
+
+[profiles.alice]
+inputs = "/home/alice/aoc"
+"#;
+        let config = Config::parse(content, PathBuf::from("/aoc")).unwrap();
+        assert_eq!(Some(2025), config.default_year);
+        assert_eq!(OutputFormat::Json, config.output);
+        assert_eq!(
+            PathBuf::from("/aoc/../private/inputs/2024/day05"),
+            config.input_path(DEFAULT_PROFILE, 2024, 5)
+        );
+        assert_eq!(
+            PathBuf::from("/aoc/../private/inputs/bob/2024/day05"),
+            config.input_path("bob", 2024, 5)
+        );
+        assert_eq!(
+            PathBuf::from("/home/alice/aoc/2024/day05"),
+            config.input_path("alice", 2024, 5)
+        );
+        assert_eq!(
+            PathBuf::from("/aoc/../private/inputs/answers.json"),
+            config.answers_path()
+        );
+        assert_eq!(
+            PathBuf::from("/aoc/examples/2024/day05"),
+            config.examples_path(2024, 5)
+        );
+        assert_eq!(
+            PathBuf::from("/aoc/puzzles/2024/day05.md"),
+            config.puzzle_path(2024, 5)
+        );
+
+        assert!(Config::parse("unknown = 1", PathBuf::new()).is_err());
+        assert!(Config::parse(r#"output = "xml""#, PathBuf::new()).is_err());
+    }
+
+    #[test]
+    fn defaults() {
+        let config = Config::parse("", PathBuf::from("/aoc")).unwrap();
+        assert_eq!(Config::default().inputs, config.inputs);
+        assert_eq!(
+            PathBuf::from("/aoc/inputs/2024/day05"),
+            config.input_path(DEFAULT_PROFILE, 2024, 5)
+        );
+        assert_eq!(
+            PathBuf::from("/aoc/inputs/answers.json"),
+            config.answers_path()
+        );
+    }
+}
@@ -12,14 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::answers::DEFAULT_PROFILE;
+use crate::config::Config;
 use std::path::{Path, PathBuf};
 
 pub const COMMON_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 
-/// Creates a path relative to the project root
+/// Creates a path relative to the project root, i.e. the directory of the `aoc.toml` config.
+/// If there's no config, the location of the checkout the project was built in is used instead.
 pub fn root_path<P: AsRef<Path>>(segment: P) -> PathBuf {
-    // hehe, that's a disgusting hack, but hey, it works
-    PathBuf::from(COMMON_ROOT).join("..").join(segment)
+    crate::config::project_root().join(segment)
+}
+
+/// Location of the input of the particular day for the default profile, as configured in `aoc.toml`.
+pub fn input_path(year: u16, day: u8) -> anyhow::Result<PathBuf> {
+    Ok(Config::load()?.input_path(DEFAULT_PROFILE, year, day))
 }
 
 pub trait Digits {
@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod answers;
 pub mod benchmark;
+pub mod config;
 pub mod constants;
 pub mod helpers;
 pub mod input_read;
 pub mod parsing;
+pub mod profiles;
+pub mod scaling;
 pub mod types;
 
 // to help with import of 2019-2021 solutions;
@@ -0,0 +1,91 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inputs of different AoC accounts, i.e. profiles, live in `inputs/<profile>/<year>/dayXX`,
+//! apart from the default profile, which uses `inputs/<year>/dayXX`.
+//! The location of the inputs directory itself is determined by the [config](crate::config).
+
+use crate::answers::DEFAULT_PROFILE;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INPUTS_DIRECTORY: &str = "inputs";
+pub const ANSWERS_FILE: &str = "answers.json";
+pub const EXAMPLES_DIRECTORY: &str = "examples";
+pub const PUZZLES_DIRECTORY: &str = "puzzles";
+
+fn is_year_directory(name: &str) -> bool {
+    name.len() == 4 && name.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Path of the particular day within the directory, i.e. `<directory>/<year>/dayXX`
+pub fn day_path<P: AsRef<Path>>(directory: P, year: u16, day: u8) -> PathBuf {
+    directory
+        .as_ref()
+        .join(year.to_string())
+        .join(format!("day{day:02}"))
+}
+
+/// Lists all profiles with at least a single event directory within the inputs directory.
+pub fn discover_profiles(inputs: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(inputs) else {
+        return Vec::new();
+    };
+
+    let mut has_default = false;
+    let mut profiles = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if is_year_directory(&name) {
+            has_default = true;
+            continue;
+        }
+
+        let has_events = fs::read_dir(entry.path())
+            .map(|events| {
+                events
+                    .flatten()
+                    .any(|e| is_year_directory(&e.file_name().to_string_lossy()))
+            })
+            .unwrap_or_default();
+        if has_events {
+            profiles.push(name)
+        }
+    }
+    profiles.sort();
+    if has_default {
+        profiles.insert(0, DEFAULT_PROFILE.to_string())
+    }
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_paths() {
+        assert_eq!(
+            PathBuf::from("inputs/2024/day05"),
+            day_path(INPUTS_DIRECTORY, 2024, 5)
+        );
+        assert_eq!(
+            PathBuf::from("inputs/alice/2019/day25"),
+            day_path(Path::new(INPUTS_DIRECTORY).join("alice"), 2019, 25)
+        );
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::benchmark::GENERATED_SEED_ENV;
+use anyhow::{Context, anyhow, bail};
+use aoc_solution::AocSolution;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Environmental variable that switches the benchmarks into the scaling mode.
+/// It can either be empty, to use sizes around the real input, or specify the range as `min..max`.
+pub const SCALING_ENV: &str = "AOC_BENCH_SCALING";
+
+const GROWTH_FACTOR: usize = 2;
+const DEFAULT_STEPS: u32 = 6;
+// every step generates and solves a twice as big input, so anything beyond that would never finish anyway
+const MAX_STEPS: usize = 20;
+
+// every measurement is repeated until either of the limits is reached and the fastest run is used
+const MEASUREMENT_BUDGET: Duration = Duration::from_millis(500);
+const MAX_ITERATIONS: usize = 10;
+
+/// Parses the requested sizes, either as `min..max` or, if empty, as the default range
+/// spanning from below to above the real input size.
+pub fn scaling_sizes(raw: &str, real_size: usize) -> anyhow::Result<Vec<usize>> {
+    let (min, max) = match raw.trim().split_once("..") {
+        Some((min, max)) => (min.parse::<usize>()?, max.parse::<usize>()?),
+        None if raw.trim().is_empty() => {
+            let max = real_size.saturating_mul(GROWTH_FACTOR).max(1);
+            ((max / GROWTH_FACTOR.pow(DEFAULT_STEPS - 1)).max(1), max)
+        }
+        None => bail!("'{raw}' is not a valid size range, expected 'min..max'"),
+    };
+    if min == 0 || min > max {
+        bail!("'{min}..{max}' is not a valid size range")
+    }
+
+    let mut sizes = Vec::new();
+    let mut size = Some(min);
+    while let Some(current) = size
+        && current <= max
+    {
+        if sizes.len() == MAX_STEPS {
+            bail!("'{min}..{max}' spans more than {MAX_STEPS} sizes")
+        }
+        sizes.push(current);
+        size = current.checked_mul(GROWTH_FACTOR);
+    }
+    Ok(sizes)
+}
+
+/// Fits `time = c * size^k` using the least squares method on the log-log scale and returns `k`.
+pub fn growth_exponent(points: &[(usize, Duration)]) -> Option<f64> {
+    let points = points
+        .iter()
+        .filter(|(size, taken)| *size > 0 && !taken.is_zero())
+        .map(|(size, taken)| ((*size as f64).ln(), taken.as_secs_f64().ln()))
+        .collect::<Vec<_>>();
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    if variance == 0. {
+        return None;
+    }
+    Some(covariance / variance)
+}
+
+fn fastest_run<I, F>(mut setup: impl FnMut() -> I, mut routine: F) -> Duration
+where
+    F: FnMut(I),
+{
+    let started = Instant::now();
+    let mut fastest = Duration::MAX;
+    for _ in 0..MAX_ITERATIONS {
+        let input = setup();
+        let start = Instant::now();
+        routine(input);
+        fastest = fastest.min(start.elapsed());
+        if started.elapsed() > MEASUREMENT_BUDGET {
+            break;
+        }
+    }
+    fastest
+}
+
+struct Series {
+    name: String,
+    points: Vec<(usize, Duration)>,
+}
+
+/// Runs the parsing and every part of the solution, including the alternatives,
+/// across generated inputs of increasing sizes and reports how their timings grow.
+pub fn run_scaling<T: AocSolution>(raw_sizes: &str) -> anyhow::Result<()> {
+    let generator = T::input_generator()
+        .context("the scaling benchmark requires the solution to have an input generator")?;
+    let sizes = scaling_sizes(raw_sizes, generator.real_size)?;
+    let seed = match std::env::var(GENERATED_SEED_ENV) {
+        Ok(seed) => seed.parse()?,
+        Err(_) => 0,
+    };
+
+    let part1_alternatives = T::part1_alternatives();
+    let part2_alternatives = T::part2_alternatives();
+
+    let mut series = vec![
+        Series {
+            name: "parsing".to_string(),
+            points: Vec::new(),
+        },
+        Series {
+            name: "part1".to_string(),
+            points: Vec::new(),
+        },
+        Series {
+            name: "part2".to_string(),
+            points: Vec::new(),
+        },
+    ];
+    for alt in &part1_alternatives {
+        series.push(Series {
+            name: format!("part1_{}", alt.name),
+            points: Vec::new(),
+        })
+    }
+    for alt in &part2_alternatives {
+        series.push(Series {
+            name: format!("part2_{}", alt.name),
+            points: Vec::new(),
+        })
+    }
+
+    for size in sizes {
+        let raw = (generator.generate)(size, seed);
+        let input = T::parse_input(&raw)
+            .map_err(|err| anyhow!("failed to parse the generated input of size {size}: {err}"))?;
+
+        let mut timings = vec![
+            fastest_run(
+                || &raw,
+                |raw| {
+                    let _ = black_box(T::parse_input(raw));
+                },
+            ),
+            fastest_run(
+                || input.clone(),
+                |input| {
+                    let _ = black_box(T::part1(input));
+                },
+            ),
+            fastest_run(
+                || input.clone(),
+                |input| {
+                    let _ = black_box(T::part2(input));
+                },
+            ),
+        ];
+        for alt in &part1_alternatives {
+            timings.push(fastest_run(
+                || input.clone(),
+                |input| {
+                    let _ = black_box((alt.runner)(input));
+                },
+            ));
+        }
+        for alt in &part2_alternatives {
+            timings.push(fastest_run(
+                || input.clone(),
+                |input| {
+                    let _ = black_box((alt.runner)(input));
+                },
+            ));
+        }
+
+        print!("size {size:>10}:");
+        for (series, taken) in series.iter_mut().zip(timings) {
+            print!("\t{}: {taken:?}", series.name);
+            series.points.push((size, taken));
+        }
+        println!();
+    }
+
+    println!("\n# GROWTH EXPONENTS #");
+    for series in &series {
+        match growth_exponent(&series.points) {
+            Some(exponent) => println!("{}:\tO(n^{exponent:.2})", series.name),
+            None => println!("{}:\tnot enough data", series.name),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_sizes() {
+        assert_eq!(
+            vec![10, 20, 40, 80],
+            scaling_sizes("10..100", 1000).unwrap()
+        );
+        assert_eq!(
+            vec![62, 124, 248, 496, 992, 1984],
+            scaling_sizes("", 1000).unwrap()
+        );
+        assert!(scaling_sizes("100..10", 1000).is_err());
+        assert!(scaling_sizes("foo", 1000).is_err());
+
+        // doubling the size must neither overflow, nor go on for too long
+        let max = usize::MAX;
+        let top = 1 << (usize::BITS - 1);
+        assert_eq!(
+            vec![top / 2, top],
+            scaling_sizes(&format!("{}..{max}", top / 2), 1000).unwrap()
+        );
+        assert!(scaling_sizes(&format!("1..{max}"), 1000).is_err());
+        assert_eq!(20, scaling_sizes("1..1048575", 1000).unwrap().len());
+        assert_eq!(
+            vec![usize::MAX],
+            scaling_sizes(&format!("{max}..{max}"), 1000).unwrap()
+        );
+    }
+
+    #[test]
+    fn fitting_exponents() {
+        let quadratic = (1..=5)
+            .map(|i| {
+                let size = 100 * i;
+                (size, Duration::from_nanos((size * size) as u64))
+            })
+            .collect::<Vec<_>>();
+        let exponent = growth_exponent(&quadratic).unwrap();
+        assert!((exponent - 2.).abs() < 1e-6);
+
+        let linear = vec![
+            (10, Duration::from_micros(30)),
+            (20, Duration::from_micros(60)),
+        ];
+        let exponent = growth_exponent(&linear).unwrap();
+        assert!((exponent - 1.).abs() < 1e-6);
+
+        assert!(growth_exponent(&[(10, Duration::from_secs(1))]).is_none());
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::solutions::{Solution, event_days};
+use aoc_common::answers::{AnswersStore, Part};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BADGES_DIRECTORY: &str = ".github/badges";
+
+const CELL_SIZE: usize = 48;
+const CELL_GAP: usize = 8;
+const CALENDAR_PADDING: usize = 16;
+const CALENDAR_HEADER: usize = 32;
+const CALENDAR_COLUMNS: usize = 5;
+
+/// Stars collected for a single day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stars {
+    None,
+    PartOneOnly,
+    Both,
+}
+
+impl Stars {
+    pub fn count(self) -> usize {
+        match self {
+            Stars::None => 0,
+            Stars::PartOneOnly => 1,
+            Stars::Both => 2,
+        }
+    }
+}
+
+/// Stars of every day of a single event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventProgress {
+    pub year: u16,
+    /// Stars of each day of the event, starting with day 1
+    pub days: Vec<Stars>,
+}
+
+impl EventProgress {
+    /// Derives the stars from the registered solutions of the event and the answers verified for the profile.
+    pub fn new(year: u16, solutions: &[Solution], answers: &AnswersStore, profile: &str) -> Self {
+        let registered = solutions
+            .iter()
+            .filter(|solution| solution.year == year)
+            .map(|solution| solution.day)
+            .collect::<BTreeSet<_>>();
+        EventProgress::collect(year, &registered, |day, part| {
+            answers.correct_answer(profile, year, day, part).is_some()
+        })
+    }
+
+    fn collect(year: u16, registered: &BTreeSet<u8>, verified: impl Fn(u8, Part) -> bool) -> Self {
+        let mut days = (1..=event_days(year))
+            .map(|day| {
+                if !registered.contains(&day) {
+                    return Stars::None;
+                }
+                match (verified(day, Part::One), verified(day, Part::Two)) {
+                    (true, true) => Stars::Both,
+                    (true, false) => Stars::PartOneOnly,
+                    _ => Stars::None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // the last puzzle only has a single part, its second star is awarded for collecting all the other ones
+        if let Some((last, others)) = days.split_last_mut()
+            && *last == Stars::PartOneOnly
+            && others.iter().all(|stars| *stars == Stars::Both)
+        {
+            *last = Stars::Both
+        }
+
+        EventProgress { year, days }
+    }
+
+    pub fn day(&self, day: u8) -> Stars {
+        self.days
+            .get(usize::from(day).wrapping_sub(1))
+            .copied()
+            .unwrap_or(Stars::None)
+    }
+
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(|stars| stars.count()).sum()
+    }
+
+    pub fn total(&self) -> usize {
+        self.days.len() * 2
+    }
+
+    /// Days that only have the first part solved
+    pub fn part_one_only(&self) -> Vec<u8> {
+        self.days
+            .iter()
+            .zip(1..)
+            .filter(|(stars, _)| **stars == Stars::PartOneOnly)
+            .map(|(_, day)| day)
+            .collect()
+    }
+
+    fn color(&self) -> &'static str {
+        let (stars, total) = (self.stars(), self.total());
+        if stars == total {
+            "green"
+        } else if stars * 4 >= total * 3 {
+            "yellow"
+        } else if stars * 5 >= total * 2 {
+            "orange"
+        } else {
+            "red"
+        }
+    }
+
+    pub fn badge(&self) -> Badge {
+        let total = self.total();
+        Badge {
+            schema_version: 1,
+            label: self.year.to_string(),
+            message: format!(
+                "{:0width$}/{total}",
+                self.stars(),
+                width = total.to_string().len()
+            ),
+            color: self.color(),
+            style: "for-the-badge",
+        }
+    }
+
+    /// Draws the calendar of the event, with gold days for both stars and silver ones for only the first one.
+    pub fn calendar_svg(&self) -> String {
+        let rows = self.days.len().div_ceil(CALENDAR_COLUMNS);
+        let width = 2 * CALENDAR_PADDING + CALENDAR_COLUMNS * (CELL_SIZE + CELL_GAP) - CELL_GAP;
+        let height =
+            2 * CALENDAR_PADDING + CALENDAR_HEADER + rows * (CELL_SIZE + CELL_GAP) - CELL_GAP;
+
+        let mut svg = format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="monospace">
+<rect width="{width}" height="{height}" fill="#0f0f23"/>
+<text x="{CALENDAR_PADDING}" y="{}" fill="#cccccc" font-size="18">{}: {}/{}</text>
+"##,
+            CALENDAR_PADDING + 18,
+            self.year,
+            self.stars(),
+            self.total()
+        );
+        for (i, stars) in self.days.iter().enumerate() {
+            let x = CALENDAR_PADDING + (i % CALENDAR_COLUMNS) * (CELL_SIZE + CELL_GAP);
+            let y = CALENDAR_PADDING
+                + CALENDAR_HEADER
+                + (i / CALENDAR_COLUMNS) * (CELL_SIZE + CELL_GAP);
+            let (fill, label) = match stars {
+                Stars::None => ("#333340", ""),
+                Stars::PartOneOnly => ("#9999cc", "★"),
+                Stars::Both => ("#ffff66", "★★"),
+            };
+            let centre = x + CELL_SIZE / 2;
+            svg.push_str(&format!(
+                r##"<rect x="{x}" y="{y}" width="{CELL_SIZE}" height="{CELL_SIZE}" rx="6" fill="{fill}"/>
+<text x="{centre}" y="{}" fill="#0f0f23" font-size="16" text-anchor="middle">{}</text>
+<text x="{centre}" y="{}" fill="#0f0f23" font-size="12" text-anchor="middle">{label}</text>
+"##,
+                y + 20,
+                i + 1,
+                y + 38
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Endpoint served to shields.io, see <https://shields.io/badges/endpoint-badge>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Badge {
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: &'static str,
+    pub style: &'static str,
+}
+
+pub fn badge_path(directory: &Path, year: u16) -> PathBuf {
+    directory.join(format!("completion{year}.json"))
+}
+
+pub fn calendar_path(directory: &Path, year: u16) -> PathBuf {
+    directory.join(format!("calendar{year}.svg"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(year: u16, registered: &[u8], verified: &[(u8, Part)]) -> EventProgress {
+        EventProgress::collect(year, &registered.iter().copied().collect(), |day, part| {
+            verified.contains(&(day, part))
+        })
+    }
+
+    #[test]
+    fn counting_stars() {
+        let event = progress(
+            2025,
+            &[1, 2, 3],
+            &[
+                (1, Part::One),
+                (1, Part::Two),
+                (2, Part::One),
+                // answers without a registered solution don't count
+                (4, Part::One),
+                (4, Part::Two),
+            ],
+        );
+        assert_eq!(12, event.days.len());
+        assert_eq!(3, event.stars());
+        assert_eq!(24, event.total());
+        assert_eq!(vec![2], event.part_one_only());
+
+        let expected = Badge {
+            schema_version: 1,
+            label: "2025".to_string(),
+            message: "03/24".to_string(),
+            color: "red",
+            style: "for-the-badge",
+        };
+        assert_eq!(expected, event.badge());
+        assert_eq!(
+            r#"{"schemaVersion":1,"label":"2025","message":"03/24","color":"red","style":"for-the-badge"}"#,
+            serde_json::to_string(&event.badge()).unwrap()
+        );
+    }
+
+    #[test]
+    fn awarding_the_last_star() {
+        let days = (1..=25).collect::<Vec<_>>();
+        let mut verified = days
+            .iter()
+            .flat_map(|&day| [(day, Part::One), (day, Part::Two)])
+            .filter(|&(day, part)| day != 25 || part == Part::One)
+            .collect::<Vec<_>>();
+
+        let complete = progress(2020, &days, &verified);
+        assert_eq!(50, complete.stars());
+        assert_eq!("green", complete.badge().color);
+        assert!(complete.part_one_only().is_empty());
+
+        verified.retain(|&entry| entry != (24, Part::Two));
+        let incomplete = progress(2020, &days, &verified);
+        assert_eq!(48, incomplete.stars());
+        assert_eq!(vec![24, 25], incomplete.part_one_only());
+        assert_eq!("yellow", incomplete.badge().color);
+    }
+
+    #[test]
+    fn drawing_calendar() {
+        let event = progress(
+            2024,
+            &[1, 2],
+            &[(1, Part::One), (1, Part::Two), (2, Part::One)],
+        );
+        let svg = event.calendar_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("2024: 3/50"));
+        // the background, and one rect for each of the days
+        assert_eq!(26, svg.matches("<rect").count());
+        assert_eq!(1, svg.matches("#ffff66").count());
+        assert_eq!(1, svg.matches("#9999cc").count());
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Terminal rendering of the archived puzzle descriptions. It only supports the subset of Markdown
+//! produced by `aoc-init` (headings, paragraphs, lists, code blocks and inline emphasis, code and links).
+
+pub const DEFAULT_WIDTH: usize = 100;
+
+const RESET: &str = "\x1B[0m";
+const HEADING: &str = "\x1B[1;33m";
+const CODE_BLOCK_INDENT: &str = "    ";
+const LIST_BULLET: &str = "  • ";
+const LIST_INDENT: &str = "    ";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn ansi(&self) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1")
+        }
+        if self.italic {
+            codes.push("3")
+        }
+        if self.link {
+            codes.push("4")
+        }
+        if self.code {
+            codes.push("36")
+        }
+        if codes.is_empty() {
+            String::new()
+        } else {
+            format!("\x1B[{}m", codes.join(";"))
+        }
+    }
+}
+
+/// Fragment of a word with a uniform style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Fragment {
+    text: String,
+    style: Style,
+}
+
+/// Splits the inline Markdown into words, each made of the differently styled fragments.
+fn words(markdown: &str) -> Vec<Vec<Fragment>> {
+    let mut words: Vec<Vec<Fragment>> = Vec::new();
+    let mut current: Vec<Fragment> = Vec::new();
+    let mut style = Style::default();
+
+    let push = |current: &mut Vec<Fragment>, c: char, style: Style| match current.last_mut() {
+        Some(last) if last.style == style => last.text.push(c),
+        _ => current.push(Fragment {
+            text: c.to_string(),
+            style,
+        }),
+    };
+
+    let mut chars = markdown.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    push(&mut current, escaped, style)
+                }
+            }
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                style.bold = !style.bold;
+            }
+            '*' => style.italic = !style.italic,
+            '`' => {
+                let code_style = Style {
+                    code: true,
+                    ..style
+                };
+                for c in chars.by_ref() {
+                    if c == '`' {
+                        break;
+                    }
+                    if c.is_whitespace() {
+                        // code fragments are kept within a single word, so that they're never broken up
+                        push(&mut current, '\u{A0}', code_style)
+                    } else {
+                        push(&mut current, c, code_style)
+                    }
+                }
+            }
+            '[' => style.link = true,
+            ']' if style.link => {
+                style.link = false;
+                // the target itself is not displayed
+                if chars.peek() == Some(&'(') {
+                    for c in chars.by_ref() {
+                        if c == ')' {
+                            break;
+                        }
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current))
+                }
+            }
+            c => push(&mut current, c, style),
+        }
+    }
+    if !current.is_empty() {
+        words.push(current)
+    }
+    words
+}
+
+fn word_width(word: &[Fragment]) -> usize {
+    word.iter()
+        .map(|fragment| fragment.text.chars().count())
+        .sum()
+}
+
+fn render_word(word: &[Fragment], colour: bool) -> String {
+    word.iter()
+        .map(|fragment| {
+            let text = fragment.text.replace('\u{A0}', " ");
+            let ansi = fragment.style.ansi();
+            if colour && !ansi.is_empty() {
+                format!("{ansi}{text}{RESET}")
+            } else {
+                text
+            }
+        })
+        .collect()
+}
+
+/// Wraps the inline Markdown into lines of at most the provided width, apart from the words that are longer.
+fn wrap(markdown: &str, first_prefix: &str, prefix: &str, width: usize, colour: bool) -> String {
+    let mut lines = Vec::new();
+    let mut line = first_prefix.to_string();
+    let mut line_width = first_prefix.chars().count();
+    let mut line_empty = true;
+
+    for word in words(markdown) {
+        let width_of_word = word_width(&word);
+        if !line_empty && line_width + 1 + width_of_word > width {
+            lines.push(std::mem::replace(&mut line, prefix.to_string()));
+            line_width = prefix.chars().count();
+            line_empty = true;
+        }
+        if !line_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(&render_word(&word, colour));
+        line_width += width_of_word;
+        line_empty = false;
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+/// Renders the archived description for the terminal, optionally using ANSI colours.
+pub fn render(markdown: &str, width: usize, colour: bool) -> String {
+    let mut output = Vec::new();
+    let mut lines = markdown.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if line == "```" {
+            let mut block = Vec::new();
+            for line in lines.by_ref() {
+                if line == "```" {
+                    break;
+                }
+                if line.is_empty() {
+                    block.push(String::new())
+                } else {
+                    block.push(format!("{CODE_BLOCK_INDENT}{line}"))
+                }
+            }
+            output.push(block.join("\n"));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            if colour {
+                output.push(format!("{HEADING}{heading}{RESET}"))
+            } else {
+                output.push(heading.to_string())
+            }
+        } else if let Some(item) = line.strip_prefix("- ") {
+            output.push(wrap(item, LIST_BULLET, LIST_INDENT, width, colour))
+        } else if !line.trim().is_empty() {
+            // paragraphs may span multiple lines, which get reflowed
+            let mut paragraph = line.to_string();
+            while let Some(next) = lines.peek() {
+                if next.trim().is_empty() || next.starts_with("- ") || *next == "```" {
+                    break;
+                }
+                paragraph.push(' ');
+                paragraph.push_str(next);
+                lines.next();
+            }
+            output.push(wrap(&paragraph, "", "", width, colour))
+        } else if output.last().is_some_and(|last: &String| !last.is_empty()) {
+            output.push(String::new())
+        }
+    }
+
+    let mut rendered = output.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+/// Terminal width, based on the `COLUMNS` variable set by most of the shells.
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = r#"## --- Day 5: Sorting Sleighs ---
+
+The elves keep a list of *sleigh pairs* that must never be swapped, followed by the [stacks](/2024/day/4) they want to load:
+
+```
+3|7
+
+3,7,1
+```
+
+- Rules use `|` & nothing else.
+
+Summing the middle elements produces **`7`**, not \*7\*.
+"#;
+
+    #[test]
+    fn rendering_plain_text() {
+        let expected = r#"--- Day 5: Sorting Sleighs ---
+
+The elves keep a list of sleigh pairs that must never
+be swapped, followed by the stacks they want to load:
+
+    3|7
+
+    3,7,1
+
+  • Rules use | & nothing else.
+
+Summing the middle elements produces 7, not *7*.
+"#;
+        assert_eq!(expected, render(DESCRIPTION, 55, false));
+        assert_eq!(
+            "  • Rules use | &\n    nothing else.\n",
+            render("- Rules use `|` & nothing else.", 20, false)
+        );
+        assert_eq!(
+            expected,
+            render(&DESCRIPTION.replace("swapped, ", "swapped,\n"), 55, false)
+        );
+    }
+
+    #[test]
+    fn rendering_styles() {
+        let rendered = render("Run `cargo test` **`now`**, *please*.", 80, true);
+        assert_eq!(
+            "Run \x1B[36mcargo test\x1B[0m \x1B[1;36mnow\x1B[0m, \x1B[3mplease\x1B[0m.\n",
+            rendered
+        );
+        assert_eq!("\x1B[1;33mDay 1\x1B[0m\n", render("## Day 1", 80, true));
+    }
+}
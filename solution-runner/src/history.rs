@@ -0,0 +1,400 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{Context, bail};
+use aoc_common::config::Config;
+use aoc_solution::summary::{DaySummary, PartSummary};
+use humantime::format_duration;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_HISTORY_FILE: &str = ".aoc/history.jsonl";
+
+pub fn default_history_path(config: &Config) -> PathBuf {
+    config.resolve(DEFAULT_HISTORY_FILE)
+}
+
+/// Cargo profile the runner has been built with, as the debug timings are not comparable with the release ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildProfile {
+    Debug,
+    Release,
+}
+
+impl BuildProfile {
+    pub fn current() -> Self {
+        if cfg!(debug_assertions) {
+            BuildProfile::Debug
+        } else {
+            BuildProfile::Release
+        }
+    }
+}
+
+impl FromStr for BuildProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(BuildProfile::Debug),
+            "release" => Ok(BuildProfile::Release),
+            other => bail!("'{other}' is not a valid build profile, expected 'debug' or 'release'"),
+        }
+    }
+}
+
+impl Display for BuildProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildProfile::Debug => write!(f, "debug"),
+            BuildProfile::Release => write!(f, "release"),
+        }
+    }
+}
+
+/// Timings of a single run of a particular day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Unix timestamp of the run
+    pub timestamp: u64,
+    pub revision: String,
+    pub machine: String,
+    /// Not present in the entries recorded before the build profile got tracked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildProfile>,
+    pub year: u16,
+    pub day: u8,
+    pub parsing_ns: u64,
+    /// Not present if the part has failed to get solved
+    pub part1_ns: Option<u64>,
+    pub part2_ns: Option<u64>,
+}
+
+fn part_nanos(part: &PartSummary) -> Option<u64> {
+    part.answer
+        .as_ref()
+        .ok()
+        .map(|_| part.taken.as_nanos().try_into().unwrap_or(u64::MAX))
+}
+
+impl HistoryEntry {
+    pub fn new(year: u16, day: u8, summary: &DaySummary, revision: &str, machine: &str) -> Self {
+        HistoryEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            revision: revision.to_string(),
+            machine: machine.to_string(),
+            build: Some(BuildProfile::current()),
+            year,
+            day,
+            parsing_ns: summary.parsing.as_nanos().try_into().unwrap_or(u64::MAX),
+            part1_ns: part_nanos(&summary.part1),
+            part2_ns: part_nanos(&summary.part2),
+        }
+    }
+}
+
+/// 64-bit FNV-1a, which, unlike the std hashers, is guaranteed to stay the same between the releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Label of the revision with uncommitted changes, which is unique for every different set of changes.
+fn dirty_revision(revision: &str, diff: &str) -> String {
+    format!("{revision}-dirty-{:08x}", fnv1a(diff.as_bytes()) >> 32)
+}
+
+/// Attempts to determine the current git revision of the project, marking it if there are any uncommitted changes.
+pub fn current_revision(root: &Path) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let Some(revision) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+    match git(&["diff", "HEAD"]) {
+        Some(diff) if !diff.is_empty() => dirty_revision(&revision, &diff),
+        _ => revision,
+    }
+}
+
+/// Identifies the current machine either through the `AOC_MACHINE_ID` environmental variable
+/// or its hostname.
+pub fn current_machine() -> String {
+    if let Ok(machine) = std::env::var("AOC_MACHINE_ID") {
+        return machine;
+    }
+    fs::read_to_string("/etc/hostname")
+        .ok()
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+pub fn append<P: AsRef<Path>>(path: P, entries: &[HistoryEntry]) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    for entry in entries {
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    }
+    Ok(())
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<HistoryEntry>> {
+    let path = path.as_ref();
+    let file =
+        fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+
+    let mut entries = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(
+            serde_json::from_str(&line)
+                .with_context(|| format!("malformed history entry in line {}", i + 1))?,
+        );
+    }
+    Ok(entries)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Parsing,
+    Part1,
+    Part2,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parsing => write!(f, "parsing"),
+            Stage::Part1 => write!(f, "part 1"),
+            Stage::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Duration,
+    pub candidate: Duration,
+}
+
+impl Regression {
+    pub fn slowdown_percent(&self) -> f64 {
+        (self.candidate.as_secs_f64() / self.baseline.as_secs_f64() - 1.) * 100.
+    }
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} day {:02} {}: {} -> {} (+{:.1}%)",
+            self.year,
+            self.day,
+            self.stage,
+            format_duration(self.baseline),
+            format_duration(self.candidate),
+            self.slowdown_percent()
+        )
+    }
+}
+
+type Timings = BTreeMap<(u16, u8, Stage), u64>;
+
+/// Picks the fastest recorded timing of every stage, so that a single noisy run would not
+/// get reported as a regression.
+fn fastest_timings<'a>(entries: impl Iterator<Item = &'a HistoryEntry>) -> Timings {
+    let mut timings = Timings::new();
+    for entry in entries {
+        let stages = [
+            (Stage::Parsing, Some(entry.parsing_ns)),
+            (Stage::Part1, entry.part1_ns),
+            (Stage::Part2, entry.part2_ns),
+        ];
+        for (stage, nanos) in stages {
+            let Some(nanos) = nanos else { continue };
+            timings
+                .entry((entry.year, entry.day, stage))
+                .and_modify(|fastest| *fastest = (*fastest).min(nanos))
+                .or_insert(nanos);
+        }
+    }
+    timings
+}
+
+/// Returns the last revision present in the history and the one preceding it.
+pub fn latest_revisions(entries: &[HistoryEntry]) -> Option<(Option<&str>, &str)> {
+    let candidate = entries.last()?.revision.as_str();
+    let baseline = entries
+        .iter()
+        .rev()
+        .map(|entry| entry.revision.as_str())
+        .find(|revision| *revision != candidate);
+    Some((baseline, candidate))
+}
+
+/// Finds all stages that got slower than the threshold (in percent) between the baseline and the candidate revision.
+pub fn compare(
+    entries: &[HistoryEntry],
+    baseline: &str,
+    candidate: &str,
+    threshold: f64,
+) -> anyhow::Result<Vec<Regression>> {
+    let baseline_timings = fastest_timings(entries.iter().filter(|e| e.revision == baseline));
+    let candidate_timings = fastest_timings(entries.iter().filter(|e| e.revision == candidate));
+    if baseline_timings.is_empty() {
+        bail!("there are no timings recorded for revision {baseline}")
+    }
+    if candidate_timings.is_empty() {
+        bail!("there are no timings recorded for revision {candidate}")
+    }
+
+    let mut regressions = Vec::new();
+    for ((year, day, stage), candidate_ns) in candidate_timings {
+        let Some(&baseline_ns) = baseline_timings.get(&(year, day, stage)) else {
+            continue;
+        };
+        let regression = Regression {
+            year,
+            day,
+            stage,
+            baseline: Duration::from_nanos(baseline_ns),
+            candidate: Duration::from_nanos(candidate_ns),
+        };
+        if baseline_ns > 0 && regression.slowdown_percent() > threshold {
+            regressions.push(regression)
+        }
+    }
+
+    Ok(regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(revision: &str, day: u8, parsing_ns: u64, part1_ns: Option<u64>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 0,
+            revision: revision.to_string(),
+            machine: "test".to_string(),
+            build: Some(BuildProfile::Release),
+            year: 2024,
+            day,
+            parsing_ns,
+            part1_ns,
+            part2_ns: None,
+        }
+    }
+
+    #[test]
+    fn determining_latest_revisions() {
+        assert!(latest_revisions(&[]).is_none());
+
+        let entries = vec![entry("aaa", 1, 1, None)];
+        assert_eq!(Some((None, "aaa")), latest_revisions(&entries));
+
+        let entries = vec![
+            entry("aaa", 1, 1, None),
+            entry("bbb", 1, 1, None),
+            entry("ccc", 1, 1, None),
+            entry("ccc", 2, 1, None),
+        ];
+        assert_eq!(Some((Some("bbb"), "ccc")), latest_revisions(&entries));
+    }
+
+    #[test]
+    fn comparing_revisions() {
+        let entries = vec![
+            entry("aaa", 1, 100, Some(1000)),
+            entry("aaa", 1, 150, Some(900)),
+            entry("aaa", 2, 100, Some(1000)),
+            entry("bbb", 1, 105, Some(2000)),
+            entry("bbb", 1, 200, Some(1900)),
+            entry("bbb", 2, 100, None),
+            entry("bbb", 3, 100, Some(1000)),
+        ];
+
+        let regressions = compare(&entries, "aaa", "bbb", 10.).unwrap();
+        assert_eq!(
+            vec![Regression {
+                year: 2024,
+                day: 1,
+                stage: Stage::Part1,
+                baseline: Duration::from_nanos(900),
+                candidate: Duration::from_nanos(1900),
+            }],
+            regressions
+        );
+
+        assert!(compare(&entries, "aaa", "bbb", 200.).unwrap().is_empty());
+        assert!(compare(&entries, "aaa", "ccc", 10.).is_err());
+    }
+
+    #[test]
+    fn labelling_dirty_revisions() {
+        let label = dirty_revision("abc1234", "+foo");
+        assert!(label.starts_with("abc1234-dirty-"));
+        assert_eq!(label, dirty_revision("abc1234", "+foo"));
+        assert_ne!(label, dirty_revision("abc1234", "+bar"));
+    }
+
+    #[test]
+    fn loading_entries_without_build_profile() {
+        let line = r#"{"timestamp":0,"revision":"aaa","machine":"test","year":2024,"day":1,"parsing_ns":1,"part1_ns":null,"part2_ns":null}"#;
+        let legacy = serde_json::from_str::<HistoryEntry>(line).unwrap();
+        assert_eq!(None, legacy.build);
+
+        let serialised = serde_json::to_string(&entry("aaa", 1, 1, None)).unwrap();
+        assert!(serialised.contains(r#""build":"release""#));
+        assert_eq!(BuildProfile::Debug, "debug".parse().unwrap());
+        assert!("profiling".parse::<BuildProfile>().is_err());
+    }
+}
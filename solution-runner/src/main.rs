@@ -12,130 +12,874 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use aoc_common::helpers::root_path;
-use aoc_solution::AocSolutionSolver;
-use clap::Parser;
+use crate::badges::{EventProgress, Stars};
+use crate::history::{BuildProfile, HistoryEntry};
+use crate::plugin::Plugin;
+use crate::report::DayReport;
+use crate::solutions::{Solution, registered_solutions};
+use anyhow::{Context, bail};
+use aoc_common::answers::{AnswersStore, DEFAULT_PROFILE};
+use aoc_common::config::{Config, OutputFormat};
+use aoc_solution::summary::DaySummary;
+use clap::{Args, Parser, Subcommand};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+mod badges;
+mod describe;
+mod history;
+mod plugin;
+#[cfg(feature = "profile")]
+mod profile;
+mod report;
+mod server;
+mod solutions;
+mod tui;
+mod verify;
+mod watch;
 
 /// Simple solution runner for Advent of Code puzzles.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    /// Specifies the event year
-    #[arg(short, long, required = true)]
-    year: u16,
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Runs the solutions of the specified event (this is the default command)
+    Run(RunArgs),
+
+    /// Compares timings recorded in the history and reports the parts that got slower
+    Compare(CompareArgs),
+
+    /// Generates Markdown and HTML reports of all the solutions
+    Report(ReportArgs),
+
+    /// Runs the solutions against the inputs of every profile and checks them against the recorded answers
+    Verify(VerifyArgs),
+
+    /// Exposes the solutions through a local HTTP API
+    Serve(ServeArgs),
+
+    /// Opens an interactive terminal dashboard for browsing and running the solutions
+    Tui(TuiArgs),
+
+    /// Reruns the solution of the specified day whenever its input or example files change
+    Watch(WatchArgs),
+
+    /// Displays the archived description of the puzzle
+    Describe(DescribeArgs),
+
+    /// Updates the completion badges from the registered solutions and the verified answers
+    Badges(BadgesArgs),
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    /// Specifies the event year. Defaults to the one from the config.
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Specifies the event day. If omitted, all days of the event are going to be run.
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    /// Directory containing day crates built as `cdylib` plugins.
+    /// If it contains a plugin for the specified day, it is used instead of the built-in solution.
+    #[arg(long)]
+    plugin_dir: Option<PathBuf>,
+
+    /// Do not append the timings of this run to the history file
+    #[arg(long)]
+    no_history: bool,
+
+    /// Path to the timing history file
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Input profile, i.e. AoC account, whose inputs are going to be used
+    #[arg(long, default_value = DEFAULT_PROFILE)]
+    profile: String,
+
+    /// Save the answers of this run as the correct ones for the selected profile
+    #[arg(long)]
+    record_answers: bool,
+
+    /// Output format of the results (text or json). Defaults to the one from the config.
+    #[arg(long)]
+    output: Option<OutputFormat>,
+
+    /// Run the solutions against synthetic inputs produced by their generators
+    #[arg(long, conflicts_with = "record_answers")]
+    generate: bool,
+
+    /// Size of the generated inputs. Defaults to the size resembling the real input.
+    #[arg(long, requires = "generate")]
+    size: Option<usize>,
+
+    /// Seed of the generated inputs
+    #[arg(long, default_value_t = 0, requires = "generate")]
+    seed: u64,
+
+    /// Write the flamegraph of the selected part (1 or 2) of the built-in solution.
+    /// Can be specified twice to profile both parts.
+    #[cfg(feature = "profile")]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    cpu_profile: Vec<u8>,
+
+    /// Directory for the flamegraphs. Defaults to `profiles` in the project root.
+    #[cfg(feature = "profile")]
+    #[arg(long)]
+    cpu_profile_dir: Option<PathBuf>,
+
+    /// Path to a custom input used instead of the one of the profile
+    #[arg(long, conflicts_with_all = ["custom_input", "generate", "record_answers"])]
+    custom_input_filepath: Option<PathBuf>,
+
+    /// Custom input used instead of the one of the profile
+    #[arg(long, conflicts_with_all = ["custom_input_filepath", "generate", "record_answers"])]
+    custom_input: Option<String>,
+}
+
+impl RunArgs {
+    /// Whether the run uses anything other than the actual input of the profile
+    fn uses_custom_input(&self) -> bool {
+        self.generate || self.custom_input.is_some() || self.custom_input_filepath.is_some()
+    }
+}
+
+#[derive(Args, Debug)]
+struct CompareArgs {
+    /// Revision to compare against. Defaults to the revision preceding the candidate in the history.
+    #[arg(long)]
+    baseline: Option<String>,
+
+    /// Revision to check for regressions. Defaults to the latest revision in the history.
+    #[arg(long)]
+    candidate: Option<String>,
+
+    /// Minimum slowdown, in percent, for a part to be reported
+    #[arg(long, default_value_t = 10.)]
+    threshold: f64,
+
+    /// Only compare timings recorded on this machine. Defaults to the current machine.
+    #[arg(long)]
+    machine: Option<String>,
+
+    /// Only compare timings recorded with this build profile, i.e. `debug` or `release`.
+    /// Defaults to the profile of the runner itself.
+    #[arg(long)]
+    build: Option<BuildProfile>,
+
+    /// Path to the timing history file
+    #[arg(long)]
+    history: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct ReportArgs {
+    /// Use the latest timings recorded in the history instead of running all the solutions
+    #[arg(long)]
+    from_history: bool,
+
+    /// Path to the timing history file
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Directory containing day crates built as `cdylib` plugins
+    #[arg(long)]
+    plugin_dir: Option<PathBuf>,
+
+    /// Output path of the Markdown report
+    #[arg(long, default_value = ".aoc/report.md")]
+    markdown: PathBuf,
+
+    /// Output path of the HTML report
+    #[arg(long, default_value = ".aoc/report.html")]
+    html: PathBuf,
+
+    /// Also replace the solutions section of the README with the generated report
+    #[arg(long)]
+    update_readme: bool,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// Specifies the event year. Defaults to the one from the config.
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Specifies the event day. If omitted, all days of the event are going to be verified.
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    /// Profiles to verify. If omitted, every profile with inputs is going to be used.
+    #[arg(long)]
+    profile: Vec<String>,
+
+    /// Directory containing day crates built as `cdylib` plugins
+    #[arg(long)]
+    plugin_dir: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Port to listen on
+    #[arg(short, long, default_value_t = 8080)]
+    port: u16,
+
+    /// Address to bind to
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Directory containing day crates built as `cdylib` plugins
+    #[arg(long)]
+    plugin_dir: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct TuiArgs {
+    /// Directory containing day crates built as `cdylib` plugins
+    #[arg(long)]
+    plugin_dir: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct DescribeArgs {
+    /// Specifies the event year. Defaults to the one from the config.
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Specifies the event day
+    #[arg(short, long)]
+    day: u8,
+
+    /// Maximum width of the lines, defaults to the width of the terminal
+    #[arg(long)]
+    width: Option<usize>,
+
+    /// Don't use any colours
+    #[arg(long)]
+    plain: bool,
+}
+
+#[derive(Args, Debug)]
+struct BadgesArgs {
+    /// Specifies the event year. If omitted, the badges of all the events with registered solutions are updated.
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Input profile, i.e. AoC account, whose verified answers count towards the stars
+    #[arg(long, default_value = DEFAULT_PROFILE)]
+    profile: String,
+
+    /// Directory of the shields.io endpoint files
+    #[arg(long, default_value = badges::DEFAULT_BADGES_DIRECTORY)]
+    output_dir: PathBuf,
+
+    /// Also draw the calendars of the events as SVG files in this directory, e.g. `.aoc`
+    #[arg(long)]
+    calendar_dir: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct WatchArgs {
+    /// Specifies the event year. Defaults to the one from the config.
+    #[arg(short, long)]
+    year: Option<u16>,
 
     /// Specifies the event day
-    #[arg(short, long, required = true)]
+    #[arg(short, long)]
     day: u8,
-    // not implemented yet
-    // #[arg(long, conflicts_with = "custom_input")]
-    // custom_input_filepath: Option<PathBuf>,
 
-    // not implemented yet
-    // #[arg(long, conflicts_with = "custom_input_filepath")]
-    // custom_input: Option<String>,
+    /// Input profile, i.e. AoC account, whose input is going to be watched
+    #[arg(long, default_value = DEFAULT_PROFILE)]
+    profile: String,
+
+    /// Path to a custom input watched instead of the one of the profile
+    #[arg(long)]
+    custom_input_filepath: Option<PathBuf>,
+
+    /// Directory containing day crates built as `cdylib` plugins
+    #[arg(long)]
+    plugin_dir: Option<PathBuf>,
+
+    /// Time, in milliseconds, to wait for further changes before rerunning the solution
+    #[arg(long, default_value_t = 200)]
+    debounce: u64,
+}
+
+/// Attempts to run the solution using a plugin from the provided directory.
+/// Returns `None` if no appropriate plugin exists.
+fn try_run_plugin(
+    plugin_dir: &Path,
+    year: u16,
+    day: u8,
+    input: &str,
+) -> anyhow::Result<Option<(String, Result<DaySummary, String>)>> {
+    let Some(info) = plugin::find(plugin_dir, year, day)? else {
+        return Ok(None);
+    };
+
+    let plugin = Plugin::load(&info.path)?;
+    Ok(Some((info.path.display().to_string(), plugin.run(input))))
+}
+
+/// Runs the solution, preferring its plugin if one is available.
+/// Returns the name of the solver alongside the results.
+fn solve(
+    solution: &Solution,
+    input: &str,
+    plugin_dir: Option<&Path>,
+) -> anyhow::Result<(String, Result<DaySummary, String>)> {
+    let plugin_result = match plugin_dir {
+        Some(plugin_dir) => try_run_plugin(plugin_dir, solution.year, solution.day, input)?,
+        None => None,
+    };
+    Ok(plugin_result.unwrap_or_else(|| (solution.name.to_string(), (solution.run)(input))))
+}
+
+fn read_input(config: &Config, solution: &Solution, profile: &str) -> anyhow::Result<String> {
+    let input_path = config.input_path(profile, solution.year, solution.day);
+    fs::read_to_string(&input_path)
+        .with_context(|| format!("failed to read {}", input_path.display()))
+}
+
+fn generate_input(solution: &Solution, size: Option<usize>, seed: u64) -> anyhow::Result<String> {
+    let Some(generator) = solution.generator else {
+        bail!(
+            "{} day {:02} does not have an input generator",
+            solution.year,
+            solution.day
+        )
+    };
+    Ok((generator.generate)(
+        size.unwrap_or(generator.real_size),
+        seed,
+    ))
+}
+
+#[cfg(feature = "profile")]
+fn profile_parts(
+    config: &Config,
+    solution: &Solution,
+    input: &str,
+    args: &RunArgs,
+) -> anyhow::Result<()> {
+    let directory = args
+        .cpu_profile_dir
+        .clone()
+        .unwrap_or_else(|| config.resolve(profile::DEFAULT_PROFILES_DIRECTORY));
+    for &part in &args.cpu_profile {
+        let part = aoc_common::answers::Part::try_from(part)?;
+        let output = profile::flamegraph_path(&directory, solution.year, solution.day, part);
+        let iterations = (solution.profile)(input, part, &output)?;
+        println!(
+            "wrote the flamegraph of {iterations} run(s) to {}",
+            output.display()
+        );
+    }
+    Ok(())
 }
 
-macro_rules! define_solution {
-    ($args: ident, $year: literal, $day: literal, $input: literal, $solver: ty) => {
-        if $args.year == $year && $args.day == $day {
-            return <$solver as AocSolutionSolver>::try_solve_from_file(root_path($input));
+fn run_solution(
+    config: &Config,
+    solution: &Solution,
+    args: &RunArgs,
+) -> anyhow::Result<Option<DaySummary>> {
+    let input = if let Some(input) = &args.custom_input {
+        input.clone()
+    } else if let Some(path) = &args.custom_input_filepath {
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?
+    } else if args.generate {
+        generate_input(solution, args.size, args.seed)?
+    } else {
+        read_input(config, solution, &args.profile)?
+    };
+    let (name, result) = solve(solution, &input, args.plugin_dir.as_deref())?;
+
+    let summary = match result {
+        Ok(summary) => {
+            match args.output.unwrap_or(config.output) {
+                OutputFormat::Text => summary.report(&name),
+                OutputFormat::Json => {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&server::DayResultJson::new(
+                            solution.year,
+                            solution.day,
+                            name.clone(),
+                            &summary
+                        ))?
+                    );
+                    for disagreement in summary.disagreements() {
+                        eprintln!("'{name}' variants disagree: {disagreement}")
+                    }
+                }
+            }
+            Some(summary)
+        }
+        Err(err) => {
+            eprintln!("failed to solve aoc for '{name}': {err}");
+            None
         }
     };
+
+    #[cfg(feature = "profile")]
+    profile_parts(config, solution, &input, args)?;
+
+    Ok(summary)
 }
 
-fn main() {
-    let args = Args::parse();
-
-    // AUTOGENERATED SOLUTIONS START
-    define_solution!(args, 2020, 1, "inputs/2019/day01", day01_2019::Day01);
-    define_solution!(args, 2019, 2, "inputs/2019/day02", day02_2019::Day02);
-    define_solution!(args, 2019, 3, "inputs/2019/day03", day03_2019::Day03);
-    define_solution!(args, 2019, 4, "inputs/2019/day04", day04_2019::Day04);
-    define_solution!(args, 2019, 5, "inputs/2019/day05", day05_2019::Day05);
-    define_solution!(args, 2019, 6, "inputs/2019/day06", day06_2019::Day06);
-    define_solution!(args, 2019, 7, "inputs/2019/day07", day07_2019::Day07);
-    define_solution!(args, 2019, 8, "inputs/2019/day08", day08_2019::Day08);
-    define_solution!(args, 2019, 9, "inputs/2019/day09", day09_2019::Day09);
-    define_solution!(args, 2020, 1, "inputs/2020/day01", day01_2020::Day01);
-    define_solution!(args, 2020, 2, "inputs/2020/day02", day02_2020::Day02);
-    define_solution!(args, 2020, 3, "inputs/2020/day03", day03_2020::Day03);
-    define_solution!(args, 2020, 4, "inputs/2020/day04", day04_2020::Day04);
-    define_solution!(args, 2020, 5, "inputs/2020/day05", day05_2020::Day05);
-    define_solution!(args, 2020, 6, "inputs/2020/day06", day06_2020::Day06);
-    define_solution!(args, 2020, 7, "inputs/2020/day07", day07_2020::Day07);
-    define_solution!(args, 2020, 8, "inputs/2020/day08", day08_2020::Day08);
-    define_solution!(args, 2020, 9, "inputs/2020/day09", day09_2020::Day09);
-    define_solution!(args, 2020, 10, "inputs/2020/day10", day10_2020::Day10);
-    define_solution!(args, 2020, 11, "inputs/2020/day11", day11_2020::Day11);
-    define_solution!(args, 2020, 12, "inputs/2020/day12", day12_2020::Day12);
-    define_solution!(args, 2020, 13, "inputs/2020/day13", day13_2020::Day13);
-    define_solution!(args, 2020, 14, "inputs/2020/day14", day14_2020::Day14);
-    define_solution!(args, 2020, 15, "inputs/2020/day15", day15_2020::Day15);
-    define_solution!(args, 2020, 16, "inputs/2020/day16", day16_2020::Day16);
-    define_solution!(args, 2020, 17, "inputs/2020/day17", day17_2020::Day17);
-    define_solution!(args, 2020, 18, "inputs/2020/day18", day18_2020::Day18);
-    define_solution!(args, 2020, 19, "inputs/2020/day19", day19_2020::Day19);
-    define_solution!(args, 2020, 20, "inputs/2020/day20", day20_2020::Day20);
-    define_solution!(args, 2020, 21, "inputs/2020/day21", day21_2020::Day21);
-    define_solution!(args, 2020, 22, "inputs/2020/day22", day22_2020::Day22);
-    define_solution!(args, 2020, 23, "inputs/2020/day23", day23_2020::Day23);
-    define_solution!(args, 2020, 24, "inputs/2020/day24", day24_2020::Day24);
-    define_solution!(args, 2020, 25, "inputs/2020/day25", day25_2020::Day25);
-    define_solution!(args, 2021, 1, "inputs/2021/day01", day01_2021::Day01);
-    define_solution!(args, 2021, 2, "inputs/2021/day02", day02_2021::Day02);
-    define_solution!(args, 2021, 3, "inputs/2021/day03", day03_2021::Day03);
-    define_solution!(args, 2021, 4, "inputs/2021/day04", day04_2021::Day04);
-    define_solution!(args, 2021, 5, "inputs/2021/day05", day05_2021::Day05);
-    define_solution!(args, 2021, 6, "inputs/2021/day06", day06_2021::Day06);
-    define_solution!(args, 2021, 7, "inputs/2021/day07", day07_2021::Day07);
-    define_solution!(args, 2021, 8, "inputs/2021/day08", day08_2021::Day08);
-    define_solution!(args, 2021, 9, "inputs/2021/day09", day09_2021::Day09);
-    define_solution!(args, 2021, 10, "inputs/2021/day10", day10_2021::Day10);
-    define_solution!(args, 2021, 11, "inputs/2021/day11", day11_2021::Day11);
-    define_solution!(args, 2021, 12, "inputs/2021/day12", day12_2021::Day12);
-    define_solution!(args, 2021, 13, "inputs/2021/day13", day13_2021::Day13);
-    define_solution!(args, 2021, 14, "inputs/2021/day14", day14_2021::Day14);
-    define_solution!(args, 2021, 15, "inputs/2021/day15", day15_2021::Day15);
-    define_solution!(args, 2021, 16, "inputs/2021/day16", day16_2021::Day16);
-    define_solution!(args, 2021, 17, "inputs/2021/day17", day17_2021::Day17);
-    define_solution!(args, 2021, 18, "inputs/2021/day18", day18_2021::Day18);
-    define_solution!(args, 2021, 19, "inputs/2021/day19", day19_2021::Day19);
-    define_solution!(args, 2021, 20, "inputs/2021/day20", day20_2021::Day20);
-    define_solution!(args, 2021, 21, "inputs/2021/day21", day21_2021::Day21);
-    define_solution!(args, 2021, 22, "inputs/2021/day22", day22_2021::Day22);
-    define_solution!(args, 2021, 24, "inputs/2021/day24", day24_2021::Day24);
-    define_solution!(args, 2022, 1, "inputs/2022/day01", day01_2022::Day01);
-    define_solution!(args, 2022, 2, "inputs/2022/day02", day02_2022::Day02);
-    define_solution!(args, 2022, 3, "inputs/2022/day03", day03_2022::Day03);
-    define_solution!(args, 2022, 4, "inputs/2022/day04", day04_2022::Day04);
-    define_solution!(args, 2022, 5, "inputs/2022/day05", day05_2022::Day05);
-    define_solution!(args, 2022, 6, "inputs/2022/day06", day06_2022::Day06);
-    define_solution!(args, 2022, 7, "inputs/2022/day07", day07_2022::Day07);
-    define_solution!(args, 2022, 8, "inputs/2022/day08", day08_2022::Day08);
-    define_solution!(args, 2022, 11, "inputs/2022/day11", day11_2022::Day11);
-    define_solution!(args, 2022, 10, "inputs/2022/day10", day10_2022::Day10);
-    define_solution!(args, 2023, 1, "inputs/2023/day01", day01_2023::Day01);
-    define_solution!(args, 2023, 2, "inputs/2023/day02", day02_2023::Day02);
-    define_solution!(args, 2023, 3, "inputs/2023/day03", day03_2023::Day03);
-    define_solution!(args, 2023, 4, "inputs/2023/day04", day04_2023::Day04);
-    define_solution!(args, 2023, 5, "inputs/2023/day05", day05_2023::Day05);
-    define_solution!(args, 2024, 1, "inputs/2024/day01", day01_2024::Day01);
-    define_solution!(args, 2024, 2, "inputs/2024/day02", day02_2024::Day02);
-    define_solution!(args, 2024, 3, "inputs/2024/day03", day03_2024::Day03);
-    define_solution!(args, 2024, 4, "inputs/2024/day04", day04_2024::Day04);
-    define_solution!(args, 2024, 5, "inputs/2024/day05", day05_2024::Day05);
-    define_solution!(args, 2024, 6, "inputs/2024/day06", day06_2024::Day06);
-    define_solution!(args, 2024, 7, "inputs/2024/day07", day07_2024::Day07);
-    define_solution!(args, 2024, 8, "inputs/2024/day08", day08_2024::Day08);
-    define_solution!(args, 2024, 9, "inputs/2024/day09", day09_2024::Day09);
-    define_solution!(args, 2024, 10, "inputs/2024/day10", day10_2024::Day10);
-    define_solution!(args, 2024, 11, "inputs/2024/day11", day11_2024::Day11);
-    define_solution!(args, 2025, 1, "inputs/2025/day01", day01_2025::Day01);
-    define_solution!(args, 2025, 2, "inputs/2025/day02", day02_2025::Day02);
-    define_solution!(args, 2025, 3, "inputs/2025/day03", day03_2025::Day03);
-    define_solution!(args, 2025, 4, "inputs/2025/day04", day04_2025::Day04);
-    // AUTOGENERATED SOLUTIONS END
-
-    println!("no solution found for year {}, day {}", args.year, args.day);
-}
\ No newline at end of file
+/// Returns the number of disagreements between the main and the alternative part runners
+fn run(config: &Config, args: RunArgs) -> anyhow::Result<usize> {
+    let Some(year) = args.year.or(config.default_year) else {
+        bail!("the event year has not been specified and there's no default one in the config")
+    };
+    let output = args.output.unwrap_or(config.output);
+
+    let solutions = registered_solutions()
+        .into_iter()
+        .filter(|s| s.year == year && args.day.is_none_or(|day| s.day == day))
+        .collect::<Vec<_>>();
+
+    if solutions.is_empty() {
+        match args.day {
+            Some(day) => println!("no solution found for year {year}, day {day}"),
+            None => println!("no solutions found for year {year}"),
+        }
+        return Ok(0);
+    }
+
+    let revision = history::current_revision(config.root());
+    let machine = history::current_machine();
+    let mut entries = Vec::new();
+    let mut disagreements = 0;
+    let mut answers = AnswersStore::load(config.answers_path())?;
+    for solution in &solutions {
+        if solutions.len() > 1 && output == OutputFormat::Text {
+            println!("### {} DAY {:02} ###", solution.year, solution.day);
+        }
+        let summary = match run_solution(config, solution, &args) {
+            Ok(Some(summary)) => summary,
+            Ok(None) => continue,
+            Err(err) => {
+                eprintln!("{err:#}");
+                continue;
+            }
+        };
+        disagreements += summary.disagreements().len();
+
+        if !args.uses_custom_input() {
+            let warnings = verify::known_wrong_answers(
+                &answers,
+                &args.profile,
+                solution.year,
+                solution.day,
+                &summary,
+            );
+            for (part, answer, warning) in warnings {
+                eprintln!("warning: the answer '{answer}' to part {part} is wrong, {warning}")
+            }
+        }
+
+        if args.record_answers {
+            let refused = verify::record_answers(
+                &mut answers,
+                &args.profile,
+                solution.year,
+                solution.day,
+                &summary,
+            );
+            for (part, answer, _) in refused {
+                eprintln!(
+                    "the answer '{answer}' to part {part} is known to be wrong, so it has not been recorded"
+                )
+            }
+        }
+
+        // different inputs have different timings, so only keep track of the main profile
+        if args.profile == DEFAULT_PROFILE && !args.uses_custom_input() {
+            entries.push(HistoryEntry::new(
+                solution.year,
+                solution.day,
+                &summary,
+                &revision,
+                &machine,
+            ))
+        }
+    }
+
+    if args.record_answers {
+        answers.save(config.answers_path())?;
+    }
+
+    if !args.no_history && !entries.is_empty() {
+        let history_path = args
+            .history
+            .unwrap_or_else(|| history::default_history_path(config));
+        history::append(history_path, &entries)?;
+    }
+
+    Ok(disagreements)
+}
+
+/// Returns the number of failed verifications
+fn verify(config: &Config, args: VerifyArgs) -> anyhow::Result<usize> {
+    let Some(year) = args.year.or(config.default_year) else {
+        bail!("the event year has not been specified and there's no default one in the config")
+    };
+    let solutions = registered_solutions()
+        .into_iter()
+        .filter(|s| s.year == year && args.day.is_none_or(|day| s.day == day))
+        .collect::<Vec<_>>();
+    let selected_profiles = if args.profile.is_empty() {
+        config.discover_profiles()
+    } else {
+        args.profile
+    };
+    if selected_profiles.is_empty() {
+        bail!("could not find inputs of any profile")
+    }
+
+    let answers = AnswersStore::load(config.answers_path())?;
+    let mut failures = 0;
+    for solution in &solutions {
+        for profile in &selected_profiles {
+            let prefix = format!("{} day {:02} [{profile}]", solution.year, solution.day);
+            let Ok(input) = read_input(config, solution, profile) else {
+                println!("{prefix}: no input");
+                continue;
+            };
+
+            let (_, result) = solve(solution, &input, args.plugin_dir.as_deref())?;
+            let summary = match result {
+                Ok(summary) => summary,
+                Err(err) => {
+                    failures += 1;
+                    println!("{prefix}: ✗ (failed to solve: {err})");
+                    continue;
+                }
+            };
+
+            let [part1, part2] =
+                verify::verify_day(&answers, profile, solution.year, solution.day, &summary);
+            failures += usize::from(part1.is_failure()) + usize::from(part2.is_failure());
+            println!("{prefix}: part 1 {part1}, part 2 {part2}");
+            for disagreement in summary.disagreements() {
+                failures += 1;
+                println!("{prefix}: ✗ ({disagreement})");
+            }
+        }
+    }
+
+    Ok(failures)
+}
+
+/// Returns the number of found regressions
+fn compare(config: &Config, args: CompareArgs) -> anyhow::Result<usize> {
+    let history_path = args
+        .history
+        .unwrap_or_else(|| history::default_history_path(config));
+    let machine = args.machine.unwrap_or_else(history::current_machine);
+    let build = args.build.unwrap_or_else(BuildProfile::current);
+    let entries = history::load(history_path)?
+        .into_iter()
+        .filter(|entry| entry.machine == machine && entry.build == Some(build))
+        .collect::<Vec<_>>();
+
+    let Some((latest_baseline, latest_candidate)) = history::latest_revisions(&entries) else {
+        bail!("there are no {build} timings recorded for machine '{machine}'")
+    };
+
+    let candidate = args
+        .candidate
+        .unwrap_or_else(|| latest_candidate.to_string());
+    let baseline = match args.baseline {
+        Some(baseline) => baseline,
+        None => latest_baseline
+            .map(ToString::to_string)
+            .context("there is no earlier revision in the history to compare against")?,
+    };
+
+    let regressions = history::compare(&entries, &baseline, &candidate, args.threshold)?;
+    if regressions.is_empty() {
+        println!(
+            "no part got more than {}% slower between {baseline} and {candidate}",
+            args.threshold
+        );
+    } else {
+        println!("parts that got slower between {baseline} and {candidate}:");
+        for regression in &regressions {
+            println!("{regression}");
+        }
+    }
+
+    Ok(regressions.len())
+}
+
+fn report(config: &Config, args: ReportArgs) -> anyhow::Result<()> {
+    let solutions = registered_solutions();
+
+    // the stars are only awarded for the verified answers, the same way as on the badges
+    let answers = AnswersStore::load(config.answers_path())?;
+    let progress = solutions
+        .iter()
+        .map(|solution| solution.year)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|year| {
+            let progress = EventProgress::new(year, &solutions, &answers, DEFAULT_PROFILE);
+            (year, progress)
+        })
+        .collect::<BTreeMap<_, _>>();
+    let stars = |solution: &Solution| {
+        progress
+            .get(&solution.year)
+            .map(|progress| progress.day(solution.day))
+            .unwrap_or(Stars::None)
+    };
+
+    let reports = if args.from_history {
+        let history_path = args
+            .history
+            .unwrap_or_else(|| history::default_history_path(config));
+        // the debug timings would not tell much about the solutions of a release build, and vice versa
+        let build = BuildProfile::current();
+        let entries = history::load(history_path)?;
+        solutions
+            .iter()
+            .map(|solution| {
+                entries
+                    .iter()
+                    .filter(|e| e.build == Some(build))
+                    .rfind(|e| e.year == solution.year && e.day == solution.day)
+                    .map(|entry| DayReport::from_history(entry, stars(solution)))
+                    .unwrap_or_else(|| {
+                        DayReport::not_run(solution.year, solution.day, stars(solution))
+                    })
+            })
+            .collect()
+    } else {
+        let mut reports = Vec::new();
+        for solution in &solutions {
+            let Ok(input) = read_input(config, solution, DEFAULT_PROFILE) else {
+                reports.push(DayReport::not_run(
+                    solution.year,
+                    solution.day,
+                    stars(solution),
+                ));
+                continue;
+            };
+            eprintln!("running {} day {:02}...", solution.year, solution.day);
+            let (_, result) = solve(solution, &input, args.plugin_dir.as_deref())?;
+            reports.push(DayReport::from_summary(
+                solution.year,
+                solution.day,
+                &result,
+                stars(solution),
+            ));
+        }
+        reports
+    };
+
+    let reports = report::complete_events(reports);
+    let markdown = report::render_markdown(&reports);
+
+    for (path, content) in [
+        (&args.markdown, &markdown),
+        (&args.html, &report::render_html(&reports)),
+    ] {
+        let path = config.resolve(path);
+        write_file(&path, content)?;
+        println!("written report to {}", path.display());
+    }
+
+    if args.update_readme {
+        let readme_path = config.resolve("README.md");
+        let readme = fs::read_to_string(&readme_path)?;
+        fs::write(&readme_path, report::update_readme(&readme, &markdown)?)?;
+        println!("updated {}", readme_path.display());
+    }
+
+    Ok(())
+}
+
+fn write_file(path: &Path, content: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn badges(config: &Config, args: BadgesArgs) -> anyhow::Result<()> {
+    let solutions = registered_solutions();
+    let years = solutions
+        .iter()
+        .map(|solution| solution.year)
+        .filter(|year| args.year.is_none_or(|selected| selected == *year))
+        .collect::<BTreeSet<_>>();
+    if years.is_empty() {
+        bail!("there are no registered solutions to derive the badges from")
+    }
+
+    // the badges are committed, so they must never be reset just because the answers are not available locally
+    let answers_path = config.answers_path();
+    if !answers_path.exists() {
+        bail!(
+            "there are no recorded answers in {} to derive the badges from",
+            answers_path.display()
+        )
+    }
+    let answers = AnswersStore::load(&answers_path)?;
+    let output_dir = config.resolve(&args.output_dir);
+    let mut updated = 0;
+    for year in years {
+        let progress = EventProgress::new(year, &solutions, &answers, &args.profile);
+        if progress.stars() == 0 {
+            println!(
+                "{year}: skipped, there are no verified answers of the '{}' profile",
+                args.profile
+            );
+            continue;
+        }
+        updated += 1;
+
+        let badge = serde_json::to_string_pretty(&progress.badge())? + "\n";
+        write_file(&badges::badge_path(&output_dir, year), &badge)?;
+
+        print!("{year}: {}/{} stars", progress.stars(), progress.total());
+        let part_one_only = progress.part_one_only();
+        if !part_one_only.is_empty() {
+            let days = part_one_only
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            print!(", only part 1 of day(s) {days}");
+        }
+        println!();
+
+        if let Some(calendar_dir) = &args.calendar_dir {
+            let path = badges::calendar_path(&config.resolve(calendar_dir), year);
+            write_file(&path, &progress.calendar_svg())?;
+            println!("written the calendar to {}", path.display());
+        }
+    }
+    if updated == 0 {
+        bail!(
+            "there are no verified answers of the '{}' profile to derive the badges from",
+            args.profile
+        )
+    }
+    println!("updated the badges in {}", output_dir.display());
+
+    Ok(())
+}
+
+fn serve(args: ServeArgs) -> anyhow::Result<()> {
+    let server = server::bind(&args.host, args.port)?;
+    println!("listening on http://{}", server.server_addr());
+    server::serve(&server, &registered_solutions(), args.plugin_dir.as_deref());
+    Ok(())
+}
+
+fn watch(config: &Config, args: WatchArgs) -> anyhow::Result<()> {
+    let Some(year) = args.year.or(config.default_year) else {
+        bail!("the event year has not been specified and there's no default one in the config")
+    };
+    let Some(solution) = registered_solutions()
+        .into_iter()
+        .find(|s| s.year == year && s.day == args.day)
+    else {
+        bail!("no solution found for year {year}, day {}", args.day)
+    };
+
+    let input = args
+        .custom_input_filepath
+        .unwrap_or_else(|| config.input_path(&args.profile, year, args.day));
+    let files = watch::WatchedFiles::new(&input, &config.examples_path(year, args.day));
+    watch::watch(
+        &solution,
+        &files,
+        args.plugin_dir.as_deref(),
+        Duration::from_millis(args.debounce),
+    )
+}
+
+fn describe(config: &Config, args: DescribeArgs) -> anyhow::Result<()> {
+    let Some(year) = args.year.or(config.default_year) else {
+        bail!("the event year has not been specified and there's no default one in the config")
+    };
+    let path = config.puzzle_path(year, args.day);
+    if !path.is_file() {
+        bail!(
+            "the description of {year} day {:02} has not been archived, get it with `aoc-init archive --year {year} --day {}`",
+            args.day,
+            args.day
+        )
+    }
+
+    let markdown =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let width = args.width.unwrap_or_else(describe::terminal_width);
+    let colour = !args.plain && std::io::stdout().is_terminal();
+    print!("{}", describe::render(&markdown, width, colour));
+    Ok(())
+}
+
+/// Runs the solutions, failing if any of the alternative part runners disagrees with its main one.
+fn run_checked(config: &Config, args: RunArgs) -> anyhow::Result<()> {
+    let disagreements = run(config, args)?;
+    if disagreements > 0 {
+        bail!("the alternative part runners disagreed {disagreements} time(s)")
+    }
+    Ok(())
+}
+
+fn execute(cli: Cli) -> anyhow::Result<()> {
+    let config = Config::load()?;
+
+    match cli.command {
+        None => run_checked(&config, cli.run),
+        Some(Commands::Run(args)) => run_checked(&config, args),
+        Some(Commands::Report(args)) => report(&config, args),
+        Some(Commands::Serve(args)) => serve(args),
+        Some(Commands::Watch(args)) => watch(&config, args),
+        Some(Commands::Describe(args)) => describe(&config, args),
+        Some(Commands::Badges(args)) => badges(&config, args),
+        Some(Commands::Tui(args)) => tui::run(config, registered_solutions(), args.plugin_dir),
+        Some(Commands::Verify(args)) => verify(&config, args).and_then(|failures| {
+            if failures > 0 {
+                bail!("{failures} verification(s) failed")
+            }
+            Ok(())
+        }),
+        Some(Commands::Compare(args)) => compare(&config, args).and_then(|regressions| {
+            if regressions > 0 {
+                bail!("found {regressions} regression(s)")
+            }
+            Ok(())
+        }),
+    }
+}
+
+fn main() -> ExitCode {
+    match execute(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
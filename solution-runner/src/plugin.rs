@@ -0,0 +1,132 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{Context, bail};
+use aoc_solution::plugin::{
+    ABI_VERSION_SYMBOL, AbiVersionFn, FREE_SYMBOL, FreeFn, PLUGIN_ABI_VERSION, RUN_SYMBOL, RunFn,
+};
+use aoc_solution::summary::DaySummary;
+use libloading::Library;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginInfo {
+    pub year: u16,
+    pub day: u8,
+    pub path: PathBuf,
+}
+
+/// Attempts to extract the year and day out of plugin file name, such as `libday05_2024.so`
+fn parse_plugin_name(file_name: &str) -> Option<(u16, u8)> {
+    let name = file_name
+        .strip_prefix(DLL_PREFIX)?
+        .strip_suffix(DLL_SUFFIX)?
+        .strip_prefix("day")?;
+    let (day, year) = name.split_once('_')?;
+    if day.len() != 2 || year.len() != 4 {
+        return None;
+    }
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// Finds all day plugins present in the specified directory.
+pub fn discover<P: AsRef<Path>>(dir: P) -> anyhow::Result<Vec<PluginInfo>> {
+    let dir = dir.as_ref();
+    let mut plugins = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if let Some((year, day)) = parse_plugin_name(file_name) {
+            plugins.push(PluginInfo { year, day, path })
+        }
+    }
+    plugins.sort_by_key(|plugin| (plugin.year, plugin.day));
+    Ok(plugins)
+}
+
+pub fn find<P: AsRef<Path>>(dir: P, year: u16, day: u8) -> anyhow::Result<Option<PluginInfo>> {
+    Ok(discover(dir)?
+        .into_iter()
+        .find(|plugin| plugin.year == year && plugin.day == day))
+}
+
+pub struct Plugin {
+    // keep the library loaded for as long as the function pointers are in use
+    _library: Library,
+    run: RunFn,
+    free: FreeFn,
+}
+
+impl Plugin {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+
+        // SAFETY: we only load libraries produced from our own day crates
+        // that do not run any initialisation routines
+        let library = unsafe { Library::new(path) }
+            .with_context(|| format!("failed to load plugin {}", path.display()))?;
+
+        // SAFETY: the symbol types match the ones emitted by the `Aoc` derive
+        let (abi_version, run, free) = unsafe {
+            let abi_version = library.get::<AbiVersionFn>(ABI_VERSION_SYMBOL)?;
+            let run = library.get::<RunFn>(RUN_SYMBOL)?;
+            let free = library.get::<FreeFn>(FREE_SYMBOL)?;
+            (abi_version(), *run, *free)
+        };
+
+        if abi_version != PLUGIN_ABI_VERSION {
+            bail!(
+                "plugin {} uses ABI version {abi_version} while {PLUGIN_ABI_VERSION} is expected",
+                path.display()
+            )
+        }
+
+        Ok(Plugin {
+            _library: library,
+            run,
+            free,
+        })
+    }
+
+    pub fn run(&self, input: &str) -> Result<DaySummary, String> {
+        // SAFETY: the input is valid UTF-8 and the result is freed by the very same plugin
+        // right after being copied
+        unsafe {
+            let result = (self.run)(input.as_ptr(), input.len());
+            let summary = result.to_summary();
+            (self.free)(result);
+            summary
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_plugin_names() {
+        let name = |raw: &str| format!("{DLL_PREFIX}{raw}{DLL_SUFFIX}");
+
+        assert_eq!(Some((2024, 5)), parse_plugin_name(&name("day05_2024")));
+        assert_eq!(Some((2019, 25)), parse_plugin_name(&name("day25_2019")));
+        assert_eq!(None, parse_plugin_name(&name("day5_2024")));
+        assert_eq!(None, parse_plugin_name(&name("aoc_common")));
+        assert_eq!(None, parse_plugin_name("day05_2024.rlib"));
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use aoc_common::answers::Part;
+use aoc_solution::AocSolution;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_PROFILES_DIRECTORY: &str = "profiles";
+
+const SAMPLING_FREQUENCY: i32 = 1000;
+
+// fast parts would not produce enough samples for a meaningful flamegraph,
+// so they're rerun until at least this much time has passed
+const MIN_PROFILING_TIME: Duration = Duration::from_secs(1);
+
+pub fn flamegraph_path(directory: &Path, year: u16, day: u8, part: Part) -> PathBuf {
+    let part = match part {
+        Part::One => 1,
+        Part::Two => 2,
+    };
+    directory.join(format!("{year}-{day:02}-part{part}.svg"))
+}
+
+/// Samples the stack while the selected part runs on the provided input and writes the flamegraph
+/// to the specified path. Returns the number of times the part has been run.
+pub fn profile_part<T: AocSolution>(
+    input: &str,
+    part: Part,
+    output: &Path,
+) -> anyhow::Result<usize> {
+    let parsed =
+        T::parse_input(input).map_err(|err| anyhow!("failed to parse the input: {err}"))?;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(SAMPLING_FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+
+    let start = Instant::now();
+    let mut iterations = 0;
+    while iterations == 0 || start.elapsed() < MIN_PROFILING_TIME {
+        let input = parsed.clone();
+        match part {
+            Part::One => {
+                let _ = black_box(T::part1(input));
+            }
+            Part::Two => {
+                let _ = black_box(T::part2(input));
+            }
+        }
+        iterations += 1;
+    }
+
+    let report = guard.report().build()?;
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    report.flamegraph(File::create(output)?)?;
+    Ok(iterations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flamegraph_naming() {
+        assert_eq!(
+            Path::new("profiles/2021-19-part1.svg"),
+            flamegraph_path(Path::new("profiles"), 2021, 19, Part::One)
+        );
+        assert_eq!(
+            Path::new("out/2024-05-part2.svg"),
+            flamegraph_path(Path::new("out"), 2024, 5, Part::Two)
+        );
+    }
+}
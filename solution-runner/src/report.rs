@@ -0,0 +1,421 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::badges::Stars;
+use crate::history::HistoryEntry;
+use crate::solutions::event_days;
+use anyhow::Context;
+use aoc_solution::summary::DaySummary;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Write};
+use std::time::Duration;
+
+pub const README_START_MARKER: &str = "<!-- SOLUTIONS REPORT START -->";
+pub const README_END_MARKER: &str = "<!-- SOLUTIONS REPORT END -->";
+
+const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Both parts have been solved
+    Solved,
+    /// Only the first part has been solved
+    Part1Only,
+    /// The solution produced answers, but none of them have been verified
+    Unverified,
+    /// The solution exists, but it did not produce any answers
+    Failing,
+    /// The solution exists, but there's no input available to run it with
+    NotRun,
+    /// There's no solution for this day
+    Missing,
+}
+
+impl Status {
+    /// The stars only come from the verified answers, the run itself just tells whether anything got answered.
+    fn new(stars: Stars, answered: bool) -> Self {
+        match stars {
+            Stars::Both => Status::Solved,
+            Stars::PartOneOnly => Status::Part1Only,
+            Stars::None if answered => Status::Unverified,
+            Stars::None => Status::Failing,
+        }
+    }
+
+    fn stars(self) -> Stars {
+        match self {
+            Status::Solved => Stars::Both,
+            Status::Part1Only => Stars::PartOneOnly,
+            _ => Stars::None,
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved => write!(f, "★★"),
+            Status::Part1Only => write!(f, "★"),
+            Status::Unverified => write!(f, "unverified"),
+            Status::Failing => write!(f, "failing"),
+            Status::NotRun => write!(f, "not run"),
+            Status::Missing => write!(f, "-"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    pub status: Status,
+    pub parsing: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl DayReport {
+    pub fn missing(year: u16, day: u8, status: Status) -> Self {
+        DayReport {
+            year,
+            day,
+            status,
+            parsing: None,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    /// Report of a day that has not been run, which still gets the stars of its verified answers.
+    pub fn not_run(year: u16, day: u8, stars: Stars) -> Self {
+        let status = match stars {
+            Stars::None => Status::NotRun,
+            stars => Status::new(stars, true),
+        };
+        DayReport::missing(year, day, status)
+    }
+
+    pub fn from_summary(
+        year: u16,
+        day: u8,
+        summary: &Result<DaySummary, String>,
+        stars: Stars,
+    ) -> Self {
+        let Ok(summary) = summary else {
+            return DayReport::missing(year, day, Status::Failing);
+        };
+
+        let part1 = summary.part1.answer.is_ok().then_some(summary.part1.taken);
+        let part2 = summary.part2.answer.is_ok().then_some(summary.part2.taken);
+        DayReport {
+            year,
+            day,
+            status: Status::new(stars, part1.is_some() || part2.is_some()),
+            parsing: Some(summary.parsing),
+            part1,
+            part2,
+        }
+    }
+
+    pub fn from_history(entry: &HistoryEntry, stars: Stars) -> Self {
+        DayReport {
+            year: entry.year,
+            day: entry.day,
+            status: Status::new(stars, entry.part1_ns.is_some() || entry.part2_ns.is_some()),
+            parsing: Some(Duration::from_nanos(entry.parsing_ns)),
+            part1: entry.part1_ns.map(Duration::from_nanos),
+            part2: entry.part2_ns.map(Duration::from_nanos),
+        }
+    }
+
+    fn source_path(&self) -> String {
+        format!("{}/day{:02}/src/lib.rs", self.year, self.day)
+    }
+
+    fn puzzle_url(&self) -> String {
+        format!("https://adventofcode.com/{}/day/{}", self.year, self.day)
+    }
+}
+
+/// Formats the duration using at most 3 significant digits, e.g. `1.23ms`
+fn format_compact(duration: Option<Duration>) -> String {
+    let Some(duration) = duration else {
+        return "-".to_string();
+    };
+
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+
+    if value < 10. {
+        format!("{value:.2}{unit}")
+    } else if value < 100. {
+        format!("{value:.1}{unit}")
+    } else {
+        format!("{value:.0}{unit}")
+    }
+}
+
+/// Fills all the gaps in the reports so that every day of each of the events would be included.
+pub fn complete_events(mut reports: Vec<DayReport>) -> Vec<DayReport> {
+    let years = reports.iter().map(|r| r.year).collect::<BTreeSet<_>>();
+    for year in years {
+        for day in 1..=event_days(year) {
+            if !reports.iter().any(|r| r.year == year && r.day == day) {
+                reports.push(DayReport::missing(year, day, Status::Missing))
+            }
+        }
+    }
+    // show the latest event first
+    reports.sort_by_key(|r| (std::cmp::Reverse(r.year), r.day));
+    reports
+}
+
+fn events(reports: &[DayReport]) -> Vec<(u16, Vec<&DayReport>)> {
+    let mut events: Vec<(u16, Vec<&DayReport>)> = Vec::new();
+    for report in reports {
+        match events.last_mut() {
+            Some((year, days)) if *year == report.year => days.push(report),
+            _ => events.push((report.year, vec![report])),
+        }
+    }
+    events
+}
+
+fn stars(days: &[&DayReport]) -> usize {
+    days.iter().map(|r| r.status.stars().count()).sum()
+}
+
+/// Renders Markdown tables with links relative to the project root, so that they could be embedded in the README.
+pub fn render_markdown(reports: &[DayReport]) -> String {
+    let mut out = String::new();
+    for (year, days) in events(reports) {
+        let _ = writeln!(out, "### {year} ({}/{} ★)", stars(&days), days.len() * 2);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "| Day | Status | Parsing | Part 1 | Part 2 | Source |");
+        let _ = writeln!(out, "|----:|:------:|--------:|-------:|-------:|--------|");
+        for r in days {
+            let source = if r.status == Status::Missing {
+                "-".to_string()
+            } else {
+                let path = r.source_path();
+                format!("[{path}]({path})")
+            };
+            let _ = writeln!(
+                out,
+                "| [{}]({}) | {} | {} | {} | {} | {source} |",
+                r.day,
+                r.puzzle_url(),
+                r.status,
+                format_compact(r.parsing),
+                format_compact(r.part1),
+                format_compact(r.part2),
+            );
+        }
+        let _ = writeln!(out);
+    }
+    out
+}
+
+/// Renders a self-contained HTML page with links pointing to the upstream repository.
+pub fn render_html(reports: &[DayReport]) -> String {
+    let mut out = String::new();
+    out.push_str(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code solutions</title>
+<style>
+body { font-family: monospace; background: #0f0f23; color: #cccccc; }
+a { color: #009900; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { padding: 0.2em 1em; text-align: right; border-bottom: 1px solid #333340; }
+.solved { color: #ffff66; }
+.part1-only { color: #9999cc; }
+.failing { color: #ff6666; }
+</style>
+</head>
+<body>
+<h1>Advent of Code solutions</h1>
+"#,
+    );
+
+    for (year, days) in events(reports) {
+        let _ = writeln!(
+            out,
+            "<h2>{year} ({}/{} ★)</h2>",
+            stars(&days),
+            days.len() * 2
+        );
+        out.push_str(
+            "<table>\n<tr><th>Day</th><th>Status</th><th>Parsing</th><th>Part 1</th><th>Part 2</th><th>Source</th></tr>\n",
+        );
+        for r in days {
+            let class = match r.status {
+                Status::Solved => "solved",
+                Status::Part1Only => "part1-only",
+                Status::Failing => "failing",
+                Status::Unverified | Status::NotRun | Status::Missing => "",
+            };
+            let source = if r.status == Status::Missing {
+                "-".to_string()
+            } else {
+                let path = r.source_path();
+                format!(r#"<a href="{REPOSITORY}/blob/master/{path}">{path}</a>"#)
+            };
+            let _ = writeln!(
+                out,
+                r#"<tr><td><a href="{}">{}</a></td><td class="{class}">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{source}</td></tr>"#,
+                r.puzzle_url(),
+                r.day,
+                r.status,
+                format_compact(r.parsing),
+                format_compact(r.part1),
+                format_compact(r.part2),
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Replaces the content between the report markers in the README with the provided report.
+pub fn update_readme(readme: &str, report: &str) -> anyhow::Result<String> {
+    let start = readme
+        .find(README_START_MARKER)
+        .context("the README does not contain the report start marker")?
+        + README_START_MARKER.len();
+    let end = readme
+        .find(README_END_MARKER)
+        .context("the README does not contain the report end marker")?;
+    anyhow::ensure!(start <= end, "the README report markers are out of order");
+
+    Ok(format!(
+        "{}\n\n{}{}",
+        &readme[..start],
+        report,
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_solution::summary::PartSummary;
+
+    #[test]
+    fn compact_duration_formatting() {
+        assert_eq!("-", format_compact(None));
+        assert_eq!("123ns", format_compact(Some(Duration::from_nanos(123))));
+        assert_eq!("1.50µs", format_compact(Some(Duration::from_nanos(1500))));
+        assert_eq!("12.3ms", format_compact(Some(Duration::from_micros(12345))));
+        assert_eq!("2.00s", format_compact(Some(Duration::from_secs(2))));
+    }
+
+    #[test]
+    fn deriving_status_from_verified_answers() {
+        let summary: Result<DaySummary, String> = Ok(DaySummary {
+            parsing: Duration::from_millis(1),
+            part1: PartSummary {
+                taken: Duration::from_millis(2),
+                answer: Ok("1".to_string()),
+                alternatives: Vec::new(),
+            },
+            part2: PartSummary {
+                taken: Duration::from_millis(3),
+                answer: Ok("2".to_string()),
+                alternatives: Vec::new(),
+            },
+        });
+
+        let status = |stars| DayReport::from_summary(2024, 1, &summary, stars).status;
+        assert_eq!(Status::Solved, status(Stars::Both));
+        assert_eq!(Status::Part1Only, status(Stars::PartOneOnly));
+        // answering both parts is not enough without verifying the answers
+        assert_eq!(Status::Unverified, status(Stars::None));
+
+        let failed = Err("oops".to_string());
+        assert_eq!(
+            Status::Failing,
+            DayReport::from_summary(2024, 1, &failed, Stars::Both).status
+        );
+
+        assert_eq!(
+            Status::Solved,
+            DayReport::not_run(2024, 1, Stars::Both).status
+        );
+        assert_eq!(
+            Status::NotRun,
+            DayReport::not_run(2024, 1, Stars::None).status
+        );
+    }
+
+    #[test]
+    fn completing_events() {
+        let reports = vec![
+            DayReport::missing(2024, 2, Status::NotRun),
+            DayReport::missing(2025, 1, Status::Failing),
+        ];
+        let completed = complete_events(reports);
+
+        assert_eq!(25 + 12, completed.len());
+        assert_eq!((2025, 1, Status::Failing), {
+            let r = &completed[0];
+            (r.year, r.day, r.status)
+        });
+        assert_eq!(Status::Missing, completed[1].status);
+        assert_eq!(Status::NotRun, completed[13].status);
+    }
+
+    #[test]
+    fn rendering_markdown() {
+        let reports = vec![DayReport {
+            year: 2024,
+            day: 9,
+            status: Status::Solved,
+            parsing: Some(Duration::from_micros(10)),
+            part1: Some(Duration::from_millis(1)),
+            part2: Some(Duration::from_millis(200)),
+        }];
+
+        let expected = "### 2024 (2/2 ★)
+
+| Day | Status | Parsing | Part 1 | Part 2 | Source |
+|----:|:------:|--------:|-------:|-------:|--------|
+| [9](https://adventofcode.com/2024/day/9) | ★★ | 10.0µs | 1.00ms | 200ms | [2024/day09/src/lib.rs](2024/day09/src/lib.rs) |
+
+";
+        assert_eq!(expected, render_markdown(&reports));
+    }
+
+    #[test]
+    fn updating_readme() {
+        let readme = format!("# title\n{README_START_MARKER}\nold\n{README_END_MARKER}\nfooter");
+        let expected =
+            format!("# title\n{README_START_MARKER}\n\nnew\n{README_END_MARKER}\nfooter");
+        assert_eq!(expected, update_readme(&readme, "new\n").unwrap());
+
+        assert!(update_readme("no markers", "new").is_err());
+    }
+}
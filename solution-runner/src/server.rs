@@ -0,0 +1,341 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::solutions::Solution;
+use anyhow::anyhow;
+use aoc_solution::plugin::panic_message;
+use aoc_solution::summary::{AlternativeSummary, DaySummary, PartSummary};
+use serde::Serialize;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Debug, Serialize)]
+pub struct SolutionInfo {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    /// Registered location of the default input, relative to the project root
+    pub input: &'static str,
+    pub has_generator: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnswerJson {
+    pub taken_ns: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AlternativeJson {
+    pub name: String,
+    #[serde(flatten)]
+    pub result: AnswerJson,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartJson {
+    #[serde(flatten)]
+    pub result: AnswerJson,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<AlternativeJson>,
+}
+
+/// JSON representation of the `DayResult` of a single run.
+#[derive(Debug, Serialize)]
+pub struct DayResultJson {
+    pub year: u16,
+    pub day: u8,
+    pub solver: String,
+    pub parsing_ns: u64,
+    pub part1: PartJson,
+    pub part2: PartJson,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorJson {
+    error: String,
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl AnswerJson {
+    fn new(taken: Duration, answer: &Result<String, String>) -> Self {
+        AnswerJson {
+            taken_ns: nanos(taken),
+            answer: answer.as_ref().ok().cloned(),
+            error: answer.as_ref().err().cloned(),
+        }
+    }
+}
+
+impl From<&AlternativeSummary> for AlternativeJson {
+    fn from(alt: &AlternativeSummary) -> Self {
+        AlternativeJson {
+            name: alt.name.clone(),
+            result: AnswerJson::new(alt.taken, &alt.answer),
+        }
+    }
+}
+
+impl From<&PartSummary> for PartJson {
+    fn from(part: &PartSummary) -> Self {
+        PartJson {
+            result: AnswerJson::new(part.taken, &part.answer),
+            alternatives: part.alternatives.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl DayResultJson {
+    pub fn new(year: u16, day: u8, solver: String, summary: &DaySummary) -> Self {
+        DayResultJson {
+            year,
+            day,
+            solver,
+            parsing_ns: nanos(summary.parsing),
+            part1: (&summary.part1).into(),
+            part2: (&summary.part2).into(),
+        }
+    }
+}
+
+/// Response to a request, as its status code and the JSON body.
+pub type RouteResponse = (u16, String);
+
+fn json<T: Serialize>(status: u16, value: &T) -> RouteResponse {
+    match serde_json::to_string(value) {
+        Ok(body) => (status, body),
+        Err(err) => error(500, err.to_string()),
+    }
+}
+
+fn error<S: Into<String>>(status: u16, message: S) -> RouteResponse {
+    let body = ErrorJson {
+        error: message.into(),
+    };
+    (
+        status,
+        serde_json::to_string(&body).unwrap_or_else(|_| "{}".to_string()),
+    )
+}
+
+fn run_day(
+    solutions: &[Solution],
+    plugin_dir: Option<&Path>,
+    year: &str,
+    day: &str,
+    input: &str,
+) -> RouteResponse {
+    let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) else {
+        return error(400, format!("'{year}/{day}' is not a valid puzzle"));
+    };
+    let Some(solution) = solutions.iter().find(|s| s.year == year && s.day == day) else {
+        return error(404, format!("no solution found for year {year}, day {day}"));
+    };
+
+    // plenty of solutions panic on malformed inputs, which must not take the whole server down
+    match catch_unwind(AssertUnwindSafe(|| {
+        crate::solve(solution, input, plugin_dir)
+    })) {
+        Ok(Ok((solver, Ok(summary)))) => {
+            json(200, &DayResultJson::new(year, day, solver, &summary))
+        }
+        Ok(Ok((_, Err(err)))) => error(422, err),
+        Ok(Err(err)) => error(500, format!("{err:#}")),
+        Err(payload) => error(
+            500,
+            format!(
+                "the solution has panicked: {}",
+                panic_message(payload.as_ref())
+            ),
+        ),
+    }
+}
+
+/// Routes the request to the appropriate handler.
+pub fn handle(
+    solutions: &[Solution],
+    plugin_dir: Option<&Path>,
+    method: &Method,
+    url: &str,
+    body: &str,
+) -> RouteResponse {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["solutions"]) => {
+            let infos = solutions
+                .iter()
+                .map(|s| SolutionInfo {
+                    year: s.year,
+                    day: s.day,
+                    name: s.name,
+                    input: s.input,
+                    has_generator: s.generator.is_some(),
+                })
+                .collect::<Vec<_>>();
+            json(200, &infos)
+        }
+        (Method::Post, ["run", year, day]) => run_day(solutions, plugin_dir, year, day, body),
+        (_, ["solutions"] | ["run", _, _]) => error(405, "method not allowed"),
+        _ => error(404, format!("'{path}' not found")),
+    }
+}
+
+fn respond(solutions: &[Solution], plugin_dir: Option<&Path>, mut request: Request) {
+    let mut body = String::new();
+    let (status, content) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => handle(
+            solutions,
+            plugin_dir,
+            request.method(),
+            request.url(),
+            &body,
+        ),
+        Err(_) => error(400, "the request body is not valid UTF-8"),
+    };
+
+    let mut response = Response::from_string(content).with_status_code(status);
+    if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+        response = response.with_header(header);
+    }
+    if let Err(err) = request.respond(response) {
+        eprintln!("failed to send the response: {err}")
+    }
+}
+
+/// Handles the incoming requests until the server gets shut down.
+pub fn serve(server: &Server, solutions: &[Solution], plugin_dir: Option<&Path>) {
+    for request in server.incoming_requests() {
+        respond(solutions, plugin_dir, request)
+    }
+}
+
+pub fn bind(host: &str, port: u16) -> anyhow::Result<Server> {
+    Server::http((host, port)).map_err(|err| anyhow!("failed to bind to {host}:{port}: {err}"))
+}
+
+#[cfg(test)]
+#[cfg(feature = "year2024")]
+mod tests {
+    use super::*;
+    use crate::solutions::registered_solutions;
+    use std::sync::Arc;
+
+    #[test]
+    fn routing() {
+        let solutions = registered_solutions();
+
+        let (status, body) = handle(&solutions, None, &Method::Get, "/solutions", "");
+        assert_eq!(200, status);
+        assert!(
+            body.contains(
+                r#"{"year":2024,"day":9,"name":"day09_2024::Day09","input":"inputs/2024/day09","has_generator":true}"#
+            )
+        );
+
+        let (status, body) = handle(
+            &solutions,
+            None,
+            &Method::Post,
+            "/run/2024/9",
+            "2333133121414131402",
+        );
+        assert_eq!(200, status);
+        let result: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!("day09_2024::Day09", result["solver"]);
+        assert_eq!("1928", result["part1"]["answer"]);
+        assert_eq!("2858", result["part2"]["answer"]);
+
+        assert_eq!(
+            404,
+            handle(&solutions, None, &Method::Post, "/run/2024/26", "").0
+        );
+        assert_eq!(
+            400,
+            handle(&solutions, None, &Method::Post, "/run/foo/9", "").0
+        );
+        assert_eq!(
+            405,
+            handle(&solutions, None, &Method::Get, "/run/2024/9", "").0
+        );
+        assert_eq!(404, handle(&solutions, None, &Method::Get, "/foo", "").0);
+    }
+
+    #[test]
+    fn serving_local_clients() {
+        let server = Arc::new(bind("127.0.0.1", 0).unwrap());
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = std::thread::spawn({
+            let server = Arc::clone(&server);
+            move || serve(&server, &registered_solutions(), None)
+        });
+
+        let client = reqwest::blocking::Client::new();
+        let base = format!("http://127.0.0.1:{port}");
+        let solutions = client
+            .get(format!("{base}/solutions"))
+            .send()
+            .unwrap()
+            .text()
+            .unwrap();
+        let solutions: serde_json::Value = serde_json::from_str(&solutions).unwrap();
+        assert!(solutions.as_array().unwrap().len() > 1);
+
+        let response = client
+            .post(format!("{base}/run/2024/9"))
+            .body("2333133121414131402")
+            .send()
+            .unwrap();
+        assert_eq!(200, response.status().as_u16());
+        let result: serde_json::Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+        assert_eq!("1928", result["part1"]["answer"]);
+
+        // the solution panics on the malformed input, but the server keeps on running
+        let response = client
+            .post(format!("{base}/run/2024/9"))
+            .body("#")
+            .send()
+            .unwrap();
+        assert_eq!(500, response.status().as_u16());
+        let result: serde_json::Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+        assert!(
+            result["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("the solution has panicked")
+        );
+
+        let response = client
+            .post(format!("{base}/run/2024/9"))
+            .body("2333133121414131402")
+            .send()
+            .unwrap();
+        assert_eq!(200, response.status().as_u16());
+
+        server.unblock();
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,597 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::badges::{EventProgress, Stars};
+use crate::history::{self, HistoryEntry};
+use crate::report::{DayReport, Status};
+use crate::solutions::{Solution, event_days};
+use aoc_common::answers::{AnswersStore, DEFAULT_PROFILE};
+use aoc_common::config::Config;
+use aoc_solution::plugin::panic_message;
+use aoc_solution::summary::{DaySummary, PartSummary};
+use humantime::format_duration;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::{BTreeMap, BTreeSet};
+use std::panic::{self, AssertUnwindSafe, catch_unwind};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MAX_DAYS: u8 = 25;
+const TICK: Duration = Duration::from_millis(100);
+const SOLVER_THREAD: &str = "solver";
+
+type RunResult = (String, Result<DaySummary, String>);
+
+#[derive(Debug)]
+enum DayState {
+    Finished {
+        solver: String,
+        result: Result<DaySummary, String>,
+    },
+    Unavailable(String),
+}
+
+struct Running {
+    year: u16,
+    day: u8,
+    started: Instant,
+    receiver: Receiver<anyhow::Result<RunResult>>,
+}
+
+pub struct App {
+    config: Config,
+    solutions: Vec<Solution>,
+    years: Vec<u16>,
+    plugin_dir: Option<PathBuf>,
+    selected_year: usize,
+    selected_day: u8,
+    states: BTreeMap<(u16, u8), DayState>,
+    /// Statuses of the days from before the session, shown until they get run again
+    recorded: BTreeMap<(u16, u8), Status>,
+    /// Stars of the answers verified for the default profile
+    progress: BTreeMap<u16, EventProgress>,
+    running: Option<Running>,
+    quit: bool,
+}
+
+/// Statuses of the days known before anything gets run, i.e. from the latest recorded run of each day,
+/// or, if it has never been timed, from the verified answers alone.
+fn recorded_statuses(
+    solutions: &[Solution],
+    history: &[HistoryEntry],
+    stars: impl Fn(u16, u8) -> Stars,
+) -> BTreeMap<(u16, u8), Status> {
+    solutions
+        .iter()
+        .filter_map(|s| {
+            let stars = stars(s.year, s.day);
+            let report = match history.iter().rfind(|e| e.year == s.year && e.day == s.day) {
+                Some(entry) => DayReport::from_history(entry, stars),
+                None => DayReport::not_run(s.year, s.day, stars),
+            };
+            (report.status != Status::NotRun).then_some(((s.year, s.day), report.status))
+        })
+        .collect()
+}
+
+/// Runs the solution in the background. Its panics get caught and reported like any other error,
+/// so that they would never unwind past the solver thread.
+fn spawn_solver(
+    solution: Solution,
+    input: String,
+    plugin_dir: Option<PathBuf>,
+) -> std::io::Result<Receiver<anyhow::Result<RunResult>>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name(SOLVER_THREAD.to_string())
+        .spawn(move || {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                crate::solve(&solution, &input, plugin_dir.as_deref())
+            }))
+            .unwrap_or_else(|payload| {
+                Err(anyhow::anyhow!(
+                    "the solution has panicked: {}",
+                    panic_message(payload.as_ref())
+                ))
+            });
+            let _ = sender.send(result);
+        })?;
+    Ok(receiver)
+}
+
+impl App {
+    pub fn new(config: Config, solutions: Vec<Solution>, plugin_dir: Option<PathBuf>) -> Self {
+        let years = solutions
+            .iter()
+            .map(|s| s.year)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            // show the latest event first
+            .rev()
+            .collect();
+
+        App {
+            config,
+            solutions,
+            years,
+            plugin_dir,
+            selected_year: 0,
+            selected_day: 1,
+            states: BTreeMap::new(),
+            recorded: BTreeMap::new(),
+            progress: BTreeMap::new(),
+            running: None,
+            quit: false,
+        }
+    }
+
+    pub fn with_recorded_statuses(
+        mut self,
+        history: &[HistoryEntry],
+        answers: &AnswersStore,
+    ) -> Self {
+        self.progress = self
+            .years
+            .iter()
+            .map(|&year| {
+                let progress = EventProgress::new(year, &self.solutions, answers, DEFAULT_PROFILE);
+                (year, progress)
+            })
+            .collect();
+        self.recorded =
+            recorded_statuses(&self.solutions, history, |year, day| self.stars(year, day));
+        self
+    }
+
+    fn stars(&self, year: u16, day: u8) -> Stars {
+        self.progress
+            .get(&year)
+            .map(|progress| progress.day(day))
+            .unwrap_or(Stars::None)
+    }
+
+    fn year(&self) -> Option<u16> {
+        self.years.get(self.selected_year).copied()
+    }
+
+    fn solution(&self, year: u16, day: u8) -> Option<&Solution> {
+        self.solutions
+            .iter()
+            .find(|s| s.year == year && s.day == day)
+    }
+
+    fn selected_solution(&self) -> Option<&Solution> {
+        self.solution(self.year()?, self.selected_day)
+    }
+
+    fn status(&self, year: u16, day: u8) -> Status {
+        if self.solution(year, day).is_none() {
+            return Status::Missing;
+        }
+        match self.states.get(&(year, day)) {
+            Some(DayState::Finished { result, .. }) => {
+                DayReport::from_summary(year, day, result, self.stars(year, day)).status
+            }
+            Some(DayState::Unavailable(_)) | None => self
+                .recorded
+                .get(&(year, day))
+                .copied()
+                .unwrap_or(Status::NotRun),
+        }
+    }
+
+    fn move_selection(&mut self, years: isize, days: isize) {
+        if self.years.is_empty() {
+            return;
+        }
+        let last_year = self.years.len() as isize - 1;
+        self.selected_year = (self.selected_year as isize + years).clamp(0, last_year) as usize;
+
+        let last_day = self.year().map(event_days).unwrap_or(MAX_DAYS) as isize;
+        self.selected_day = (self.selected_day as isize + days).clamp(1, last_day) as u8;
+    }
+
+    fn run_selected(&mut self) {
+        if self.running.is_some() {
+            return;
+        }
+        let Some(solution) = self.selected_solution().copied() else {
+            return;
+        };
+
+        let input = match crate::read_input(&self.config, &solution, DEFAULT_PROFILE) {
+            Ok(input) => input,
+            Err(err) => {
+                self.states.insert(
+                    (solution.year, solution.day),
+                    DayState::Unavailable(format!("{err:#}")),
+                );
+                return;
+            }
+        };
+
+        let receiver = match spawn_solver(solution, input, self.plugin_dir.clone()) {
+            Ok(receiver) => receiver,
+            Err(err) => {
+                self.states.insert(
+                    (solution.year, solution.day),
+                    DayState::Unavailable(format!("failed to start the solver: {err}")),
+                );
+                return;
+            }
+        };
+        self.running = Some(Running {
+            year: solution.year,
+            day: solution.day,
+            started: Instant::now(),
+            receiver,
+        });
+    }
+
+    /// Checks whether the solution running in the background has finished.
+    fn poll_running(&mut self) {
+        let Some(running) = &self.running else {
+            return;
+        };
+        let state = match running.receiver.try_recv() {
+            Err(TryRecvError::Empty) => return,
+            Ok(Ok((solver, result))) => DayState::Finished { solver, result },
+            Ok(Err(err)) => DayState::Unavailable(format!("{err:#}")),
+            Err(TryRecvError::Disconnected) => {
+                DayState::Unavailable("the solver has stopped without any result".to_string())
+            }
+        };
+        self.states.insert((running.year, running.day), state);
+        self.running = None;
+    }
+
+    pub fn on_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Left | KeyCode::Char('h') => self.move_selection(0, -1),
+            KeyCode::Right | KeyCode::Char('l') => self.move_selection(0, 1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1, 0),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1, 0),
+            KeyCode::Enter | KeyCode::Char('r') => self.run_selected(),
+            _ => {}
+        }
+    }
+
+    fn calendar(&self) -> Table<'_> {
+        let header = Row::new(
+            std::iter::once(Cell::from("year"))
+                .chain((1..=MAX_DAYS).map(|day| Cell::from(format!("{day:>2}")))),
+        )
+        .style(Style::new().add_modifier(Modifier::BOLD));
+
+        let rows = self.years.iter().enumerate().map(|(i, &year)| {
+            let days = (1..=MAX_DAYS).map(move |day| {
+                if day > event_days(year) {
+                    return Cell::from("");
+                }
+                let status = self.status(year, day);
+                let (symbol, color) = match status {
+                    Status::Solved => ("**", Color::Yellow),
+                    Status::Part1Only => ("* ", Color::Blue),
+                    Status::Unverified => ("? ", Color::White),
+                    Status::Failing => ("✗ ", Color::Red),
+                    Status::NotRun => ("· ", Color::Gray),
+                    Status::Missing => ("  ", Color::DarkGray),
+                };
+                let mut style = Style::new().fg(color);
+                if i == self.selected_year && day == self.selected_day {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Cell::from(symbol).style(style)
+            });
+            Row::new(std::iter::once(Cell::from(year.to_string())).chain(days))
+        });
+
+        let widths = std::iter::once(Constraint::Length(4))
+            .chain((1..=MAX_DAYS).map(|_| Constraint::Length(2)));
+        Table::new(rows, widths).header(header).block(
+            Block::bordered().title(" Advent of Code [arrows/hjkl: move, enter/r: run, q: quit] "),
+        )
+    }
+
+    fn timing_lines(&self) -> Vec<Line<'_>> {
+        if let Some(running) = &self.running {
+            return vec![Line::from(format!(
+                "running {} day {:02}... {}",
+                running.year,
+                running.day,
+                format_duration(Duration::from_millis(
+                    running.started.elapsed().as_millis() as u64
+                ))
+            ))];
+        }
+
+        let part_lines = |label: &str, part: &PartSummary| {
+            std::iter::once(Line::from(format!(
+                "{:<12}{}",
+                format!("{label}:"),
+                format_duration(part.taken)
+            )))
+            .chain(part.alternatives.iter().map(|alt| {
+                Line::from(format!(
+                    "{:<12}{}",
+                    format!("  {}:", alt.name),
+                    format_duration(alt.taken)
+                ))
+            }))
+            .collect::<Vec<_>>()
+        };
+
+        match self.selected_state() {
+            Some(DayState::Finished {
+                solver,
+                result: Ok(summary),
+            }) => {
+                let mut lines = vec![
+                    Line::from(solver.as_str()),
+                    Line::from(format!(
+                        "{:<12}{}",
+                        "PARSING:",
+                        format_duration(summary.parsing)
+                    )),
+                ];
+                lines.extend(part_lines("PART 1", &summary.part1));
+                lines.extend(part_lines("PART 2", &summary.part2));
+                lines
+            }
+            Some(DayState::Finished {
+                solver,
+                result: Err(err),
+            }) => vec![
+                Line::from(solver.as_str()),
+                Line::from(format!("failed to solve: {err}")),
+            ],
+            Some(DayState::Unavailable(err)) => vec![Line::from(err.as_str())],
+            None if self.selected_solution().is_some() => {
+                vec![Line::from("press enter to run")]
+            }
+            None => vec![Line::from("no solution")],
+        }
+    }
+
+    // answers are displayed as they are, so that multi-line outputs, such as grids of pixels, keep their shape
+    fn answers_text(&self) -> String {
+        let answer = |answer: &Result<String, String>| match answer {
+            Ok(answer) => answer.clone(),
+            Err(err) => format!("failed to solve: {err}"),
+        };
+        match self.selected_state() {
+            Some(DayState::Finished {
+                result: Ok(summary),
+                ..
+            }) => format!(
+                "PART 1:\n{}\n\nPART 2:\n{}",
+                answer(&summary.part1.answer),
+                answer(&summary.part2.answer)
+            ),
+            _ => String::new(),
+        }
+    }
+
+    fn selected_state(&self) -> Option<&DayState> {
+        self.states.get(&(self.year()?, self.selected_day))
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let calendar_height = self.years.len() as u16 + 3;
+        let [calendar_area, details_area] =
+            Layout::vertical([Constraint::Length(calendar_height), Constraint::Min(0)])
+                .areas(frame.area());
+        let [timing_area, answers_area] =
+            Layout::horizontal([Constraint::Length(40), Constraint::Min(0)]).areas(details_area);
+
+        let title = match self.year() {
+            Some(year) => format!(" {year} day {:02} ", self.selected_day),
+            None => " no solutions ".to_string(),
+        };
+
+        frame.render_widget(self.calendar(), calendar_area);
+        frame.render_widget(
+            Paragraph::new(self.timing_lines())
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(title)),
+            timing_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.answers_text()).block(Block::bordered().title(" answers ")),
+            answers_area,
+        );
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> anyhow::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.render(frame))?;
+        if event::poll(TICK)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.on_key(key.code)
+        }
+        app.poll_running();
+    }
+    Ok(())
+}
+
+pub fn run(
+    config: Config,
+    solutions: Vec<Solution>,
+    plugin_dir: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    // neither of the files has to exist, in which case the days are simply shown as not run
+    let history = history::load(history::default_history_path(&config)).unwrap_or_default();
+    let answers = AnswersStore::load(config.answers_path()).unwrap_or_default();
+    let mut app =
+        App::new(config, solutions, plugin_dir).with_recorded_statuses(&history, &answers);
+    let mut terminal = ratatui::init();
+    // the panics of the solutions are caught by the solver, so they must not restore the terminal in the meantime
+    let restore_on_panic = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() != Some(SOLVER_THREAD) {
+            restore_on_panic(info)
+        }
+    }));
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+#[cfg(feature = "year2024")]
+mod tests {
+    use super::*;
+    use crate::solutions::registered_solutions;
+    use aoc_common::answers::Part;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn app() -> App {
+        let solutions = registered_solutions()
+            .into_iter()
+            .filter(|s| s.year == 2024)
+            .collect();
+        App::new(Config::default(), solutions, None)
+    }
+
+    #[test]
+    fn navigating_calendar() {
+        let mut app = app();
+        assert_eq!((Some(2024), 1), (app.year(), app.selected_day));
+
+        app.on_key(KeyCode::Left);
+        assert_eq!(1, app.selected_day);
+        for _ in 0..30 {
+            app.on_key(KeyCode::Right);
+        }
+        assert_eq!(25, app.selected_day);
+        app.on_key(KeyCode::Down);
+        assert_eq!(Some(2024), app.year());
+
+        assert_eq!(Status::Missing, app.status(2024, 25));
+        assert_eq!(Status::NotRun, app.status(2024, 9));
+
+        app.on_key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn seeding_recorded_statuses() {
+        let summary = DaySummary {
+            parsing: Duration::from_millis(1),
+            part1: PartSummary {
+                taken: Duration::from_millis(2),
+                answer: Ok("1928".to_string()),
+                alternatives: Vec::new(),
+            },
+            part2: PartSummary {
+                taken: Duration::from_millis(3),
+                answer: Err("oops".to_string()),
+                alternatives: Vec::new(),
+            },
+        };
+        let history = vec![HistoryEntry::new(2024, 9, &summary, "abc", "test")];
+        let mut answers = AnswersStore::default();
+        answers.record_correct(DEFAULT_PROFILE, 2024, 1, Part::One, "11");
+        answers.record_correct(DEFAULT_PROFILE, 2024, 1, Part::Two, "31");
+        answers.record_correct(DEFAULT_PROFILE, 2024, 9, Part::Two, "2858");
+        answers.record_correct(DEFAULT_PROFILE, 2024, 3, Part::One, "161");
+
+        let app = app().with_recorded_statuses(&history, &answers);
+        assert_eq!(Status::Solved, app.status(2024, 1));
+        assert_eq!(Status::Part1Only, app.status(2024, 3));
+        // the second part alone does not earn any stars, so the answered run stays unverified
+        assert_eq!(Status::Unverified, app.status(2024, 9));
+        assert_eq!(Status::NotRun, app.status(2024, 2));
+        assert_eq!(Status::Missing, app.status(2024, 25));
+    }
+
+    #[test]
+    fn rendering_multiline_answers() {
+        let mut app = app();
+        app.selected_day = 9;
+        app.states.insert(
+            (2024, 9),
+            DayState::Finished {
+                solver: "test".to_string(),
+                result: Ok(DaySummary {
+                    parsing: Duration::from_millis(1),
+                    part1: PartSummary {
+                        taken: Duration::from_millis(2),
+                        answer: Ok("#..#\n.##.".to_string()),
+                        alternatives: Vec::new(),
+                    },
+                    part2: PartSummary {
+                        taken: Duration::from_millis(3),
+                        answer: Err("oops".to_string()),
+                        alternatives: Vec::new(),
+                    },
+                }),
+            },
+        );
+        // there are no verified answers to award the stars for
+        assert_eq!(Status::Unverified, app.status(2024, 9));
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let rendered = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(120)
+            .map(|line| line.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>();
+
+        assert!(rendered.iter().any(|line| line.contains("│#..#")));
+        assert!(rendered.iter().any(|line| line.contains("│.##.")));
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("failed to solve: oops"))
+        );
+    }
+
+    #[test]
+    fn catching_solver_panics() {
+        let solution = Solution {
+            year: 2024,
+            day: 9,
+            input: "inputs/2024/day09",
+            name: "panicking",
+            run: |_| panic!("unexpected end of the input"),
+            generator: None,
+            #[cfg(feature = "profile")]
+            profile: |_, _, _| anyhow::bail!("not supported"),
+        };
+        let receiver = spawn_solver(solution, "2333".to_string(), None).unwrap();
+        let Err(err) = receiver.recv().unwrap() else {
+            panic!("the panic has not been reported")
+        };
+        assert_eq!(
+            "the solution has panicked: unexpected end of the input",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use aoc_common::answers::{AnswerWarning, AnswersStore, Part};
+use aoc_solution::summary::DaySummary;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect { expected: String, actual: String },
+    Failed(String),
+    Unknown,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&str>, actual: &Result<String, String>) -> Self {
+        match (expected, actual) {
+            (_, Err(err)) => Verdict::Failed(err.clone()),
+            (None, Ok(_)) => Verdict::Unknown,
+            (Some(expected), Ok(actual)) if expected == actual.trim() => Verdict::Correct,
+            (Some(expected), Ok(actual)) => Verdict::Incorrect {
+                expected: expected.to_string(),
+                actual: actual.clone(),
+            },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Incorrect { .. } | Verdict::Failed(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "✓"),
+            Verdict::Incorrect { expected, actual } => {
+                write!(f, "✗ (expected '{expected}', got '{actual}')")
+            }
+            Verdict::Failed(err) => write!(f, "✗ (failed to solve: {err})"),
+            Verdict::Unknown => write!(f, "? (no recorded answer)"),
+        }
+    }
+}
+
+/// Checks both parts of the summary against the answers recorded for the profile.
+pub fn verify_day(
+    store: &AnswersStore,
+    profile: &str,
+    year: u16,
+    day: u8,
+    summary: &DaySummary,
+) -> [Verdict; 2] {
+    [
+        Verdict::check(
+            store.correct_answer(profile, year, day, Part::One),
+            &summary.part1.answer,
+        ),
+        Verdict::check(
+            store.correct_answer(profile, year, day, Part::Two),
+            &summary.part2.answer,
+        ),
+    ]
+}
+
+/// Checks the computed answers against the ones known to be wrong, returning the warnings for each part.
+pub fn known_wrong_answers(
+    store: &AnswersStore,
+    profile: &str,
+    year: u16,
+    day: u8,
+    summary: &DaySummary,
+) -> Vec<(Part, String, AnswerWarning)> {
+    [
+        (Part::One, &summary.part1.answer),
+        (Part::Two, &summary.part2.answer),
+    ]
+    .into_iter()
+    .filter_map(|(part, answer)| {
+        let answer = answer.as_ref().ok()?.trim();
+        store
+            .check_answer(profile, year, day, part, answer)
+            .map(|warning| (part, answer.to_string(), warning))
+    })
+    .collect()
+}
+
+/// Records the computed answers as the correct ones, apart from those known to be wrong, which get returned instead.
+pub fn record_answers(
+    store: &mut AnswersStore,
+    profile: &str,
+    year: u16,
+    day: u8,
+    summary: &DaySummary,
+) -> Vec<(Part, String, AnswerWarning)> {
+    let flagged = known_wrong_answers(store, profile, year, day, summary);
+    let parts = [
+        (Part::One, &summary.part1.answer),
+        (Part::Two, &summary.part2.answer),
+    ];
+    for (part, answer) in parts {
+        if let Ok(answer) = answer
+            && !flagged.iter().any(|(flagged, ..)| *flagged == part)
+        {
+            store.record_correct(profile, year, day, part, answer.trim());
+        }
+    }
+    flagged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checking_answers() {
+        assert_eq!(
+            Verdict::Correct,
+            Verdict::check(Some("42"), &Ok("42".into()))
+        );
+        assert_eq!(
+            Verdict::Incorrect {
+                expected: "42".into(),
+                actual: "41".into()
+            },
+            Verdict::check(Some("42"), &Ok("41".into()))
+        );
+        assert_eq!(Verdict::Unknown, Verdict::check(None, &Ok("42".into())));
+        assert_eq!(
+            Verdict::Failed("oops".into()),
+            Verdict::check(Some("42"), &Err("oops".into()))
+        );
+    }
+
+    #[test]
+    fn warning_about_known_wrong_answers() {
+        use aoc_common::answers::Feedback;
+        use aoc_solution::summary::PartSummary;
+        use std::time::Duration;
+
+        let mut store = AnswersStore::default();
+        store
+            .record_feedback("p", 2024, 5, Part::One, "100", Feedback::TooLow)
+            .unwrap();
+        store
+            .record_feedback("p", 2024, 5, Part::Two, "foo", Feedback::Wrong)
+            .unwrap();
+
+        let part = |answer: &str| PartSummary {
+            taken: Duration::ZERO,
+            answer: Ok(answer.to_string()),
+            alternatives: Vec::new(),
+        };
+        let summary = DaySummary {
+            parsing: Duration::ZERO,
+            part1: part("99"),
+            part2: part("bar"),
+        };
+        assert_eq!(
+            vec![(
+                Part::One,
+                "99".to_string(),
+                AnswerWarning::TooLow { bound: 100 }
+            )],
+            known_wrong_answers(&store, "p", 2024, 5, &summary)
+        );
+        assert!(known_wrong_answers(&store, "other", 2024, 5, &summary).is_empty());
+    }
+
+    #[test]
+    fn refusing_to_record_wrong_answers() {
+        use aoc_common::answers::Feedback;
+        use aoc_solution::summary::PartSummary;
+        use std::time::Duration;
+
+        let mut store = AnswersStore::default();
+        store
+            .record_feedback("p", 2024, 5, Part::Two, "42", Feedback::Wrong)
+            .unwrap();
+
+        let part = |answer: &str| PartSummary {
+            taken: Duration::ZERO,
+            answer: Ok(answer.to_string()),
+            alternatives: Vec::new(),
+        };
+        let summary = DaySummary {
+            parsing: Duration::ZERO,
+            part1: part("143\n"),
+            part2: part("42"),
+        };
+
+        let refused = record_answers(&mut store, "p", 2024, 5, &summary);
+        assert_eq!(
+            vec![(Part::Two, "42".to_string(), AnswerWarning::KnownWrong)],
+            refused
+        );
+        assert_eq!(Some("143"), store.correct_answer("p", 2024, 5, Part::One));
+        assert_eq!(None, store.correct_answer("p", 2024, 5, Part::Two));
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::solutions::Solution;
+use anyhow::Context;
+use aoc_solution::plugin::panic_message;
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::fs;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[H";
+
+/// Files whose changes trigger a rerun of the solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedFiles {
+    pub input: PathBuf,
+    pub examples: PathBuf,
+}
+
+// editors often replace the files instead of modifying them in place, so rather than the files,
+// their parent directories are watched. this in turn requires normalising the paths so that they
+// could be compared with the ones reported by the notifications
+fn normalise(path: &Path) -> PathBuf {
+    if let Ok(canonical) = fs::canonicalize(path) {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => fs::canonicalize(parent)
+            .map(|parent| parent.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl WatchedFiles {
+    pub fn new(input: &Path, examples: &Path) -> Self {
+        WatchedFiles {
+            input: normalise(input),
+            examples: normalise(examples),
+        }
+    }
+
+    /// The expected answers of the examples are not inputs themselves, so they are never run.
+    pub fn is_watched(&self, path: &Path) -> bool {
+        let is_answer = path
+            .extension()
+            .is_some_and(|extension| extension == "answer");
+        path == self.input
+            || (path.starts_with(&self.examples) && path != self.examples && !is_answer)
+    }
+}
+
+/// Checks whether the event changes the content of the file. In particular, this excludes accessing it,
+/// which would otherwise make every rerun trigger another one.
+pub fn changes_content(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(_)
+            | EventKind::Remove(_)
+            | EventKind::Modify(ModifyKind::Any | ModifyKind::Data(_) | ModifyKind::Name(_))
+    )
+}
+
+/// The examples directory might only get created while the solution is being watched, e.g. by `aoc-init`,
+/// so until it exists, its closest existing ancestor is watched instead, to pick it up once it appears.
+struct ExamplesWatch {
+    directory: PathBuf,
+    ancestor: Option<PathBuf>,
+    watching_directory: bool,
+}
+
+impl ExamplesWatch {
+    fn new(directory: &Path) -> Self {
+        ExamplesWatch {
+            directory: directory.to_path_buf(),
+            ancestor: None,
+            watching_directory: false,
+        }
+    }
+
+    /// Starts watching the examples directory if it has just appeared, returning the examples it already contains,
+    /// as they might have been written before the watch has been set up.
+    fn update<W: Watcher>(&mut self, watcher: &mut W) -> anyhow::Result<Vec<PathBuf>> {
+        if self.watching_directory {
+            return Ok(Vec::new());
+        }
+
+        // the directories might keep getting created while the watches are being swapped,
+        // so the lookup gets repeated until the closest existing ancestor no longer changes
+        loop {
+            if self.directory.is_dir() {
+                if let Some(ancestor) = self.ancestor.take() {
+                    let _ = watcher.unwatch(&ancestor);
+                }
+                watcher
+                    .watch(&self.directory, RecursiveMode::Recursive)
+                    .with_context(|| format!("failed to watch {}", self.directory.display()))?;
+                self.watching_directory = true;
+
+                let examples = fs::read_dir(&self.directory)?
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.is_file())
+                    .collect();
+                return Ok(examples);
+            }
+
+            let ancestor = self
+                .directory
+                .ancestors()
+                .skip(1)
+                .find(|ancestor| ancestor.is_dir())
+                .map(Path::to_path_buf);
+            if ancestor == self.ancestor {
+                return Ok(Vec::new());
+            }
+            if let Some(previous) = self.ancestor.take() {
+                let _ = watcher.unwatch(&previous);
+            }
+            if let Some(ancestor) = &ancestor {
+                watcher
+                    .watch(ancestor, RecursiveMode::NonRecursive)
+                    .with_context(|| format!("failed to watch {}", ancestor.display()))?;
+            }
+            self.ancestor = ancestor;
+        }
+    }
+}
+
+/// Waits for the next change and then keeps collecting any further ones until no new changes
+/// arrive within the debounce period. Returns `None` if the sender got dropped.
+pub fn next_changes(receiver: &Receiver<PathBuf>, debounce: Duration) -> Option<BTreeSet<PathBuf>> {
+    let mut changes = BTreeSet::from([receiver.recv().ok()?]);
+    loop {
+        match receiver.recv_timeout(debounce) {
+            Ok(path) => {
+                changes.insert(path);
+            }
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => {
+                return Some(changes);
+            }
+        }
+    }
+}
+
+fn rerun(solution: &Solution, path: &Path, plugin_dir: Option<&Path>) -> anyhow::Result<()> {
+    println!(
+        "### {} DAY {:02} [{}] ###",
+        solution.year,
+        solution.day,
+        path.display()
+    );
+    if !path.is_file() {
+        println!("the file does not exist");
+        return Ok(());
+    }
+
+    let input =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    // the solution is likely to panic on a half-written input, which must not stop the watch
+    let solved = catch_unwind(AssertUnwindSafe(|| {
+        crate::solve(solution, &input, plugin_dir)
+    }));
+    let (name, result) = match solved {
+        Ok(solved) => solved?,
+        Err(payload) => {
+            eprintln!(
+                "the solution has panicked: {}",
+                panic_message(payload.as_ref())
+            );
+            return Ok(());
+        }
+    };
+    match result {
+        Ok(summary) => summary.report(&name),
+        Err(err) => eprintln!("failed to solve aoc for '{name}': {err}"),
+    }
+    Ok(())
+}
+
+/// Reruns the solution whenever its input or any of its examples change, until interrupted.
+pub fn watch(
+    solution: &Solution,
+    files: &WatchedFiles,
+    plugin_dir: Option<&Path>,
+    debounce: Duration,
+) -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event
+            && changes_content(&event.kind)
+        {
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+        }
+    })?;
+
+    let input_directory = files
+        .input
+        .parent()
+        .context("the input file does not have a parent directory")?;
+    watcher
+        .watch(input_directory, RecursiveMode::NonRecursive)
+        .with_context(|| format!("failed to watch {}", input_directory.display()))?;
+    let mut examples = ExamplesWatch::new(&files.examples);
+    examples.update(&mut watcher)?;
+
+    print!("{CLEAR_SCREEN}");
+    rerun(solution, &files.input, plugin_dir)?;
+    println!("watching for changes, press ctrl-c to stop");
+
+    while let Some(changes) = next_changes(&receiver, debounce) {
+        let mut changed = changes
+            .into_iter()
+            .filter(|path| files.is_watched(path))
+            .collect::<BTreeSet<_>>();
+        // the change might have been the creation of the examples directory, alongside the examples themselves
+        changed.extend(
+            examples
+                .update(&mut watcher)?
+                .into_iter()
+                .filter(|path| files.is_watched(path)),
+        );
+        if changed.is_empty() {
+            continue;
+        }
+
+        print!("{CLEAR_SCREEN}");
+        for path in changed {
+            if let Err(err) = rerun(solution, &path, plugin_dir) {
+                eprintln!("{err:#}")
+            }
+        }
+        println!("watching for changes, press ctrl-c to stop");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn filtering_watched_files() {
+        let files = WatchedFiles {
+            input: PathBuf::from("/aoc/inputs/2024/day09"),
+            examples: PathBuf::from("/aoc/examples/2024/day09"),
+        };
+        assert!(files.is_watched(Path::new("/aoc/inputs/2024/day09")));
+        assert!(files.is_watched(Path::new("/aoc/examples/2024/day09/part1")));
+        assert!(!files.is_watched(Path::new("/aoc/examples/2024/day09")));
+        assert!(!files.is_watched(Path::new("/aoc/inputs/2024/day10")));
+        assert!(!files.is_watched(Path::new("/aoc/inputs/2024/day09.swp")));
+        assert!(!files.is_watched(Path::new("/aoc/examples/2024/day09/part1.answer")));
+    }
+
+    #[test]
+    fn surviving_panicking_solution() {
+        let solution = Solution {
+            year: 2024,
+            day: 9,
+            input: "inputs/2024/day09",
+            name: "panicking",
+            run: |_| panic!("unexpected end of the input"),
+            generator: None,
+            #[cfg(feature = "profile")]
+            profile: |_, _, _| anyhow::bail!("not supported"),
+        };
+        let path = std::env::temp_dir().join(format!("aoc-watch-panic-{}", std::process::id()));
+        fs::write(&path, "2333").unwrap();
+
+        assert!(rerun(&solution, &path, None).is_ok());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn ignoring_file_accesses() {
+        use notify::event::{AccessKind, CreateKind, DataChange, MetadataKind};
+
+        assert!(changes_content(&EventKind::Create(CreateKind::File)));
+        assert!(changes_content(&EventKind::Modify(ModifyKind::Data(
+            DataChange::Content
+        ))));
+        assert!(!changes_content(&EventKind::Access(AccessKind::Read)));
+        assert!(!changes_content(&EventKind::Modify(ModifyKind::Metadata(
+            MetadataKind::AccessTime
+        ))));
+    }
+
+    #[test]
+    fn picking_up_created_examples_directory() {
+        let root = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let directory = root.join("examples/2024/day09");
+        fs::create_dir_all(&root).unwrap();
+
+        let mut watcher = notify::recommended_watcher(|_| {}).unwrap();
+        let mut examples = ExamplesWatch::new(&directory);
+        assert!(examples.update(&mut watcher).unwrap().is_empty());
+        assert_eq!(Some(&root), examples.ancestor.as_ref());
+
+        fs::create_dir_all(root.join("examples")).unwrap();
+        assert!(examples.update(&mut watcher).unwrap().is_empty());
+        assert_eq!(Some(root.join("examples")), examples.ancestor);
+
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("part1"), "2333133121414131402").unwrap();
+        assert_eq!(
+            vec![directory.join("part1")],
+            examples.update(&mut watcher).unwrap()
+        );
+        assert!(examples.watching_directory);
+        assert!(examples.ancestor.is_none());
+
+        // the examples get reported through the notifications from now on
+        fs::write(directory.join("part2"), "2333133121414131402").unwrap();
+        assert!(examples.update(&mut watcher).unwrap().is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn debouncing_changes() {
+        let (sender, receiver) = mpsc::channel();
+        sender.send(PathBuf::from("a")).unwrap();
+        sender.send(PathBuf::from("b")).unwrap();
+        sender.send(PathBuf::from("a")).unwrap();
+
+        let changes = next_changes(&receiver, Duration::from_millis(10)).unwrap();
+        assert_eq!(
+            BTreeSet::from([PathBuf::from("a"), PathBuf::from("b")]),
+            changes
+        );
+
+        let delayed = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            sender.send(PathBuf::from("c")).unwrap();
+        });
+        let changes = next_changes(&receiver, Duration::from_millis(10)).unwrap();
+        assert_eq!(BTreeSet::from([PathBuf::from("c")]), changes);
+
+        delayed.join().unwrap();
+        assert!(next_changes(&receiver, Duration::from_millis(10)).is_none());
+    }
+}
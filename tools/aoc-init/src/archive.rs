@@ -0,0 +1,240 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Offline copies of the puzzle descriptions, converted from the HTML of the site into Markdown.
+
+use anyhow::{Context, anyhow};
+use scraper::{ElementRef, Html, Node, Selector};
+use std::fs;
+use std::path::Path;
+
+/// Result of archiving the description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Archived {
+    /// The description has been written, with the specified number of parts
+    Written { parts: usize },
+    /// The existing description has more parts than the fetched one, e.g. it was fetched without a session
+    KeptExisting,
+    /// The page does not contain any description
+    Missing,
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn inline(element: ElementRef) -> String {
+    let mut markdown = String::new();
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => markdown.push_str(&escape(text)),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    markdown.push_str(&inline_element(child))
+                }
+            }
+            _ => {}
+        }
+    }
+    markdown
+}
+
+fn inline_element(element: ElementRef) -> String {
+    match element.value().name() {
+        "code" => {
+            let code = element.text().collect::<String>();
+            let emphasised = element.children().any(|child| {
+                ElementRef::wrap(child).is_some_and(|child| child.value().name() == "em")
+            });
+            if emphasised {
+                format!("**`{code}`**")
+            } else {
+                format!("`{code}`")
+            }
+        }
+        "em" => {
+            let content = inline(element);
+            // emphasised fragments of the code are already handled by the code itself
+            if content.starts_with('`') {
+                format!("**{content}**")
+            } else {
+                format!("*{content}*")
+            }
+        }
+        "a" => match element.value().attr("href") {
+            Some(href) => format!("[{}]({href})", inline(element)),
+            None => inline(element),
+        },
+        "br" => "\n".to_string(),
+        _ => inline(element),
+    }
+}
+
+fn block(element: ElementRef, markdown: &mut Vec<String>) {
+    match element.value().name() {
+        "h2" => markdown.push(format!("## {}", element.text().collect::<String>())),
+        "pre" => {
+            let code = element.text().collect::<String>();
+            markdown.push(format!("```\n{}\n```", code.trim_end_matches('\n')))
+        }
+        "ul" | "ol" => {
+            let items = element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|child| child.value().name() == "li")
+                .map(|item| format!("- {}", inline(item).trim()))
+                .collect::<Vec<_>>();
+            markdown.push(items.join("\n"))
+        }
+        _ => {
+            let paragraph = inline(element);
+            if !paragraph.trim().is_empty() {
+                markdown.push(paragraph.trim().to_string())
+            }
+        }
+    }
+}
+
+/// Converts the descriptions of all the unlocked parts into Markdown, along with the number of the parts.
+pub fn to_markdown(page: &str) -> anyhow::Result<Option<(String, usize)>> {
+    let document = Html::parse_document(page);
+    let articles = Selector::parse("article.day-desc").map_err(|err| anyhow!("{err}"))?;
+
+    let mut parts = 0;
+    let mut markdown = Vec::new();
+    for article in document.select(&articles) {
+        parts += 1;
+        for child in article.children().filter_map(ElementRef::wrap) {
+            block(child, &mut markdown)
+        }
+    }
+
+    if parts == 0 {
+        return Ok(None);
+    }
+    Ok(Some((markdown.join("\n\n") + "\n", parts)))
+}
+
+// every part starts with its own heading. the code blocks have to be skipped, as grids of the examples
+// may very well contain lines looking exactly like the headings
+fn archived_parts(markdown: &str) -> usize {
+    let mut in_code_block = false;
+    let mut parts = 0;
+    for line in markdown.lines() {
+        if line == "```" {
+            in_code_block = !in_code_block;
+        } else if !in_code_block && line.starts_with("## ") {
+            parts += 1;
+        }
+    }
+    parts
+}
+
+/// Saves the description, unless the existing one already has more parts.
+pub fn archive(path: &Path, page: &str) -> anyhow::Result<Archived> {
+    let Some((markdown, parts)) = to_markdown(page)? else {
+        return Ok(Archived::Missing);
+    };
+
+    if let Ok(existing) = fs::read_to_string(path)
+        && archived_parts(&existing) > parts
+    {
+        return Ok(Archived::KeptExisting);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, markdown).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(Archived::Written { parts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = include_str!("../fixtures/puzzle.html");
+
+    #[test]
+    fn converting_descriptions() {
+        let (markdown, parts) = to_markdown(PUZZLE).unwrap().unwrap();
+        assert_eq!(2, parts);
+
+        let expected = r#"## --- Day 5: Sorting Sleighs ---
+
+The elves keep a list of *sleigh pairs* that must never be swapped, followed by the [stacks](/2024/day/4) they want to load:
+
+```
+3|7
+7|1
+
+3,7,1
+1,3
+```
+
+A stack is *valid* if every pair is in order. The first stack above is valid, the second one is not.
+
+- Stacks use `,` as the separator.
+- Rules use `|` & nothing else.
+
+For example, intermediate states look like this:
+
+```
+3 < 7 < 1
+```
+
+Summing the middle elements of all the valid stacks produces **`7`**.
+
+*What is the sum of the middle elements of the valid stacks?*
+
+## --- Part Two ---
+
+Now fix the invalid stacks by sorting them, e.g. `1,3` becomes `3,1`. In the example, this produces **`123`**.
+
+*What is the sum of the middle elements of the fixed stacks?*
+"#;
+        assert_eq!(expected, markdown);
+        assert_eq!(None, to_markdown("<html></html>").unwrap());
+        assert_eq!(2, archived_parts(&markdown));
+        assert_eq!(1, archived_parts("## Day 1\n\n```\n## ##\n```\n"));
+    }
+
+    #[test]
+    fn keeping_more_complete_descriptions() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc-init-{}-archive", std::process::id()))
+            .join("day05.md");
+        let locked = PUZZLE.replacen("<article class=\"day-desc\"><h2 id=\"part2\">", "<div>", 1);
+
+        assert_eq!(
+            Archived::Written { parts: 1 },
+            archive(&path, &locked).unwrap()
+        );
+        assert_eq!(
+            Archived::Written { parts: 2 },
+            archive(&path, PUZZLE).unwrap()
+        );
+        assert_eq!(Archived::KeptExisting, archive(&path, &locked).unwrap());
+        assert!(fs::read_to_string(&path).unwrap().contains("Part Two"));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,410 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Context;
+use aoc_common::answers::{Feedback, Part};
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use reqwest::header::{COOKIE, HeaderMap, USER_AGENT};
+use reqwest::redirect::Policy;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+const USER_AGENT_VALUE: &str =
+    "https://github.com/jstuczyn/AdventOfCode by jedrzej.stuczynski@gmail.com";
+
+/// Base URL of the site, either the explicitly provided one, the one from `AOC_BASE_URL` or the real one.
+pub fn base_url(explicit: Option<String>) -> String {
+    explicit
+        .or_else(|| env::var(BASE_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub fn session_cookie() -> anyhow::Result<String> {
+    env::var(SESSION_ENV)
+        .map(|session| session.trim().to_string())
+        .with_context(|| format!("could not find a valid {SESSION_ENV} cookie"))
+}
+
+/// Response of the site to the submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part has either already been solved, or it's not unlocked yet
+    WrongLevel,
+    Unrecognised,
+}
+
+impl SubmissionOutcome {
+    /// Parses the page returned after submitting the answer.
+    pub fn parse(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                SubmissionOutcome::TooHigh
+            } else if page.contains("your answer is too low") {
+                SubmissionOutcome::TooLow
+            } else {
+                SubmissionOutcome::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            SubmissionOutcome::RateLimited {
+                wait: parse_wait_time(page),
+            }
+        } else if page.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unrecognised
+        }
+    }
+
+    /// The feedback worth remembering, if any.
+    pub fn feedback(&self) -> Option<Feedback> {
+        match self {
+            SubmissionOutcome::Correct => Some(Feedback::Correct),
+            SubmissionOutcome::Wrong => Some(Feedback::Wrong),
+            SubmissionOutcome::TooHigh => Some(Feedback::TooHigh),
+            SubmissionOutcome::TooLow => Some(Feedback::TooLow),
+            SubmissionOutcome::RateLimited { .. }
+            | SubmissionOutcome::WrongLevel
+            | SubmissionOutcome::Unrecognised => None,
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "that's the right answer!"),
+            SubmissionOutcome::Wrong => write!(f, "that's not the right answer"),
+            SubmissionOutcome::TooHigh => write!(f, "that's not the right answer, it's too high"),
+            SubmissionOutcome::TooLow => write!(f, "that's not the right answer, it's too low"),
+            SubmissionOutcome::RateLimited { wait: Some(wait) } => write!(
+                f,
+                "an answer was submitted too recently, {}s left to wait",
+                wait.as_secs()
+            ),
+            SubmissionOutcome::RateLimited { wait: None } => {
+                write!(f, "an answer was submitted too recently")
+            }
+            SubmissionOutcome::WrongLevel => {
+                write!(f, "the part is either already solved or still locked")
+            }
+            SubmissionOutcome::Unrecognised => write!(f, "the response was not recognised"),
+        }
+    }
+}
+
+// the site reports it as "You have 1m 23s left to wait." or "You have 45s left to wait."
+fn parse_wait_time(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for component in page[start..end].split_whitespace() {
+        let (value, unit) = component.split_at(component.len().checked_sub(1)?);
+        let value: u64 = value.parse().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Retry policy of the requests that may fail transiently, with exponential backoff between the attempts.
+#[derive(Debug, Clone, Copy)]
+pub struct Retry {
+    attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Retry::new(4, Duration::from_secs(1))
+    }
+}
+
+impl Retry {
+    pub fn new(attempts: u32, initial_backoff: Duration) -> Self {
+        Retry {
+            attempts: attempts.max(1),
+            initial_backoff,
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff * 2u32.saturating_pow(attempt)
+    }
+}
+
+// client errors, such as an invalid session or a locked puzzle, won't go away by themselves
+fn is_transient(err: &reqwest::Error) -> bool {
+    match err.status() {
+        Some(status) => status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+        None => err.is_connect() || err.is_timeout() || err.is_request() || err.is_body(),
+    }
+}
+
+/// Minimal client of the Advent of Code website.
+pub struct AocClient {
+    base_url: String,
+    client: Client,
+    retry: Retry,
+}
+
+impl AocClient {
+    /// Creates the client, which is only able to access the public pages without the session.
+    pub fn new(base_url: String, session: Option<&str>) -> anyhow::Result<Self> {
+        let mut headers = HeaderMap::new();
+        if let Some(session) = session {
+            headers.insert(COOKIE, format!("session={session}").parse()?);
+        }
+        headers.insert(USER_AGENT, USER_AGENT_VALUE.parse()?);
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .redirect(Policy::none())
+            .build()?;
+
+        Ok(AocClient {
+            base_url,
+            client,
+            retry: Retry::default(),
+        })
+    }
+
+    #[must_use]
+    pub fn with_retry(mut self, retry: Retry) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn get(&self, url: &str) -> anyhow::Result<Vec<u8>> {
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
+                .get(url)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.bytes());
+
+            match result {
+                Ok(content) => return Ok(content.to_vec()),
+                Err(err) if attempt + 1 < self.retry.attempts && is_transient(&err) => {
+                    let backoff = self.retry.backoff(attempt);
+                    eprintln!("failed to get {url} ({err}), retrying in {backoff:?}");
+                    thread::sleep(backoff);
+                    attempt += 1;
+                }
+                Err(err) => return Err(err).with_context(|| format!("failed to get {url}")),
+            }
+        }
+    }
+
+    pub fn fetch_input(&self, year: u16, day: u8) -> anyhow::Result<Vec<u8>> {
+        self.get(&format!("{}/{year}/day/{day}/input", self.base_url))
+    }
+
+    /// Gets the page with the description of the puzzle, including the second part if it's unlocked.
+    pub fn fetch_puzzle(&self, year: u16, day: u8) -> anyhow::Result<String> {
+        let page = self.get(&format!("{}/{year}/day/{day}", self.base_url))?;
+        String::from_utf8(page).context("the puzzle page is not valid UTF-8")
+    }
+
+    /// Gets the JSON of the private leaderboard. It's only available to its members.
+    pub fn fetch_leaderboard(&self, year: u16, id: u64) -> anyhow::Result<String> {
+        let json = self.get(&format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            self.base_url
+        ))?;
+        String::from_utf8(json).context("the leaderboard is not valid UTF-8")
+    }
+
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> anyhow::Result<SubmissionOutcome> {
+        let page = self
+            .client
+            .post(format!("{}/{year}/day/{day}/answer", self.base_url))
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())?;
+        Ok(SubmissionOutcome::parse(&page))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use tiny_http::{Response, Server};
+
+    #[derive(Debug)]
+    pub(crate) struct ReceivedRequest {
+        pub(crate) method: String,
+        pub(crate) url: String,
+        pub(crate) body: String,
+        pub(crate) cookie: Option<String>,
+    }
+
+    /// Starts a local server replying to the consecutive requests with the provided responses.
+    /// Returns its base URL and the receiver of all the requests it got.
+    pub(crate) fn stub_server(
+        responses: Vec<(u16, String)>,
+    ) -> (String, mpsc::Receiver<ReceivedRequest>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, content) in responses {
+                let mut request = server.recv().unwrap();
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                sender
+                    .send(ReceivedRequest {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        body,
+                        cookie,
+                    })
+                    .unwrap();
+                request
+                    .respond(Response::from_string(content).with_status_code(status))
+                    .unwrap();
+            }
+        });
+
+        (format!("http://127.0.0.1:{port}"), receiver)
+    }
+
+    fn page(main: &str) -> String {
+        format!("<html><body><main><article><p>{main}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn parsing_submission_responses() {
+        assert_eq!(
+            SubmissionOutcome::Correct,
+            SubmissionOutcome::parse(&page(
+                "That's the right answer!  You are one gold star closer to saving Christmas."
+            ))
+        );
+        assert_eq!(
+            SubmissionOutcome::TooHigh,
+            SubmissionOutcome::parse(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data"
+            ))
+        );
+        assert_eq!(
+            SubmissionOutcome::TooLow,
+            SubmissionOutcome::parse(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+        );
+        assert_eq!(
+            SubmissionOutcome::Wrong,
+            SubmissionOutcome::parse(&page(
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data"
+            ))
+        );
+        assert_eq!(
+            SubmissionOutcome::RateLimited {
+                wait: Some(Duration::from_secs(83))
+            },
+            SubmissionOutcome::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait."
+            ))
+        );
+        assert_eq!(
+            SubmissionOutcome::RateLimited {
+                wait: Some(Duration::from_secs(45))
+            },
+            SubmissionOutcome::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 45s left to wait."
+            ))
+        );
+        assert_eq!(
+            SubmissionOutcome::WrongLevel,
+            SubmissionOutcome::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+        );
+        assert_eq!(
+            SubmissionOutcome::Unrecognised,
+            SubmissionOutcome::parse("")
+        );
+    }
+
+    #[test]
+    fn submitting_answers() {
+        let (base_url, requests) = stub_server(vec![(
+            200,
+            page("That's not the right answer; your answer is too low."),
+        )]);
+        let client = AocClient::new(base_url, Some("secret")).unwrap();
+
+        let outcome = client.submit(2024, 5, Part::Two, "1234").unwrap();
+        assert_eq!(SubmissionOutcome::TooLow, outcome);
+
+        let request = requests.recv().unwrap();
+        assert_eq!("POST", request.method);
+        assert_eq!("/2024/day/5/answer", request.url);
+        assert_eq!("level=2&answer=1234", request.body);
+        assert_eq!(Some("session=secret"), request.cookie.as_deref());
+    }
+
+    #[test]
+    fn fetching_leaderboards() {
+        let json = crate::leaderboard::tests::sample_json();
+        let (base_url, requests) = stub_server(vec![(200, json.clone())]);
+        let client = AocClient::new(base_url, Some("secret")).unwrap();
+
+        assert_eq!(json, client.fetch_leaderboard(2023, 123456).unwrap());
+
+        let request = requests.recv().unwrap();
+        assert_eq!("GET", request.method);
+        assert_eq!("/2023/leaderboard/private/view/123456.json", request.url);
+        assert_eq!(Some("session=secret"), request.cookie.as_deref());
+    }
+}
@@ -0,0 +1,600 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Consistency checks of the workspace, i.e. whether every day crate is a member of the workspace,
+//! a dependency of the solution runner and has its solution registered with the right year, day, input and type.
+
+use crate::workspace::{self, DayCrate};
+use aoc_common::answers::DEFAULT_PROFILE;
+use aoc_common::config::Config;
+use cargo_edit::LocalManifest;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Inconsistency found in the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The crate exists, but it's not a member of the workspace
+    NotAMember(DayCrate),
+    /// The member of the workspace does not exist
+    UnknownMember(String),
+    /// The member is listed after a later day
+    MemberOutOfOrder { member: String, after: String },
+    /// The crate is not a dependency of the solution runner
+    MissingDependency(DayCrate),
+    /// The dependency of the solution runner points at a different location
+    MismatchedDependencyPath { day: DayCrate, path: String },
+    /// The dependency is not gated behind the feature of its year
+    MissingFromFeature(DayCrate),
+    /// The solution runner depends on a crate that does not exist
+    UnknownDependency(DayCrate),
+    /// The solution of the crate is not registered in the solution runner
+    NotRegistered(DayCrate),
+    /// The registration of the solution does not match the crate
+    MismatchedRegistration { day: DayCrate, found: String },
+    /// The solution is registered more than once
+    DuplicateRegistration(DayCrate),
+    /// The solution is registered after the one of a later day
+    RegistrationOutOfOrder { day: DayCrate, after: DayCrate },
+    /// The registered solution is not a dependency of the solution runner
+    UnknownRegistration { found: String },
+    /// The input of the default profile is missing or empty
+    MissingInput { day: DayCrate, path: PathBuf },
+    /// The crate has no benchmarks
+    MissingBenchmarks(DayCrate),
+}
+
+impl Problem {
+    /// Warnings do not make the workspace inconsistent, they just point at something that's likely missing.
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            Problem::MissingInput { .. } | Problem::MissingBenchmarks(_)
+        )
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            Problem::NotAMember(_)
+                | Problem::UnknownMember(_)
+                | Problem::MemberOutOfOrder { .. }
+                | Problem::MissingDependency(_)
+                | Problem::MissingFromFeature(_)
+                | Problem::UnknownDependency(_)
+                | Problem::NotRegistered(_)
+                | Problem::MismatchedRegistration { .. }
+                | Problem::RegistrationOutOfOrder { .. }
+        )
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::NotAMember(day) => {
+                write!(f, "{} is not a member of the workspace", day.member_path())
+            }
+            Problem::UnknownMember(member) => {
+                write!(f, "the workspace member {member} does not exist")
+            }
+            Problem::MemberOutOfOrder { member, after } => {
+                write!(f, "the workspace member {member} is listed after {after}")
+            }
+            Problem::MissingDependency(day) => write!(
+                f,
+                "{} is not a dependency of the solution runner",
+                day.package_name()
+            ),
+            Problem::MismatchedDependencyPath { day, path } => write!(
+                f,
+                "the solution runner depends on {} from '{path}', rather than '../{}'",
+                day.package_name(),
+                day.member_path()
+            ),
+            Problem::MissingFromFeature(day) => write!(
+                f,
+                "{} is missing from the {} feature of the solution runner",
+                day.package_name(),
+                day.year_feature()
+            ),
+            Problem::UnknownDependency(day) => write!(
+                f,
+                "the solution runner depends on {}, which does not exist",
+                day.package_name()
+            ),
+            Problem::NotRegistered(day) => write!(
+                f,
+                "the solution of {} is not registered in the solution runner",
+                day.package_name()
+            ),
+            Problem::MismatchedRegistration { day, found } => write!(
+                f,
+                "the registration of {} does not match the crate: `{found}`, expected `{}`",
+                day.package_name(),
+                day.solution_definition().trim()
+            ),
+            Problem::DuplicateRegistration(day) => write!(
+                f,
+                "the solution of {} is registered more than once",
+                day.package_name()
+            ),
+            Problem::RegistrationOutOfOrder { day, after } => write!(
+                f,
+                "the solution of {} is registered after the one of {}",
+                day.package_name(),
+                after.package_name()
+            ),
+            Problem::UnknownRegistration { found } => write!(
+                f,
+                "the registered solution is not a dependency of the solution runner: `{found}`"
+            ),
+            Problem::MissingInput { day, path } => write!(
+                f,
+                "the input of {} is missing, expected it in {}",
+                day.member_path(),
+                path.display()
+            ),
+            Problem::MissingBenchmarks(day) => {
+                write!(f, "{} has no benches/benchmarks.rs", day.member_path())
+            }
+        }
+    }
+}
+
+/// Solution registered in the solution runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Registration {
+    /// Line preceding the registration, i.e. its feature gate
+    gate: Option<String>,
+    line: String,
+    /// Crate of the registered type
+    day: Option<DayCrate>,
+}
+
+fn registrations(solutions: &str) -> Vec<Registration> {
+    let mut registrations = Vec::new();
+    let mut previous: Option<&str> = None;
+    for line in solutions.lines() {
+        if line.trim().starts_with("define_solution!(") {
+            registrations.push(Registration {
+                gate: previous
+                    .filter(|previous| previous.trim_start().starts_with("#[cfg("))
+                    .map(String::from),
+                line: line.to_string(),
+                day: DayCrate::from_registration(line),
+            })
+        }
+        previous = Some(line);
+    }
+    registrations
+}
+
+/// Finds the crates of all the days, i.e. `<year>/dayXX` directories containing a manifest.
+fn day_crates(root: &Path) -> anyhow::Result<BTreeSet<DayCrate>> {
+    let mut crates = BTreeSet::new();
+    for year in fs::read_dir(root)? {
+        let year = year?;
+        let Some(year_name) = year.file_name().to_str().map(String::from) else {
+            continue;
+        };
+        if year_name.len() != 4 || year_name.parse::<u16>().is_err() || !year.path().is_dir() {
+            continue;
+        }
+        for day in fs::read_dir(year.path())? {
+            let day = day?;
+            let member = format!("{year_name}/{}", day.file_name().to_string_lossy());
+            if let Some(day_crate) = DayCrate::from_member_path(&member)
+                && day.path().join("Cargo.toml").is_file()
+            {
+                crates.insert(day_crate);
+            }
+        }
+    }
+    Ok(crates)
+}
+
+fn check_members(
+    root: &Path,
+    crates: &BTreeSet<DayCrate>,
+    problems: &mut Vec<Problem>,
+) -> anyhow::Result<()> {
+    let members = workspace::workspace_member_paths(root)?;
+
+    for day in crates {
+        if !members.contains(&day.member_path()) {
+            problems.push(Problem::NotAMember(*day))
+        }
+    }
+
+    let mut latest: Option<(DayCrate, &String)> = None;
+    for member in &members {
+        if !root.join(member).join("Cargo.toml").is_file() {
+            problems.push(Problem::UnknownMember(member.clone()));
+            continue;
+        }
+        let Some(day) = DayCrate::from_member_path(member) else {
+            continue;
+        };
+        match latest {
+            Some((latest_day, after)) if day < latest_day => {
+                problems.push(Problem::MemberOutOfOrder {
+                    member: member.clone(),
+                    after: after.clone(),
+                })
+            }
+            _ => latest = Some((day, member)),
+        }
+    }
+    Ok(())
+}
+
+fn check_runner_dependencies(
+    root: &Path,
+    crates: &BTreeSet<DayCrate>,
+    problems: &mut Vec<Problem>,
+) -> anyhow::Result<BTreeSet<DayCrate>> {
+    let manifest = LocalManifest::try_new(&workspace::runner_manifest_path(root))?;
+    let data = &manifest.manifest.data;
+
+    let mut dependencies = BTreeSet::new();
+    if let Some(table) = data
+        .get("dependencies")
+        .and_then(|deps| deps.as_table_like())
+    {
+        for (name, dependency) in table.iter() {
+            let Some(day) = DayCrate::from_package_name(name) else {
+                continue;
+            };
+            dependencies.insert(day);
+            if !crates.contains(&day) {
+                problems.push(Problem::UnknownDependency(day));
+                continue;
+            }
+
+            let path = dependency
+                .as_table_like()
+                .and_then(|dependency| dependency.get("path"))
+                .and_then(|path| path.as_str())
+                .unwrap_or_default();
+            if path != format!("../{}", day.member_path()) {
+                problems.push(Problem::MismatchedDependencyPath {
+                    day,
+                    path: path.to_string(),
+                })
+            }
+        }
+    }
+
+    let features = data
+        .get("features")
+        .and_then(|features| features.as_table_like());
+    for day in crates {
+        if !dependencies.contains(day) {
+            problems.push(Problem::MissingDependency(*day));
+            continue;
+        }
+        let dependency = format!("dep:{}", day.package_name());
+        let gated = features
+            .and_then(|features| features.get(&day.year_feature()))
+            .and_then(|feature| feature.as_array())
+            .is_some_and(|feature| {
+                feature
+                    .iter()
+                    .any(|entry| entry.as_str() == Some(&dependency))
+            });
+        if !gated {
+            problems.push(Problem::MissingFromFeature(*day))
+        }
+    }
+    Ok(dependencies)
+}
+
+fn check_registrations(
+    root: &Path,
+    crates: &BTreeSet<DayCrate>,
+    dependencies: &BTreeSet<DayCrate>,
+    problems: &mut Vec<Problem>,
+) -> anyhow::Result<()> {
+    let solutions = fs::read_to_string(workspace::runner_solutions_path(root))?;
+
+    let mut registered: BTreeMap<DayCrate, usize> = BTreeMap::new();
+    let mut latest: Option<DayCrate> = None;
+    for registration in registrations(&solutions) {
+        let Some(day) = registration.day.filter(|day| dependencies.contains(day)) else {
+            problems.push(Problem::UnknownRegistration {
+                found: registration.line.trim().to_string(),
+            });
+            continue;
+        };
+
+        match latest {
+            Some(after) if day < after => {
+                problems.push(Problem::RegistrationOutOfOrder { day, after })
+            }
+            _ => latest = Some(day),
+        }
+        *registered.entry(day).or_default() += 1;
+        let matches = registration.line == day.solution_definition()
+            && registration.gate.as_deref() == Some(&*day.solution_feature_gate());
+        if !matches {
+            problems.push(Problem::MismatchedRegistration {
+                day,
+                found: registration.line.trim().to_string(),
+            })
+        }
+    }
+
+    for day in crates.intersection(dependencies) {
+        match registered.get(day) {
+            None => problems.push(Problem::NotRegistered(*day)),
+            Some(count) if *count > 1 => problems.push(Problem::DuplicateRegistration(*day)),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_files(
+    root: &Path,
+    crates: &BTreeSet<DayCrate>,
+    problems: &mut Vec<Problem>,
+) -> anyhow::Result<()> {
+    let config = Config::load_from(root)?;
+    for day in crates {
+        let path = config.input_path(DEFAULT_PROFILE, day.year, day.day);
+        let has_content = fs::metadata(&path)
+            .map(|metadata| metadata.len() > 0)
+            .unwrap_or_default();
+        if !has_content {
+            problems.push(Problem::MissingInput { day: *day, path })
+        }
+
+        let benchmarks = root.join(day.member_path()).join("benches/benchmarks.rs");
+        if !benchmarks.is_file() {
+            problems.push(Problem::MissingBenchmarks(*day))
+        }
+    }
+    Ok(())
+}
+
+/// Cross-checks the members of the workspace, the dependencies and registrations of the solution runner,
+/// and the inputs and benchmarks of all the day crates.
+pub fn diagnose(root: &Path) -> anyhow::Result<Vec<Problem>> {
+    let crates = day_crates(root)?;
+    let mut problems = Vec::new();
+
+    check_members(root, &crates, &mut problems)?;
+    let dependencies = check_runner_dependencies(root, &crates, &mut problems)?;
+    check_registrations(root, &crates, &dependencies, &mut problems)?;
+    check_files(root, &crates, &mut problems)?;
+
+    Ok(problems)
+}
+
+/// Fixes all the fixable problems, returning the number of them that have been attempted.
+pub fn fix(root: &Path, problems: &[Problem]) -> anyhow::Result<usize> {
+    let mut attempted = 0;
+    let mut sort_members = false;
+    let mut sort_registrations = false;
+    for problem in problems.iter().filter(|problem| problem.is_fixable()) {
+        attempted += 1;
+        match problem {
+            Problem::NotAMember(day) => {
+                workspace::add_to_workspace(root, *day)?;
+                sort_members = true;
+            }
+            Problem::UnknownMember(member) => {
+                workspace::remove_workspace_member(root, member)?;
+            }
+            Problem::MemberOutOfOrder { .. } => sort_members = true,
+            Problem::MissingDependency(day)
+            | Problem::MissingFromFeature(day)
+            | Problem::NotRegistered(day) => {
+                workspace::add_to_solution_runner(root, *day)?;
+                sort_registrations = true;
+            }
+            Problem::UnknownDependency(day) => {
+                workspace::remove_from_solution_runner(root, *day)?;
+            }
+            Problem::MismatchedRegistration { day, .. } => {
+                workspace::fix_registration(root, *day)?;
+            }
+            Problem::RegistrationOutOfOrder { .. } => sort_registrations = true,
+            _ => {}
+        }
+    }
+
+    // new members and registrations get appended at the very end, so they have to be moved into place
+    if sort_members {
+        workspace::sort_workspace_members(root)?;
+    }
+    if sort_registrations {
+        workspace::sort_registrations(root)?;
+    }
+    Ok(attempted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::tests::TestWorkspace;
+
+    const DAY_2019: DayCrate = DayCrate { year: 2019, day: 1 };
+    const DAY_2024: DayCrate = DayCrate { year: 2024, day: 1 };
+
+    fn create_crate(root: &Path, day: DayCrate, benchmarks: bool) {
+        let directory = root.join(day.member_path());
+        fs::create_dir_all(directory.join("benches")).unwrap();
+        fs::write(directory.join("Cargo.toml"), "[package]").unwrap();
+        if benchmarks {
+            fs::write(directory.join("benches/benchmarks.rs"), "").unwrap();
+        }
+    }
+
+    fn replace(path: PathBuf, from: &str, to: &str) {
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(from));
+        fs::write(path, content.replacen(from, to, 1)).unwrap();
+    }
+
+    #[test]
+    fn parsing_registrations() {
+        let solutions = r#"    #[cfg(feature = "year2019")]
+    define_solution!(solutions, 2020, 1, "inputs/2019/day01", day01_2019::Day01);
+    define_solution!(solutions, 2024, 1, "inputs/2024/day01", plugin::Solver);"#;
+        let registrations = registrations(solutions);
+        assert_eq!(2, registrations.len());
+        assert_eq!(Some(DAY_2019), registrations[0].day);
+        assert_eq!(
+            Some("    #[cfg(feature = \"year2019\")]"),
+            registrations[0].gate.as_deref()
+        );
+        assert_eq!(None, registrations[1].day);
+        assert_eq!(None, registrations[1].gate);
+    }
+
+    #[test]
+    fn diagnosing_and_fixing_problems() {
+        let workspace = TestWorkspace::new("doctor");
+        let root = &workspace.0;
+        create_crate(root, DAY_2019, false);
+        create_crate(root, DAY_2024, true);
+        let day_2024_2 = DayCrate { year: 2024, day: 2 };
+        create_crate(root, day_2024_2, true);
+        workspace::add_to_workspace(root, day_2024_2).unwrap();
+        workspace::add_to_solution_runner(root, day_2024_2).unwrap();
+        fs::create_dir_all(root.join("inputs/2024")).unwrap();
+        fs::write(root.join("inputs/2024/day01"), "1").unwrap();
+        fs::write(root.join("inputs/2024/day02"), "2").unwrap();
+
+        // the same kind of mistakes that have crept into the real workspace
+        replace(
+            root.join("Cargo.toml"),
+            "\"2024/day01\",\n    \"2024/day02\"",
+            "\"2024/day02\",\n    \"2024/day01\"",
+        );
+        replace(
+            workspace::runner_solutions_path(root),
+            "solutions, 2019, 1,",
+            "solutions, 2020, 1,",
+        );
+
+        let problems = diagnose(root).unwrap();
+        assert_eq!(
+            vec![
+                Problem::NotAMember(DAY_2019),
+                Problem::MemberOutOfOrder {
+                    member: "2024/day01".to_string(),
+                    after: "2024/day02".to_string(),
+                },
+                Problem::MismatchedRegistration {
+                    day: DAY_2019,
+                    found: "define_solution!(solutions, 2020, 1, \"inputs/2019/day01\", day01_2019::Day01);"
+                        .to_string(),
+                },
+                Problem::MissingInput {
+                    day: DAY_2019,
+                    path: root.join("inputs/2019/day01"),
+                },
+                Problem::MissingBenchmarks(DAY_2019),
+            ],
+            problems
+        );
+
+        assert_eq!(3, fix(root, &problems).unwrap());
+        let problems = diagnose(root).unwrap();
+        assert!(problems.iter().all(Problem::is_warning));
+        assert_eq!(2, problems.len());
+
+        let contents = workspace.contents();
+        assert!(contents[0].contains(
+            "    \"solution-runner\",\n    \"2019/day01\",\n    \"2024/day01\",\n    \"2024/day02\"\n]"
+        ));
+        assert!(contents[2].contains("solutions, 2019, 1,"));
+    }
+
+    #[test]
+    fn reordering_registrations() {
+        let workspace = TestWorkspace::new("doctor-order");
+        let root = &workspace.0;
+        let day_2024_2 = DayCrate { year: 2024, day: 2 };
+        create_crate(root, DAY_2024, true);
+        create_crate(root, day_2024_2, true);
+        workspace::add_to_workspace(root, day_2024_2).unwrap();
+        workspace::add_to_solution_runner(root, day_2024_2).unwrap();
+
+        let registration = |day: DayCrate| {
+            format!(
+                "{}\n{}\n",
+                day.solution_feature_gate(),
+                day.solution_definition()
+            )
+        };
+        let ordered = format!("{}{}", registration(DAY_2024), registration(day_2024_2));
+        replace(
+            workspace::runner_solutions_path(root),
+            &ordered,
+            &format!("{}{}", registration(day_2024_2), registration(DAY_2024)),
+        );
+
+        let out_of_order = Problem::RegistrationOutOfOrder {
+            day: DAY_2024,
+            after: day_2024_2,
+        };
+        let problems = diagnose(root).unwrap();
+        assert!(problems.contains(&out_of_order), "{problems:?}");
+
+        fix(root, &problems).unwrap();
+        assert!(!diagnose(root).unwrap().contains(&out_of_order));
+        assert!(workspace.contents()[2].contains(&ordered));
+        assert!(!workspace::sort_registrations(root).unwrap());
+    }
+
+    #[test]
+    fn detecting_stale_registrations() {
+        let workspace = TestWorkspace::new("doctor-stale");
+        let root = &workspace.0;
+        create_crate(root, DAY_2024, true);
+        replace(
+            workspace::runner_solutions_path(root),
+            "day01_2024::Day01",
+            "day01_2024::Day1",
+        );
+        fs::write(
+            root.join("Cargo.toml"),
+            fs::read_to_string(root.join("Cargo.toml"))
+                .unwrap()
+                .replace("\"2024/day01\"", "\"2024/day01\",\n    \"2024/day03\""),
+        )
+        .unwrap();
+
+        let problems = diagnose(root).unwrap();
+        assert!(problems.contains(&Problem::UnknownMember("2024/day03".to_string())));
+        // the legacy day has been removed, but not unregistered
+        assert!(problems.contains(&Problem::UnknownDependency(DAY_2019)));
+        assert!(
+            problems
+                .iter()
+                .any(|problem| matches!(problem, Problem::MismatchedRegistration { day, .. } if *day == DAY_2024))
+        );
+
+        fix(root, &problems).unwrap();
+        let problems = diagnose(root).unwrap();
+        assert!(problems.iter().all(Problem::is_warning), "{problems:?}");
+        assert!(!workspace.contents()[1].contains("day01_2019"));
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::client::AocClient;
+use anyhow::Context;
+use aoc_common::profiles::day_path;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_CACHE_DIRECTORY: &str = ".aoc/cache";
+
+// puzzles unlock at midnight US Eastern time, i.e. UTC-5
+const RELEASE_HOUR_UTC: u64 = 5;
+
+// days since the unix epoch of the provided date, based on http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The moment the puzzle, and thus its input, becomes available.
+pub fn release_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year.into(), 12, day.into()).max(0) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + RELEASE_HOUR_UTC * 3600)
+}
+
+/// Time left until the release of the puzzle, if it's not out yet.
+pub fn until_release(year: u16, day: u8, now: SystemTime) -> Option<Duration> {
+    release_time(year, day)
+        .duration_since(now)
+        .ok()
+        .filter(|left| !left.is_zero())
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}h {:02}m {:02}s",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+// 64-bit FNV-1a, unlike `DefaultHasher` it's guaranteed to stay the same between the Rust releases,
+// so the cached inputs don't get orphaned after a toolchain update
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Local copy of the downloaded inputs, kept separately for every session.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    directory: PathBuf,
+}
+
+impl InputCache {
+    pub fn new<P: AsRef<Path>>(base: P, session: &str) -> Self {
+        // the session itself is a secret, so it's not used as the directory name directly
+        InputCache {
+            directory: base
+                .as_ref()
+                .join(format!("{:016x}", fnv1a(session.as_bytes()))),
+        }
+    }
+
+    fn path(&self, year: u16, day: u8) -> PathBuf {
+        day_path(&self.directory, year, day)
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<Vec<u8>> {
+        fs::read(self.path(year, day))
+            .ok()
+            .filter(|input| !input.is_empty())
+    }
+
+    pub fn put(&self, year: u16, day: u8, input: &[u8]) -> anyhow::Result<()> {
+        let path = self.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, input).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Result of the attempt to obtain the input of the day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Download {
+    Downloaded,
+    Cached,
+    /// The input file already had some content, which was left untouched
+    AlreadyPresent,
+    NotReleased {
+        left: Duration,
+    },
+}
+
+/// Writes the input of the day to the provided file, either from the cache or from the site.
+/// Existing non-empty files are never overwritten and nothing gets created unless the input is available.
+pub fn download_input(
+    client: &AocClient,
+    cache: &InputCache,
+    destination: &Path,
+    year: u16,
+    day: u8,
+    now: SystemTime,
+) -> anyhow::Result<Download> {
+    let has_content = fs::metadata(destination)
+        .map(|metadata| metadata.len() > 0)
+        .unwrap_or_default();
+    if has_content {
+        return Ok(Download::AlreadyPresent);
+    }
+
+    let (input, download) = match cache.get(year, day) {
+        Some(cached) => (cached, Download::Cached),
+        None => {
+            if let Some(left) = until_release(year, day, now) {
+                return Ok(Download::NotReleased { left });
+            }
+            let input = client.fetch_input(year, day)?;
+            cache.put(year, day, &input)?;
+            (input, Download::Downloaded)
+        }
+    };
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(destination, input)
+        .with_context(|| format!("failed to write {}", destination.display()))?;
+    Ok(download)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Retry;
+    use crate::client::tests::stub_server;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("aoc-init-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn puzzle_release_times() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(1733029200),
+            release_time(2024, 1)
+        );
+        // 2015-12-25T05:00:00Z
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(1451019600),
+            release_time(2015, 25)
+        );
+
+        let release = release_time(2024, 1);
+        assert_eq!(
+            Some(Duration::from_secs(90)),
+            until_release(2024, 1, release - Duration::from_secs(90))
+        );
+        assert_eq!(None, until_release(2024, 1, release));
+        assert_eq!("1h 01m 05s", format_duration(Duration::from_secs(3665)));
+    }
+
+    #[test]
+    fn downloading_inputs() {
+        let dir = TempDir::new("downloading");
+        let (base_url, requests) =
+            stub_server(vec![(500, "oops".into()), (200, "1\n2\n3\n".into())]);
+        let client = AocClient::new(base_url, Some("secret"))
+            .unwrap()
+            .with_retry(Retry::new(3, Duration::from_millis(1)));
+        let cache = InputCache::new(dir.0.join("cache"), "secret");
+        let destination = dir.0.join("inputs/2024/day01");
+        let now = release_time(2024, 1);
+
+        // not released yet, so nothing is requested or created
+        let before = now - Duration::from_secs(10);
+        assert_eq!(
+            Download::NotReleased {
+                left: Duration::from_secs(10)
+            },
+            download_input(&client, &cache, &destination, 2024, 1, before).unwrap()
+        );
+        assert!(!destination.exists());
+
+        // the first attempt fails and gets retried
+        assert_eq!(
+            Download::Downloaded,
+            download_input(&client, &cache, &destination, 2024, 1, now).unwrap()
+        );
+        assert_eq!("1\n2\n3\n", fs::read_to_string(&destination).unwrap());
+        assert_eq!(2, requests.try_iter().count());
+        // the cache location has to stay the same across the toolchain updates
+        assert!(dir.0.join("cache/ab23f0eec020c951/2024/day01").is_file());
+
+        // existing inputs are never overwritten
+        fs::write(&destination, "mine").unwrap();
+        assert_eq!(
+            Download::AlreadyPresent,
+            download_input(&client, &cache, &destination, 2024, 1, now).unwrap()
+        );
+        assert_eq!("mine", fs::read_to_string(&destination).unwrap());
+
+        // while empty ones are restored from the cache, without contacting the site
+        fs::write(&destination, "").unwrap();
+        assert_eq!(
+            Download::Cached,
+            download_input(&client, &cache, &destination, 2024, 1, now).unwrap()
+        );
+        assert_eq!("1\n2\n3\n", fs::read_to_string(&destination).unwrap());
+        assert_eq!(0, requests.try_iter().count());
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let dir = TempDir::new("client-errors");
+        let (base_url, requests) = stub_server(vec![(400, "bad session".into())]);
+        let client = AocClient::new(base_url, Some("secret"))
+            .unwrap()
+            .with_retry(Retry::new(3, Duration::from_millis(1)));
+        let cache = InputCache::new(dir.0.join("cache"), "secret");
+        let destination = dir.0.join("inputs/2024/day01");
+
+        let now = release_time(2024, 1);
+        assert!(download_input(&client, &cache, &destination, 2024, 1, now).is_err());
+        assert!(!destination.exists());
+        assert_eq!(1, requests.try_iter().count());
+    }
+}
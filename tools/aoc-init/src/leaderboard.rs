@@ -0,0 +1,702 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statistics of the private leaderboards, i.e. the star timelines of all the members,
+//! the time it took them to get from the first to the second part and their local score rankings.
+
+use crate::client::AocClient;
+use crate::input::{format_duration, release_time};
+use anyhow::{Context, bail};
+use aoc_common::answers::Part;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_CACHE_DIRECTORY: &str = ".aoc/leaderboards";
+
+/// The site asks for the private leaderboards not to be requested more often than that.
+pub const CACHE_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// Timestamp, in seconds since the unix epoch, at which the part was completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct CompletedPart {
+    pub get_star_ts: u64,
+
+    /// Global order in which the stars were obtained, used to break the ties of the timestamps
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+
+    /// Anonymous users have no name
+    pub name: Option<String>,
+    pub stars: usize,
+    pub local_score: u64,
+    pub last_star_ts: u64,
+
+    /// Completed parts of each day
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, CompletedPart>>,
+}
+
+impl Member {
+    /// Name of the member, the same way the site shows it.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn completed(&self, day: u8, part: u8) -> Option<CompletedPart> {
+        self.completion_day_level
+            .get(&day)
+            .and_then(|parts| parts.get(&part))
+            .copied()
+    }
+}
+
+/// The JSON served by `/<year>/leaderboard/private/view/<id>.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+
+    /// Unlock time of the first day, only present in the more recent events
+    #[serde(default)]
+    pub day1_ts: Option<u64>,
+    pub members: BTreeMap<String, Member>,
+
+    #[serde(skip)]
+    year: u16,
+}
+
+/// Star obtained by a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Star {
+    pub day: u8,
+    pub part: Part,
+    pub obtained: SystemTime,
+    pub since_unlock: Duration,
+}
+
+/// Position of a member in the local score rankings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub score: u64,
+    pub stars: usize,
+    pub last_star: Option<SystemTime>,
+}
+
+/// Member whose score reported by the site differs from the one computed from the completion times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreMismatch {
+    pub name: String,
+    pub reported: u64,
+    pub computed: u64,
+}
+
+/// Time it took a member to solve the second part after solving the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartDelta {
+    pub name: String,
+    pub delta: Duration,
+}
+
+fn timestamp(seconds: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+fn format_timestamp(time: SystemTime) -> String {
+    humantime::format_rfc3339_seconds(time).to_string()
+}
+
+impl Leaderboard {
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let mut leaderboard: Leaderboard =
+            serde_json::from_str(json).context("the leaderboard is not in the expected format")?;
+        leaderboard.year = leaderboard
+            .event
+            .parse()
+            .with_context(|| format!("'{}' is not a valid event year", leaderboard.event))?;
+        Ok(leaderboard)
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// The moment the puzzle of the day became available.
+    pub fn unlock_time(&self, day: u8) -> SystemTime {
+        match self.day1_ts {
+            Some(day1) => timestamp(day1 + u64::from(day.saturating_sub(1)) * 86400),
+            None => release_time(self.year, day),
+        }
+    }
+
+    fn members(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|member| member.id);
+        members
+    }
+
+    /// Local scores computed from the completion times: on every part, the first member to solve it
+    /// gets as many points as there are members on the leaderboard, the second one gets one point less, and so on.
+    pub fn local_scores(&self) -> BTreeMap<u64, u64> {
+        let members = self.members();
+        let mut scores: BTreeMap<_, _> = members.iter().map(|member| (member.id, 0)).collect();
+
+        let puzzles = members
+            .iter()
+            .flat_map(|member| member.completion_day_level.iter())
+            .flat_map(|(day, parts)| parts.keys().map(|part| (*day, *part)))
+            .collect::<BTreeSet<_>>();
+        for (day, part) in puzzles {
+            let mut solvers: Vec<_> = members
+                .iter()
+                .filter_map(|member| member.completed(day, part).map(|star| (star, member.id)))
+                .collect();
+            solvers.sort_by_key(|(star, id)| (star.get_star_ts, star.star_index, *id));
+            for (rank, (_, id)) in solvers.into_iter().enumerate() {
+                *scores.entry(id).or_default() += (members.len() - rank) as u64;
+            }
+        }
+        scores
+    }
+
+    /// Members whose scores reported by the site can't be reproduced from their completion times,
+    /// e.g. because some of the puzzles didn't award any points.
+    pub fn score_mismatches(&self) -> Vec<ScoreMismatch> {
+        let scores = self.local_scores();
+        self.members()
+            .into_iter()
+            .filter_map(|member| {
+                let computed = scores.get(&member.id).copied().unwrap_or_default();
+                (computed != member.local_score).then(|| ScoreMismatch {
+                    name: member.display_name(),
+                    reported: member.local_score,
+                    computed,
+                })
+            })
+            .collect()
+    }
+
+    /// Members ordered by their local score as reported by the site, then by the number of stars
+    /// and finally by who got the last one first.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<_> = self
+            .members()
+            .into_iter()
+            .map(|member| {
+                let stars = self.timeline(member);
+                Standing {
+                    name: member.display_name(),
+                    score: member.local_score,
+                    stars: stars.len(),
+                    last_star: stars.last().map(|star| star.obtained),
+                }
+            })
+            .collect();
+        standings.sort_by_key(|standing| {
+            (
+                Reverse(standing.score),
+                Reverse(standing.stars),
+                standing.last_star,
+            )
+        });
+        standings
+    }
+
+    /// All the stars of the member, in the order they were obtained.
+    pub fn timeline(&self, member: &Member) -> Vec<Star> {
+        let mut stars: Vec<_> = member
+            .completion_day_level
+            .iter()
+            .flat_map(|(day, parts)| parts.iter().map(move |(part, star)| (*day, *part, *star)))
+            .filter_map(|(day, part, star)| {
+                let part = Part::try_from(part).ok()?;
+                let obtained = timestamp(star.get_star_ts);
+                Some(Star {
+                    day,
+                    part,
+                    obtained,
+                    since_unlock: obtained
+                        .duration_since(self.unlock_time(day))
+                        .unwrap_or_default(),
+                })
+            })
+            .collect();
+        stars.sort_by_key(|star| (star.obtained, star.day, star.part));
+        stars
+    }
+
+    /// For every day, the members who solved both parts, ordered from the quickest to solve the second one.
+    pub fn part_deltas(&self) -> BTreeMap<u8, Vec<PartDelta>> {
+        let mut deltas: BTreeMap<u8, Vec<PartDelta>> = BTreeMap::new();
+        for member in self.members() {
+            for day in member.completion_day_level.keys() {
+                if let (Some(first), Some(second)) =
+                    (member.completed(*day, 1), member.completed(*day, 2))
+                {
+                    deltas.entry(*day).or_default().push(PartDelta {
+                        name: member.display_name(),
+                        delta: Duration::from_secs(
+                            second.get_star_ts.saturating_sub(first.get_star_ts),
+                        ),
+                    })
+                }
+            }
+        }
+        for day in deltas.values_mut() {
+            day.sort_by(|a, b| a.delta.cmp(&b.delta).then_with(|| a.name.cmp(&b.name)));
+        }
+        deltas
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedLeaderboard {
+    /// Seconds since the unix epoch at which the leaderboard was downloaded
+    fetched: u64,
+    json: String,
+}
+
+/// Local copies of the downloaded leaderboards, so that the site isn't requested more often than it allows.
+#[derive(Debug, Clone)]
+pub struct LeaderboardCache {
+    directory: PathBuf,
+}
+
+impl LeaderboardCache {
+    pub fn new<P: AsRef<Path>>(directory: P) -> Self {
+        LeaderboardCache {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, year: u16, id: u64) -> PathBuf {
+        self.directory
+            .join(year.to_string())
+            .join(format!("{id}.json"))
+    }
+
+    /// The leaderboard downloaded less than 15 minutes ago, along with its age.
+    pub fn get(&self, year: u16, id: u64, now: SystemTime) -> Option<(String, Duration)> {
+        let content = fs::read_to_string(self.path(year, id)).ok()?;
+        let cached: CachedLeaderboard = serde_json::from_str(&content).ok()?;
+        let age = now.duration_since(timestamp(cached.fetched)).ok()?;
+        (age < CACHE_LIFETIME).then_some((cached.json, age))
+    }
+
+    pub fn put(&self, year: u16, id: u64, json: &str, now: SystemTime) -> anyhow::Result<()> {
+        let path = self.path(year, id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let cached = CachedLeaderboard {
+            fetched: now.duration_since(UNIX_EPOCH)?.as_secs(),
+            json: json.to_string(),
+        };
+        fs::write(&path, serde_json::to_string(&cached)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Result of the attempt to obtain the leaderboard JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(String),
+    Cached { json: String, age: Duration },
+}
+
+/// Gets the leaderboard JSON from the cache, only downloading it if there's no recent enough copy.
+pub fn fetch_leaderboard(
+    client: &AocClient,
+    cache: &LeaderboardCache,
+    year: u16,
+    id: u64,
+    now: SystemTime,
+) -> anyhow::Result<Fetched> {
+    if let Some((json, age)) = cache.get(year, id, now) {
+        return Ok(Fetched::Cached { json, age });
+    }
+    let json = client.fetch_leaderboard(year, id)?;
+    cache.put(year, id, &json, now)?;
+    Ok(Fetched::Downloaded(json))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LeaderboardFormat {
+    #[default]
+    Terminal,
+    Markdown,
+}
+
+impl FromStr for LeaderboardFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(LeaderboardFormat::Terminal),
+            "markdown" => Ok(LeaderboardFormat::Markdown),
+            other => bail!(
+                "'{other}' is not a valid leaderboard format, expected 'terminal' or 'markdown'"
+            ),
+        }
+    }
+}
+
+impl Display for LeaderboardFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaderboardFormat::Terminal => write!(f, "terminal"),
+            LeaderboardFormat::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+pub fn render(leaderboard: &Leaderboard, format: LeaderboardFormat) -> String {
+    match format {
+        LeaderboardFormat::Terminal => render_terminal(leaderboard),
+        LeaderboardFormat::Markdown => render_markdown(leaderboard),
+    }
+}
+
+/// Renders plain text with aligned columns.
+pub fn render_terminal(leaderboard: &Leaderboard) -> String {
+    let mut out = String::new();
+    let standings = leaderboard.standings();
+    let name_width = standings
+        .iter()
+        .map(|standing| standing.name.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max("member".len());
+
+    let _ = writeln!(
+        out,
+        "private leaderboard of {} ({} members)",
+        leaderboard.year(),
+        standings.len()
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "  # {:<name_width$}  score  stars  last star",
+        "member"
+    );
+    for (position, standing) in standings.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "{:>3} {:<name_width$}  {:>5}  {:>5}  {}",
+            position + 1,
+            standing.name,
+            standing.score,
+            standing.stars,
+            standing.last_star.map(format_timestamp).unwrap_or_default(),
+        );
+    }
+
+    for member in leaderboard.members() {
+        let stars = leaderboard.timeline(member);
+        if stars.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(out, "stars of {}:", member.display_name());
+        for star in stars {
+            let _ = writeln!(
+                out,
+                "  day {:>2} part {}  {}  +{}",
+                star.day,
+                star.part,
+                format_timestamp(star.obtained),
+                format_duration(star.since_unlock),
+            );
+        }
+    }
+
+    let deltas = leaderboard.part_deltas();
+    if !deltas.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "part 1 -> part 2:");
+        for (day, members) in deltas {
+            let _ = writeln!(out, "  day {day:>2}");
+            for member in members {
+                let _ = writeln!(
+                    out,
+                    "    {:<name_width$}  {}",
+                    member.name,
+                    format_duration(member.delta)
+                );
+            }
+        }
+    }
+    out
+}
+
+fn escape_markdown(name: &str) -> String {
+    name.replace('|', "\\|")
+}
+
+/// Renders Markdown tables, so that the results could be discussed alongside the solutions.
+pub fn render_markdown(leaderboard: &Leaderboard) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "## Private leaderboard {}", leaderboard.year());
+    let _ = writeln!(out);
+    let _ = writeln!(out, "### Rankings");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| # | Member | Score | Stars | Last star |");
+    let _ = writeln!(out, "|--:|--------|------:|------:|-----------|");
+    for (position, standing) in leaderboard.standings().iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            position + 1,
+            escape_markdown(&standing.name),
+            standing.score,
+            standing.stars,
+            standing
+                .last_star
+                .map(format_timestamp)
+                .unwrap_or_else(|| "-".to_string()),
+        );
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "### Star timelines");
+    for member in leaderboard.members() {
+        let stars = leaderboard.timeline(member);
+        if stars.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(out, "#### {}", escape_markdown(&member.display_name()));
+        let _ = writeln!(out);
+        let _ = writeln!(out, "| Day | Part | Obtained | Since unlock |");
+        let _ = writeln!(out, "|----:|-----:|----------|-------------:|");
+        for star in stars {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                star.day,
+                star.part,
+                format_timestamp(star.obtained),
+                format_duration(star.since_unlock),
+            );
+        }
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "### Part 1 → part 2");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Day | Member | Delta |");
+    let _ = writeln!(out, "|----:|--------|------:|");
+    for (day, members) in leaderboard.part_deltas() {
+        for member in members {
+            let _ = writeln!(
+                out,
+                "| {day} | {} | {} |",
+                escape_markdown(&member.name),
+                format_duration(member.delta)
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::client::tests::stub_server;
+
+    // 2023-12-01T05:00:00Z
+    const DAY1: u64 = 1701406800;
+    const DAY2: u64 = DAY1 + 86400;
+
+    pub(crate) fn sample_json() -> String {
+        format!(
+            r#"{{
+  "event": "2023",
+  "owner_id": 1,
+  "day1_ts": {DAY1},
+  "members": {{
+    "1": {{
+      "id": 1, "name": "alice", "stars": 4, "local_score": 11, "last_star_ts": {a4},
+      "completion_day_level": {{
+        "1": {{ "1": {{ "get_star_ts": {a1}, "star_index": 20 }}, "2": {{ "get_star_ts": {a2}, "star_index": 30 }} }},
+        "2": {{ "1": {{ "get_star_ts": {a3}, "star_index": 60 }}, "2": {{ "get_star_ts": {a4}, "star_index": 80 }} }}
+      }}
+    }},
+    "2": {{
+      "id": 2, "name": "bob", "stars": 3, "local_score": 7, "last_star_ts": {b3},
+      "completion_day_level": {{
+        "1": {{ "1": {{ "get_star_ts": {b1}, "star_index": 10 }}, "2": {{ "get_star_ts": {b2}, "star_index": 40 }} }},
+        "2": {{ "1": {{ "get_star_ts": {b3}, "star_index": 70 }} }}
+      }}
+    }},
+    "3": {{
+      "id": 3, "name": null, "stars": 1, "local_score": 1, "last_star_ts": {c1},
+      "completion_day_level": {{
+        "1": {{ "1": {{ "get_star_ts": {c1}, "star_index": 50 }} }}
+      }}
+    }}
+  }}
+}}"#,
+            a1 = DAY1 + 600,
+            a2 = DAY1 + 900,
+            a3 = DAY2 + 1200,
+            a4 = DAY2 + 3000,
+            b1 = DAY1 + 300,
+            b2 = DAY1 + 2100,
+            b3 = DAY2 + 1800,
+            c1 = DAY1 + 7200,
+        )
+    }
+
+    #[test]
+    fn computing_statistics() {
+        let leaderboard = Leaderboard::from_json(&sample_json()).unwrap();
+        assert_eq!(2023, leaderboard.year());
+
+        // the computed scores match the ones reported by the site
+        let scores = leaderboard.local_scores();
+        for member in leaderboard.members.values() {
+            assert_eq!(member.local_score, scores[&member.id]);
+        }
+
+        let standings = leaderboard.standings();
+        let names: Vec<_> = standings.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(vec!["alice", "bob", "(anonymous user #3)"], names);
+        assert_eq!(Some(timestamp(DAY2 + 3000)), standings[0].last_star);
+
+        let bob = &leaderboard.members["2"];
+        let timeline = leaderboard.timeline(bob);
+        assert_eq!(3, timeline.len());
+        assert_eq!(
+            Star {
+                day: 2,
+                part: Part::One,
+                obtained: timestamp(DAY2 + 1800),
+                since_unlock: Duration::from_secs(1800),
+            },
+            timeline[2]
+        );
+
+        let deltas = leaderboard.part_deltas();
+        assert_eq!(
+            vec![
+                PartDelta {
+                    name: "alice".to_string(),
+                    delta: Duration::from_secs(300)
+                },
+                PartDelta {
+                    name: "bob".to_string(),
+                    delta: Duration::from_secs(1800)
+                }
+            ],
+            deltas[&1]
+        );
+        assert_eq!(1, deltas[&2].len());
+    }
+
+    #[test]
+    fn ranking_by_reported_scores() {
+        let mut leaderboard = Leaderboard::from_json(&sample_json()).unwrap();
+        assert!(leaderboard.score_mismatches().is_empty());
+
+        // e.g. the puzzles that didn't award any points
+        if let Some(member) = leaderboard.members.get_mut("3") {
+            member.local_score = 20;
+        }
+        let names: Vec<_> = leaderboard
+            .standings()
+            .into_iter()
+            .map(|standing| standing.name)
+            .collect();
+        assert_eq!(vec!["(anonymous user #3)", "alice", "bob"], names);
+        assert_eq!(
+            vec![ScoreMismatch {
+                name: "(anonymous user #3)".to_string(),
+                reported: 20,
+                computed: 1,
+            }],
+            leaderboard.score_mismatches()
+        );
+    }
+
+    #[test]
+    fn caching_leaderboards() {
+        let directory =
+            std::env::temp_dir().join(format!("aoc-init-leaderboards-{}", std::process::id()));
+        let cache = LeaderboardCache::new(&directory);
+        let (base_url, requests) = stub_server(vec![(200, "first".into()), (200, "second".into())]);
+        let client = AocClient::new(base_url, Some("secret")).unwrap();
+        let now = timestamp(DAY1);
+
+        assert_eq!(
+            Fetched::Downloaded("first".to_string()),
+            fetch_leaderboard(&client, &cache, 2023, 1, now).unwrap()
+        );
+        let later = now + Duration::from_secs(600);
+        assert_eq!(
+            Fetched::Cached {
+                json: "first".to_string(),
+                age: Duration::from_secs(600)
+            },
+            fetch_leaderboard(&client, &cache, 2023, 1, later).unwrap()
+        );
+        assert_eq!(1, requests.try_iter().count());
+
+        // the copy is only reused for 15 minutes
+        let expired = now + CACHE_LIFETIME;
+        assert_eq!(
+            Fetched::Downloaded("second".to_string()),
+            fetch_leaderboard(&client, &cache, 2023, 1, expired).unwrap()
+        );
+        assert_eq!(1, requests.try_iter().count());
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn unlock_times_without_day1_timestamp() {
+        let mut leaderboard = Leaderboard::from_json(&sample_json()).unwrap();
+        leaderboard.day1_ts = None;
+        assert_eq!(timestamp(DAY2), leaderboard.unlock_time(2));
+
+        assert!(
+            Leaderboard::from_json(r#"{"event": "next", "owner_id": 1, "members": {}}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn rendering_markdown() {
+        let leaderboard = Leaderboard::from_json(&sample_json()).unwrap();
+        let markdown = render_markdown(&leaderboard);
+
+        assert!(markdown.contains("| 1 | alice | 11 | 4 | 2023-12-02T05:50:00Z |"));
+        assert!(markdown.contains("| 1 | 2 | 2023-12-01T05:15:00Z | 0h 15m 00s |"));
+        assert!(markdown.contains("| 1 | bob | 0h 30m 00s |"));
+        assert!(markdown.contains("#### (anonymous user #3)"));
+    }
+}
@@ -12,20 +12,69 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::{anyhow, bail, Context};
-use cargo_edit::LocalManifest;
-use cargo_generate::{generate, GenerateArgs, TemplatePath};
-use clap::Parser;
-use reqwest::header::{HeaderMap, CONTENT_TYPE, COOKIE, USER_AGENT};
-use reqwest::redirect::Policy;
-use std::io::Write;
+use anyhow::{Context, bail};
+use aoc_common::answers::{AnswersStore, DEFAULT_PROFILE, Feedback, Part};
+use aoc_common::config::Config;
+use archive::Archived;
+use clap::{Parser, Subcommand};
+use client::{AocClient, Retry, SubmissionOutcome};
+use input::{Download, InputCache};
+use leaderboard::{Fetched, Leaderboard, LeaderboardCache, LeaderboardFormat};
+use puzzle::PuzzleExamples;
 use std::path::{Path, PathBuf};
-use std::{env, fs};
-use toml_edit::{InlineTable, Value};
+use std::time::{Duration, SystemTime};
+use std::{env, fs, thread};
+use submit::Submission;
+use template::DayTemplate;
+use workspace::{DayCrate, Transaction};
+
+mod archive;
+mod client;
+mod doctor;
+mod input;
+mod leaderboard;
+mod puzzle;
+mod submit;
+mod template;
+mod workspace;
 
 /// Simple Advent of Code template initialiser.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    init: Option<Args>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Initialises the crate of the new day (this is the default command)
+    Init(Args),
+
+    /// Records the response of the site to a submitted answer
+    Record(RecordArgs),
+
+    /// Submits the answer and records the response of the site
+    Submit(SubmitArgs),
+
+    /// Saves the description of the puzzle, including any newly unlocked parts, into the local archive
+    Archive(ArchiveArgs),
+
+    /// Unregisters the day from the workspace and the solution runner, optionally deleting its crate
+    Remove(RemoveArgs),
+
+    /// Checks whether all the days are consistently registered in the workspace and the solution runner
+    Doctor(DoctorArgs),
+
+    /// Shows the rankings, star timelines and part 1 to part 2 deltas of a private leaderboard
+    Leaderboard(LeaderboardArgs),
+}
+
+#[derive(clap::Args, Debug)]
 struct Args {
     /// Specifies the event year
     #[arg(short, long, required = true)]
@@ -38,192 +87,565 @@ struct Args {
     /// Location of the cargo-generate template
     #[arg(long)]
     workspace_root: Option<PathBuf>,
+
+    /// Wait for the release of the puzzle if it's not out yet, rather than skipping the input download
+    #[arg(long)]
+    wait: bool,
+
+    /// Base URL of the site, defaults to `AOC_BASE_URL` or the real one
+    #[arg(long)]
+    base_url: Option<String>,
+
+    /// Number of attempts at downloading the input, with exponential backoff between them
+    #[arg(long, default_value_t = 4)]
+    attempts: u32,
+
+    /// Shape of the input the skeleton is wired for, i.e. 'blank', 'grid', 'lines', 'sections' or 'instructions'
+    #[arg(short, long, default_value_t = DayTemplate::Blank)]
+    template: DayTemplate,
+}
+
+#[derive(clap::Args, Debug)]
+struct RecordArgs {
+    /// Specifies the event year
+    #[arg(short, long)]
+    year: u16,
+
+    /// Specifies the event day
+    #[arg(short, long)]
+    day: u8,
+
+    /// Specifies the puzzle part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// The submitted answer
+    #[arg(short, long)]
+    answer: String,
+
+    /// The response of the site, i.e. 'correct', 'wrong', 'too-high' or 'too-low'
+    #[arg(short, long)]
+    feedback: Feedback,
+
+    /// Profile, i.e. AoC account, the answer was submitted from
+    #[arg(long, default_value = DEFAULT_PROFILE)]
+    profile: String,
+
+    /// Root of the workspace
+    #[arg(long)]
+    workspace_root: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct SubmitArgs {
+    /// Specifies the event year
+    #[arg(short, long)]
+    year: u16,
+
+    /// Specifies the event day
+    #[arg(short, long)]
+    day: u8,
+
+    /// Specifies the puzzle part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// The answer to submit. If omitted, it's computed by the solution runner
+    answer: Option<String>,
+
+    /// Profile, i.e. AoC account, submitting the answer
+    #[arg(long, default_value = DEFAULT_PROFILE)]
+    profile: String,
+
+    /// Submit the answer even if it's already known to be wrong
+    #[arg(long)]
+    force: bool,
+
+    /// Base URL of the site, defaults to `AOC_BASE_URL` or the real one
+    #[arg(long)]
+    base_url: Option<String>,
+
+    /// Root of the workspace
+    #[arg(long)]
+    workspace_root: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ArchiveArgs {
+    /// Specifies the event year
+    #[arg(short, long)]
+    year: u16,
+
+    /// Specifies the event day
+    #[arg(short, long)]
+    day: u8,
+
+    /// Base URL of the site, defaults to `AOC_BASE_URL` or the real one
+    #[arg(long)]
+    base_url: Option<String>,
+
+    /// Root of the workspace
+    #[arg(long)]
+    workspace_root: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct RemoveArgs {
+    /// Specifies the event year
+    #[arg(short, long)]
+    year: u16,
+
+    /// Specifies the event day
+    #[arg(short, long)]
+    day: u8,
+
+    /// Also delete the crate of the day
+    #[arg(long)]
+    delete_files: bool,
+
+    /// Root of the workspace
+    #[arg(long)]
+    workspace_root: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct DoctorArgs {
+    /// Fix all the problems that can be fixed automatically
+    #[arg(long)]
+    fix: bool,
+
+    /// Root of the workspace
+    #[arg(long)]
+    workspace_root: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct LeaderboardArgs {
+    /// Specifies the event year
+    #[arg(short, long, required_unless_present = "file")]
+    year: Option<u16>,
+
+    /// Id of the private leaderboard, i.e. the id of its owner
+    #[arg(long, required_unless_present = "file")]
+    id: Option<u64>,
+
+    /// Local copy of the leaderboard JSON, used instead of downloading it
+    #[arg(long, conflicts_with_all = ["id", "base_url"])]
+    file: Option<PathBuf>,
+
+    /// Base URL of the site, defaults to `AOC_BASE_URL` or the real one
+    #[arg(long)]
+    base_url: Option<String>,
+
+    /// Rendering of the statistics, i.e. 'terminal' or 'markdown'
+    #[arg(short, long, default_value_t = LeaderboardFormat::Terminal)]
+    format: LeaderboardFormat,
+
+    /// Write the statistics into the file rather than printing them
+    #[arg(short, long)]
+    output: Option<PathBuf>,
 }
 
 fn generate_project_files(args: &Args, root: &Path) -> anyhow::Result<()> {
-    let year = &args.year;
-    let day = &args.day;
-
-    // TODO: assumption: the command is run from the workspace root
-    let template_path = root.join("tools/aoc-init/template");
-
-    let generate_args = GenerateArgs {
-        template_path: TemplatePath {
-            path: Some(template_path.display().to_string()),
-            ..Default::default()
-        },
-        name: Some("placeholder".to_string()),
-        vcs: None,
-        init: true,
-        define: vec![format!("year={year}"), format!("day={day}")],
-        ..Default::default()
+    template::generate_day(
+        &root.join(template::TEMPLATE_DIRECTORY),
+        root,
+        &args.year,
+        &args.day,
+        args.template,
+    )
+}
+
+fn try_get_input(args: &Args, root: &Path) -> anyhow::Result<()> {
+    let year: u16 = args.year.parse()?;
+    let day: u8 = args.day.parse()?;
+
+    let Ok(session_cookie) = client::session_cookie() else {
+        eprintln!("could not find a valid AOC_SESSION cookie");
+        return Ok(());
     };
 
-    generate(generate_args)?;
+    if args.wait
+        && let Some(left) = input::until_release(year, day, SystemTime::now())
+    {
+        println!(
+            "waiting {} for the release of the puzzle",
+            input::format_duration(left)
+        );
+        thread::sleep(left);
+    }
+
+    let config = Config::load_from(root)?;
+    let input_file = config.input_path(DEFAULT_PROFILE, year, day);
+    let cache = InputCache::new(
+        config.resolve(input::DEFAULT_CACHE_DIRECTORY),
+        &session_cookie,
+    );
+    let client = AocClient::new(
+        client::base_url(args.base_url.clone()),
+        Some(&session_cookie),
+    )?
+    .with_retry(Retry::new(args.attempts, Duration::from_secs(1)));
+
+    match input::download_input(&client, &cache, &input_file, year, day, SystemTime::now())? {
+        Download::Downloaded => println!("downloaded the input to {}", input_file.display()),
+        Download::Cached => println!("restored the cached input to {}", input_file.display()),
+        Download::AlreadyPresent => println!(
+            "{} already exists, so it was left untouched",
+            input_file.display()
+        ),
+        Download::NotReleased { left } => eprintln!(
+            "the puzzle will be released in {}, rerun with --wait to wait for it",
+            input::format_duration(left)
+        ),
+    }
 
     Ok(())
 }
 
-fn add_to_workspace(args: &Args, root: &Path) -> anyhow::Result<()> {
-    let package_path = format!("{}/day{}", args.year, args.day);
-    let mut manifest = LocalManifest::find(Some(root))?;
-    let members = manifest
-        .manifest
-        .data
-        .get_mut("workspace")
-        .context("no workspace")?
-        .get_mut("members")
-        .context("no members")?
-        .as_array_mut()
-        .context("members are not an array")?;
-
-    for member in members.iter() {
-        let member_str = member
-            .as_str()
-            .context("workspace member is not a string")?;
-
-        if package_path == member_str {
-            bail!("{package_path} is already part of the workspace")
+fn archive_puzzle(config: &Config, year: u16, day: u8, page: &str) -> anyhow::Result<()> {
+    let path = config.puzzle_path(year, day);
+    match archive::archive(&path, page)? {
+        Archived::Written { parts } => {
+            println!(
+                "archived {parts} part(s) of the puzzle in {}",
+                path.display()
+            )
         }
+        Archived::KeptExisting => println!(
+            "{} already contains more parts of the puzzle, so it was left untouched",
+            path.display()
+        ),
+        Archived::Missing => eprintln!("the page does not contain the puzzle description"),
     }
+    Ok(())
+}
 
-    // update the formatting info
-    let first_decor = members.iter().next().unwrap().decor().clone();
-    let last_decor = members.iter().last().unwrap().decor();
+/// Archives the puzzle description and uses its examples in the generated tests.
+fn try_get_puzzle(args: &Args, root: &Path) -> anyhow::Result<()> {
+    let year: u16 = args.year.parse()?;
+    let day: u8 = args.day.parse()?;
 
-    let mut new_entry = toml_edit::Value::from(package_path);
-    let new_entry_decor = new_entry.decor_mut();
-    if let Some(prefix) = last_decor.prefix() {
-        new_entry_decor.set_prefix(prefix.clone())
-    }
-    if let Some(suffix) = last_decor.suffix() {
-        new_entry_decor.set_suffix(suffix.clone())
+    if let Some(left) = input::until_release(year, day, SystemTime::now()) {
+        eprintln!(
+            "the puzzle will be released in {}, so its description is not available yet",
+            input::format_duration(left)
+        );
+        return Ok(());
     }
 
-    let last_member = members.iter_mut().last().unwrap();
-    let old_last_decor = last_member.decor_mut();
-    if let Some(prefix) = first_decor.prefix() {
-        old_last_decor.set_prefix(prefix.clone())
-    }
-    if let Some(suffix) = first_decor.suffix() {
-        old_last_decor.set_suffix(suffix.clone())
+    let session = client::session_cookie().ok();
+    let client = AocClient::new(client::base_url(args.base_url.clone()), session.as_deref())?
+        .with_retry(Retry::new(args.attempts, Duration::from_secs(1)));
+    let page = client.fetch_puzzle(year, day)?;
+
+    let config = Config::load_from(root)?;
+    archive_puzzle(&config, year, day, &page)?;
+
+    let examples = PuzzleExamples::extract(&page)?;
+    for written in examples.write(&config.examples_path(year, day))? {
+        println!("wrote {}", written.display())
     }
 
-    members.push_formatted(new_entry);
-    manifest.write()?;
+    let lib_path = root
+        .join(&args.year)
+        .join(format!("day{}", args.day))
+        .join("src/lib.rs");
+    let source = fs::read_to_string(&lib_path)
+        .with_context(|| format!("failed to read {}", lib_path.display()))?;
+    fs::write(&lib_path, puzzle::prefill_tests(&source, &examples))?;
 
     Ok(())
 }
 
-fn add_to_solution_runner(args: &Args, root: &Path) -> anyhow::Result<()> {
-    let year = &args.year;
-    let day = &args.day;
-    let day_normalised: u8 = day.parse()?;
-    let package_name = format!("day{day}_{year}");
-    let package_path = format!("../{year}/day{day}");
-
-    let mut manifest = LocalManifest::find(Some(root.join("solution-runner").as_path()))?;
-    let runner_main = root.join("solution-runner").join("src").join("main.rs");
-    let dependencies = manifest
-        .manifest
-        .data
-        .get_mut("dependencies")
-        .context("no dependencies")?
-        .as_table_like_mut()
-        .context("dependencies are not a table")?;
-
-    let mut aoc_deps = Vec::new();
-
-    for (name, _) in dependencies.iter() {
-        if name == package_name {
-            bail!("{package_name} is already in the dependencies of the solution runner")
+/// Saves the response to the submitted answer, so that the runner could warn about it in the future.
+fn record(args: RecordArgs) -> anyhow::Result<()> {
+    let root = args.workspace_root.unwrap_or(env::current_dir()?);
+    let config = Config::load_from(&root)?;
+    let part = Part::try_from(args.part)?;
+
+    let mut answers = AnswersStore::load(config.answers_path())?;
+    if let Some(warning) =
+        answers.check_answer(&args.profile, args.year, args.day, part, &args.answer)
+    {
+        println!(
+            "the answer '{}' was already known to be wrong: {warning}",
+            args.answer
+        )
+    }
+    answers.record_feedback(
+        &args.profile,
+        args.year,
+        args.day,
+        part,
+        &args.answer,
+        args.feedback,
+    )?;
+    answers.save(config.answers_path())?;
+
+    println!(
+        "recorded '{}' as {} for {} day {:02} part {part}",
+        args.answer, args.feedback, args.year, args.day
+    );
+    Ok(())
+}
+
+fn submit(args: SubmitArgs) -> anyhow::Result<()> {
+    let root = args.workspace_root.unwrap_or(env::current_dir()?);
+    let config = Config::load_from(&root)?;
+    let submission = Submission {
+        profile: &args.profile,
+        year: args.year,
+        day: args.day,
+        part: Part::try_from(args.part)?,
+    };
+
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let answer = submit::runner_answer(&root, submission)?;
+            println!("the solution runner computed '{answer}'");
+            answer
         }
-        if name.starts_with("day") {
-            aoc_deps.push(name)
+    };
+
+    let client = AocClient::new(
+        client::base_url(args.base_url),
+        Some(&client::session_cookie()?),
+    )?;
+    let mut answers = AnswersStore::load(config.answers_path())?;
+    let outcome =
+        submit::submit_and_record(&client, &mut answers, submission, &answer, args.force)?;
+    answers.save(config.answers_path())?;
+    println!("{outcome}");
+
+    // solving the part unlocks the next one, or the rest of the description of the final day
+    if outcome == SubmissionOutcome::Correct {
+        let archived = client
+            .fetch_puzzle(args.year, args.day)
+            .and_then(|page| archive_puzzle(&config, args.year, args.day, &page));
+        if let Err(err) = archived {
+            eprintln!("failed to archive the puzzle description: {err:#}")
         }
     }
+    Ok(())
+}
 
-    let mut table = InlineTable::new();
-    table.insert("path", package_path.into());
-
-    dependencies.insert(&package_name, toml_edit::value(Value::InlineTable(table)));
-    manifest.write()?;
+/// Saves the current description of the puzzle into the archive.
+fn archive(args: ArchiveArgs) -> anyhow::Result<()> {
+    let root = args.workspace_root.unwrap_or(env::current_dir()?);
+    let config = Config::load_from(&root)?;
 
-    // is this extremely naive and fragile?
-    // yes.
-    // does it work (for now?)
-    // sure.
-    let content = fs::read_to_string(&runner_main)?;
-    let mut content_lines = content.lines().collect::<Vec<_>>();
+    let session = client::session_cookie().ok();
+    let client = AocClient::new(client::base_url(args.base_url), session.as_deref())?;
+    let page = client.fetch_puzzle(args.year, args.day)?;
+    archive_puzzle(&config, args.year, args.day, &page)
+}
 
-    let end_index = content_lines
-        .iter()
-        .enumerate()
-        .rfind(|(_, l)| l.contains("AUTOGENERATED SOLUTIONS END"))
-        .map(|(i, _)| i)
-        .ok_or(anyhow!("AUTOGENERATED tags missing"))?;
+/// Runs the steps of the day setup that modify the workspace, skipping the ones that have already been done.
+fn scaffold(args: &Args, root: &Path, day: DayCrate) -> anyhow::Result<()> {
+    let day_directory = root.join(day.member_path());
+    if day_directory.exists() {
+        println!(
+            "{} already exists, so it was not regenerated",
+            day_directory.display()
+        )
+    } else {
+        generate_project_files(args, root)?;
+    }
 
-    let def = format!(
-        "    define_solution!(args, {year}, {day_normalised}, \"inputs/{year}/day{day}\", {package_name}::Day{day});"
-    );
-    content_lines.insert(end_index, &def);
+    if !workspace::add_to_workspace(root, day)? {
+        println!("{} is already part of the workspace", day.member_path())
+    }
+    if !workspace::add_to_solution_runner(root, day)? {
+        println!(
+            "{} is already registered in the solution runner",
+            day.package_name()
+        )
+    }
+    Ok(())
+}
 
-    let new_content = content_lines.join("\n");
-    fs::write(&runner_main, new_content)?;
+/// Runs the provided steps, undoing all the changes they've made to the workspace if any of them fails.
+fn transactional<F>(root: &Path, day: DayCrate, steps: F) -> anyhow::Result<()>
+where
+    F: FnOnce() -> anyhow::Result<()>,
+{
+    let mut transaction = Transaction::default();
+    for file in workspace::registration_files(root) {
+        transaction.track_file(file)?;
+    }
+    transaction.track_directory(root.join(day.year.to_string()));
+    transaction.track_directory(root.join(day.member_path()));
 
+    if let Err(err) = steps() {
+        if let Err(rollback_err) = transaction.rollback() {
+            eprintln!("failed to roll back the changes: {rollback_err:#}");
+            return Err(err);
+        }
+        return Err(err.context("all the changes to the workspace have been rolled back"));
+    }
     Ok(())
 }
 
-fn try_get_input(args: &Args, root: &Path) -> anyhow::Result<()> {
-    let year = &args.year;
-    let day = &args.day;
-    let day_normalised: u8 = day.parse()?;
+fn init(args: Args) -> anyhow::Result<()> {
+    let root = args.workspace_root.clone().unwrap_or(env::current_dir()?);
+    let day = DayCrate {
+        year: args.year.parse().context("invalid event year")?,
+        day: args.day.parse().context("invalid event day")?,
+    };
 
-    let input_dir = root.join("inputs").join(year);
-    let input_file = input_dir.join(format!("day{day}"));
+    transactional(&root, day, || scaffold(&args, &root, day))?;
+    try_get_input(&args, &root)?;
+    // the description is only a convenience, so failing to get it shouldn't fail the whole setup
+    if let Err(err) = try_get_puzzle(&args, &root) {
+        eprintln!("failed to get the puzzle description: {err:#}")
+    }
 
-    fs::create_dir_all(input_dir)?;
-    let mut file = fs::File::create(input_file)?;
+    Ok(())
+}
 
-    let Ok(session_cookie) = env::var("AOC_SESSION") else {
-        eprintln!("could not find a valid AOC_SESSION cookie");
-        return Ok(());
+/// Reverts the registration of the day, i.e. the opposite of `init`.
+fn remove(args: RemoveArgs) -> anyhow::Result<()> {
+    let root = args.workspace_root.unwrap_or(env::current_dir()?);
+    let day = DayCrate {
+        year: args.year,
+        day: args.day,
     };
 
-    let cookie_header = format!("session={}", session_cookie.trim());
+    transactional(&root, day, || {
+        if workspace::remove_from_workspace(&root, day)? {
+            println!("removed {} from the workspace", day.member_path())
+        }
+        if workspace::remove_from_solution_runner(&root, day)? {
+            println!("removed {} from the solution runner", day.package_name())
+        }
+        Ok(())
+    })?;
+
+    let day_directory = root.join(day.member_path());
+    if args.delete_files && day_directory.exists() {
+        fs::remove_dir_all(&day_directory)
+            .with_context(|| format!("failed to delete {}", day_directory.display()))?;
+        println!("deleted {}", day_directory.display())
+    }
+    Ok(())
+}
 
-    let mut headers = HeaderMap::new();
-    headers.insert(COOKIE, cookie_header.parse()?);
-    headers.insert(CONTENT_TYPE, "text/plain".parse()?);
-    headers.insert(
-        USER_AGENT,
-        "https://github.com/jstuczyn/AdventOfCode by jedrzej.stuczynski@gmail.com".parse()?,
-    );
+fn report_problems(problems: &[doctor::Problem]) -> usize {
+    let mut errors = 0;
+    for problem in problems {
+        if problem.is_warning() {
+            println!("warning: {problem}")
+        } else {
+            errors += 1;
+            println!("error: {problem}")
+        }
+    }
+    errors
+}
 
-    let input = reqwest::blocking::Client::builder()
-        .default_headers(headers)
-        .redirect(Policy::none())
-        .build()?
-        .get(format!(
-            "https://adventofcode.com/{year}/day/{day_normalised}/input"
-        ))
-        .send()
-        .and_then(|response| response.error_for_status())
-        .and_then(|response| response.bytes())?;
+/// Cross-checks the registration of all the days, optionally fixing the problems.
+fn doctor(args: DoctorArgs) -> anyhow::Result<()> {
+    let root = args.workspace_root.unwrap_or(env::current_dir()?);
 
-    file.write_all(&input)?;
+    let mut problems = doctor::diagnose(&root)?;
+    if args.fix {
+        let attempted = doctor::fix(&root, &problems)?;
+        if attempted > 0 {
+            println!("attempted to fix {attempted} problem(s)");
+            problems = doctor::diagnose(&root)?;
+        }
+    }
 
+    let errors = report_problems(&problems);
+    let fixable = problems
+        .iter()
+        .filter(|problem| problem.is_fixable())
+        .count();
+    if fixable > 0 {
+        println!("{fixable} of the problems can be fixed automatically by running with --fix")
+    }
+    if errors > 0 {
+        bail!("found {errors} inconsistencies in the workspace")
+    }
+    println!(
+        "the workspace is consistent, with {} warning(s)",
+        problems.len()
+    );
     Ok(())
 }
 
-fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
-    let root = args.workspace_root.clone().unwrap_or(env::current_dir()?);
+/// Renders the statistics of the private leaderboard, either downloaded or read from a local file.
+fn leaderboard(args: LeaderboardArgs) -> anyhow::Result<()> {
+    let json = match (&args.file, args.year, args.id) {
+        (Some(file), _, _) => fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?,
+        (None, Some(year), Some(id)) => {
+            let client = AocClient::new(
+                client::base_url(args.base_url),
+                Some(&client::session_cookie()?),
+            )?;
+            let cache = LeaderboardCache::new(
+                Config::load()?.resolve(leaderboard::DEFAULT_CACHE_DIRECTORY),
+            );
+            match leaderboard::fetch_leaderboard(&client, &cache, year, id, SystemTime::now())? {
+                Fetched::Downloaded(json) => json,
+                Fetched::Cached { json, age } => {
+                    eprintln!(
+                        "using the leaderboard downloaded {} ago",
+                        input::format_duration(age)
+                    );
+                    json
+                }
+            }
+        }
+        _ => bail!("either the leaderboard file or its year and id have to be specified"),
+    };
 
-    generate_project_files(&args, &root)?;
-    add_to_workspace(&args, &root)?;
-    add_to_solution_runner(&args, &root)?;
-    try_get_input(&args, &root)?;
+    let leaderboard = Leaderboard::from_json(&json)?;
+    if let Some(year) = args.year
+        && year != leaderboard.year()
+    {
+        bail!(
+            "the leaderboard is of the {} event rather than {year}",
+            leaderboard.year()
+        )
+    }
+
+    for mismatch in leaderboard.score_mismatches() {
+        eprintln!(
+            "warning: the site reports the score of {} as {}, while the completion times add up to {}",
+            mismatch.name, mismatch.reported, mismatch.computed
+        )
+    }
 
+    let rendered = leaderboard::render(&leaderboard, args.format);
+    match args.output {
+        Some(output) => {
+            fs::write(&output, rendered)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            println!("wrote the leaderboard statistics to {}", output.display());
+        }
+        None => print!("{rendered}"),
+    }
     Ok(())
 }
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Init(args)) => init(args),
+        Some(Command::Record(args)) => record(args),
+        Some(Command::Submit(args)) => submit(args),
+        Some(Command::Archive(args)) => archive(args),
+        Some(Command::Remove(args)) => remove(args),
+        Some(Command::Doctor(args)) => doctor(args),
+        Some(Command::Leaderboard(args)) => leaderboard(args),
+        None => init(
+            cli.init
+                .context("the event year and day have not been specified")?,
+        ),
+    }
+}
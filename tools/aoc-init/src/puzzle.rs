@@ -0,0 +1,290 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{Context, anyhow};
+use scraper::{ElementRef, Html, Selector};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// placeholders of the tests generated from the day template, which get replaced with the extracted examples
+const SAMPLE_INPUT_PLACEHOLDER: &str = "const SAMPLE_INPUT: &str = r#\"\"#;";
+const EXPECTED_PLACEHOLDER: &str = "let expected: usize = 0;";
+// the sample tests stay ignored until their expected answers are known
+const IGNORED_TEST: &str =
+    "    #[ignore = \"the expected answer of the sample is yet to be filled in\"]\n";
+
+fn selector(selectors: &str) -> anyhow::Result<Selector> {
+    Selector::parse(selectors).map_err(|err| anyhow!("invalid selector '{selectors}': {err}"))
+}
+
+/// Example extracted from the description of a single part of the puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartExample {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    pub part1: PartExample,
+    pub part2: PartExample,
+}
+
+impl PartExample {
+    // the first code block usually contains the example input, while any later ones show the intermediate steps.
+    // similarly, the example answer is usually the last emphasised code fragment, right before the actual question
+    fn extract(article: ElementRef) -> anyhow::Result<Self> {
+        let code_blocks = selector("pre > code")?;
+        let emphasised = selector("code > em, em > code")?;
+
+        Ok(PartExample {
+            input: article
+                .select(&code_blocks)
+                .next()
+                .map(|code| code.text().collect::<String>().trim_end().to_string()),
+            answer: article
+                .select(&emphasised)
+                .last()
+                .map(|answer| answer.text().collect::<String>().trim().to_string()),
+        })
+    }
+}
+
+impl PuzzleExamples {
+    /// Extracts the examples from the puzzle page. The second part, if unlocked, falls back to the input of the first one.
+    pub fn extract(page: &str) -> anyhow::Result<Self> {
+        let document = Html::parse_document(page);
+        let articles = selector("article.day-desc")?;
+
+        let mut parts = document.select(&articles);
+        let part1 = parts
+            .next()
+            .map(PartExample::extract)
+            .transpose()?
+            .unwrap_or_default();
+        let mut part2 = parts
+            .next()
+            .map(PartExample::extract)
+            .transpose()?
+            .unwrap_or_default();
+        if part2.answer.is_some() && part2.input.is_none() {
+            part2.input.clone_from(&part1.input)
+        }
+
+        Ok(PuzzleExamples { part1, part2 })
+    }
+
+    pub fn sample_input(&self) -> Option<&str> {
+        self.part1.input.as_deref().or(self.part2.input.as_deref())
+    }
+
+    /// Writes the examples into the provided directory, as `partX` and `partX.answer`, without overwriting any existing files.
+    pub fn write(&self, directory: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let files = [
+            ("part1", &self.part1.input),
+            ("part1.answer", &self.part1.answer),
+            ("part2", &self.part2.input),
+            ("part2.answer", &self.part2.answer),
+        ];
+
+        let mut written = Vec::new();
+        for (name, content) in files {
+            let Some(content) = content else {
+                continue;
+            };
+            let path = directory.join(name);
+            let has_content = fs::metadata(&path)
+                .map(|metadata| metadata.len() > 0)
+                .unwrap_or_default();
+            if has_content {
+                continue;
+            }
+
+            fs::create_dir_all(directory)?;
+            fs::write(&path, format!("{content}\n"))
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+fn raw_string_literal(content: &str) -> String {
+    let mut hashes = "#".to_string();
+    while content.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{content}\"{hashes}")
+}
+
+fn answer_literal(answer: &str) -> String {
+    if answer.parse::<i128>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+/// Fills the sample input and the expected answers of the generated tests with the extracted examples.
+pub fn prefill_tests(source: &str, examples: &PuzzleExamples) -> String {
+    let mut source = source.to_string();
+    if let Some(input) = examples.sample_input() {
+        source = source.replacen(
+            SAMPLE_INPUT_PLACEHOLDER,
+            &format!("const SAMPLE_INPUT: &str = {};", raw_string_literal(input)),
+            1,
+        );
+    }
+
+    let answers = [
+        ("part1_sample_input", &examples.part1.answer),
+        ("part2_sample_input", &examples.part2.answer),
+    ];
+    for (test, answer) in answers {
+        let Some(answer) = answer else {
+            continue;
+        };
+        let Some(test_start) = source.find(&format!("    fn {test}()")) else {
+            continue;
+        };
+        let Some(placeholder) = source[test_start..]
+            .find(EXPECTED_PLACEHOLDER)
+            .map(|offset| test_start + offset)
+        else {
+            continue;
+        };
+
+        // the placeholder comes after the attribute, so it's replaced first to keep the position of the latter valid
+        source.replace_range(
+            placeholder..placeholder + EXPECTED_PLACEHOLDER.len(),
+            &format!("let expected = {};", answer_literal(answer)),
+        );
+        if source[..test_start].ends_with(IGNORED_TEST) {
+            source.replace_range(test_start - IGNORED_TEST.len()..test_start, "");
+        }
+    }
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::{DayTemplate, generate_day};
+
+    const PUZZLE: &str = include_str!("../fixtures/puzzle.html");
+
+    fn generated_lib(name: &str, parsing: DayTemplate) -> String {
+        let destination =
+            std::env::temp_dir().join(format!("aoc-init-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&destination);
+        fs::create_dir_all(&destination).unwrap();
+        let template = Path::new(env!("CARGO_MANIFEST_DIR")).join("template");
+        generate_day(&template, &destination, "2024", "05", parsing).unwrap();
+
+        let lib = fs::read_to_string(destination.join("2024/day05/src/lib.rs")).unwrap();
+        fs::remove_dir_all(&destination).unwrap();
+        lib
+    }
+
+    #[test]
+    fn extracting_examples() {
+        let examples = PuzzleExamples::extract(PUZZLE).unwrap();
+        let sample = "3|7\n7|1\n\n3,7,1\n1,3";
+        assert_eq!(
+            PuzzleExamples {
+                part1: PartExample {
+                    input: Some(sample.to_string()),
+                    answer: Some("7".to_string()),
+                },
+                part2: PartExample {
+                    input: Some(sample.to_string()),
+                    answer: Some("123".to_string()),
+                },
+            },
+            examples
+        );
+
+        // only the first part is available before solving it
+        let locked = PUZZLE.replacen("<article class=\"day-desc\"><h2 id=\"part2\">", "<div>", 1);
+        let examples = PuzzleExamples::extract(&locked).unwrap();
+        assert_eq!(Some("7"), examples.part1.answer.as_deref());
+        assert_eq!(PartExample::default(), examples.part2);
+
+        assert_eq!(
+            PuzzleExamples::default(),
+            PuzzleExamples::extract("<html></html>").unwrap()
+        );
+    }
+
+    #[test]
+    fn prefilling_generated_tests() {
+        let examples = PuzzleExamples::extract(PUZZLE).unwrap();
+        for parsing in [
+            DayTemplate::Blank,
+            DayTemplate::Grid,
+            DayTemplate::Lines,
+            DayTemplate::Sections,
+            DayTemplate::Instructions,
+        ] {
+            let generated = generated_lib(&format!("prefilling-{parsing}"), parsing);
+            assert_eq!(2, generated.matches(IGNORED_TEST).count());
+
+            let prefilled = prefill_tests(&generated, &examples);
+            assert!(
+                prefilled.contains("const SAMPLE_INPUT: &str = r#\"3|7\n7|1\n\n3,7,1\n1,3\"#;"),
+                "{parsing}: {prefilled}"
+            );
+            assert!(prefilled.contains("let expected = 7;"));
+            assert!(prefilled.contains("let expected = 123;"));
+            assert!(!prefilled.contains("#[ignore"));
+        }
+
+        let part2_only = PuzzleExamples {
+            part1: PartExample::default(),
+            part2: PartExample {
+                input: Some("say \"#hi\"".to_string()),
+                answer: Some("abc".to_string()),
+            },
+        };
+        let generated = generated_lib("prefilling-part2", DayTemplate::Blank);
+        let prefilled = prefill_tests(&generated, &part2_only);
+        assert!(prefilled.contains("const SAMPLE_INPUT: &str = r##\"say \"#hi\"\"##;"));
+        let part1_test = prefilled.find("part1_sample_input").unwrap();
+        let part2_test = prefilled.find("part2_sample_input").unwrap();
+        assert!(prefilled[..part1_test].ends_with(&format!("{IGNORED_TEST}    fn ")));
+        assert!(prefilled[part1_test..part2_test].contains(EXPECTED_PLACEHOLDER));
+        assert!(prefilled[part1_test..part2_test].ends_with("    #[test]\n    fn "));
+        assert!(prefilled[part2_test..].contains("let expected = \"abc\";"));
+    }
+
+    #[test]
+    fn writing_examples() {
+        let directory =
+            std::env::temp_dir().join(format!("aoc-init-{}-writing-examples", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+
+        let examples = PuzzleExamples::extract(PUZZLE).unwrap();
+        assert_eq!(4, examples.write(&directory).unwrap().len());
+        assert_eq!(
+            "123\n",
+            fs::read_to_string(directory.join("part2.answer")).unwrap()
+        );
+
+        fs::write(directory.join("part1"), "mine").unwrap();
+        assert!(examples.write(&directory).unwrap().is_empty());
+        assert_eq!("mine", fs::read_to_string(directory.join("part1")).unwrap());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
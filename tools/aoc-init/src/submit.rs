@@ -0,0 +1,177 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::client::{AocClient, SubmissionOutcome};
+use anyhow::{Context, bail};
+use aoc_common::answers::{AnswersStore, Part};
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Puzzle part the answer is submitted for.
+#[derive(Debug, Clone, Copy)]
+pub struct Submission<'a> {
+    pub profile: &'a str,
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+}
+
+/// Submits the answer, unless it's already known to be wrong, and records the response in the store.
+pub fn submit_and_record(
+    client: &AocClient,
+    answers: &mut AnswersStore,
+    submission: Submission,
+    answer: &str,
+    force: bool,
+) -> anyhow::Result<SubmissionOutcome> {
+    let Submission {
+        profile,
+        year,
+        day,
+        part,
+    } = submission;
+    let answer = answer.trim();
+
+    if let Some(correct) = answers.correct_answer(profile, year, day, part) {
+        bail!("part {part} has already been solved, the accepted answer is '{correct}'")
+    }
+    if let Some(warning) = answers.check_answer(profile, year, day, part, answer)
+        && !force
+    {
+        bail!("refusing to submit '{answer}', {warning}. use --force to submit it anyway")
+    }
+
+    let outcome = client.submit(year, day, part, answer)?;
+    if let Some(feedback) = outcome.feedback() {
+        answers.record_feedback(profile, year, day, part, answer, feedback)?;
+    }
+    Ok(outcome)
+}
+
+/// Runs the solution runner and extracts the answer to the specified part from its JSON output.
+pub fn runner_answer(root: &Path, submission: Submission) -> anyhow::Result<String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .current_dir(root)
+        .args(["run", "--release", "-q", "-p", "solution-runner", "--"])
+        .args(["--year", &submission.year.to_string()])
+        .args(["--day", &submission.day.to_string()])
+        .args(["--profile", submission.profile])
+        .args(["--output", "json", "--no-history"])
+        .output()
+        .context("failed to start the solution runner")?;
+    if !output.status.success() {
+        bail!(
+            "the solution runner has failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    }
+
+    answer_from_json(&String::from_utf8_lossy(&output.stdout), submission.part)
+}
+
+fn answer_from_json(output: &str, part: Part) -> anyhow::Result<String> {
+    let line = output
+        .lines()
+        .rfind(|line| !line.trim().is_empty())
+        .context("the solution runner did not produce any output")?;
+    let result: serde_json::Value = serde_json::from_str(line)
+        .context("the output of the solution runner is not valid JSON")?;
+
+    let part_result = &result[format!("part{part}")];
+    match (
+        part_result["answer"].as_str(),
+        part_result["error"].as_str(),
+    ) {
+        (Some(answer), _) => Ok(answer.trim().to_string()),
+        (None, Some(err)) => bail!("the solution has failed to solve part {part}: {err}"),
+        (None, None) => bail!("the solution runner did not report the answer to part {part}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::stub_server;
+    use aoc_common::answers::{AnswerWarning, DEFAULT_PROFILE};
+
+    const SUBMISSION: Submission = Submission {
+        profile: DEFAULT_PROFILE,
+        year: 2024,
+        day: 5,
+        part: Part::One,
+    };
+
+    #[test]
+    fn extracting_runner_answers() {
+        let output = r#"{"year":2024,"day":5,"solver":"day05_2024::Day05","parsing_ns":1,"part1":{"taken_ns":2,"answer":"143"},"part2":{"taken_ns":3,"error":"oops"}}"#;
+        assert_eq!("143", answer_from_json(output, Part::One).unwrap());
+        assert!(answer_from_json(output, Part::Two).is_err());
+        assert!(answer_from_json("", Part::One).is_err());
+    }
+
+    #[test]
+    fn submission_flow() {
+        let (base_url, requests) = stub_server(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too high.</p>".to_string(),
+            ),
+            (
+                200,
+                "<p>You gave an answer too recently. You have 30s left to wait.</p>".to_string(),
+            ),
+            (200, "<p>That's the right answer!</p>".to_string()),
+        ]);
+        let client = AocClient::new(base_url, Some("secret")).unwrap();
+        let mut answers = AnswersStore::default();
+
+        let outcome = submit_and_record(&client, &mut answers, SUBMISSION, "500", false).unwrap();
+        assert_eq!(SubmissionOutcome::TooHigh, outcome);
+        assert_eq!(
+            Some(AnswerWarning::TooHigh { bound: 500 }),
+            answers.check_answer(DEFAULT_PROFILE, 2024, 5, Part::One, "600")
+        );
+
+        // known to be wrong, so it never reaches the server
+        let err = submit_and_record(&client, &mut answers, SUBMISSION, "600", false).unwrap_err();
+        assert!(err.to_string().contains("refusing to submit '600'"));
+
+        let outcome = submit_and_record(&client, &mut answers, SUBMISSION, "400", false).unwrap();
+        assert!(matches!(outcome, SubmissionOutcome::RateLimited { .. }));
+        assert_eq!(
+            None,
+            answers.check_answer(DEFAULT_PROFILE, 2024, 5, Part::One, "400")
+        );
+
+        let outcome = submit_and_record(&client, &mut answers, SUBMISSION, "400", false).unwrap();
+        assert_eq!(SubmissionOutcome::Correct, outcome);
+        assert_eq!(
+            Some("400"),
+            answers.correct_answer(DEFAULT_PROFILE, 2024, 5, Part::One)
+        );
+        assert!(submit_and_record(&client, &mut answers, SUBMISSION, "400", false).is_err());
+
+        let bodies = requests.try_iter().map(|r| r.body).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "level=1&answer=500",
+                "level=1&answer=400",
+                "level=1&answer=400"
+            ],
+            bodies
+        );
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::bail;
+use cargo_generate::{GenerateArgs, TemplatePath, generate};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Location of the cargo-generate template of the days, relative to the workspace root.
+pub const TEMPLATE_DIRECTORY: &str = "tools/aoc-init/template";
+
+/// Shape of the generated day, i.e. which of the `aoc_common::parsing` helpers the skeleton is wired to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DayTemplate {
+    /// No parsing at all, the input type has to be filled in manually
+    #[default]
+    Blank,
+    /// Grid of characters, parsed into `Grid<T>` of a `ParsableGridItem`
+    Grid,
+    /// One item per line, parsed with `LineParser`
+    Lines,
+    /// Sections separated by blank lines, parsed with `GroupsParser`
+    Sections,
+    /// List of instructions, parsed with a winnow-based `AocInputParser`
+    Instructions,
+}
+
+impl FromStr for DayTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blank" => Ok(DayTemplate::Blank),
+            "grid" => Ok(DayTemplate::Grid),
+            "lines" => Ok(DayTemplate::Lines),
+            "sections" => Ok(DayTemplate::Sections),
+            "instructions" => Ok(DayTemplate::Instructions),
+            other => bail!(
+                "'{other}' is not a valid template, expected 'blank', 'grid', 'lines', 'sections' or 'instructions'"
+            ),
+        }
+    }
+}
+
+impl Display for DayTemplate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayTemplate::Blank => write!(f, "blank"),
+            DayTemplate::Grid => write!(f, "grid"),
+            DayTemplate::Lines => write!(f, "lines"),
+            DayTemplate::Sections => write!(f, "sections"),
+            DayTemplate::Instructions => write!(f, "instructions"),
+        }
+    }
+}
+
+/// Renders the template of the day, i.e. `<year>/dayXX`, into the destination directory.
+pub fn generate_day(
+    template: &Path,
+    destination: &Path,
+    year: &str,
+    day: &str,
+    parsing: DayTemplate,
+) -> anyhow::Result<()> {
+    let generate_args = GenerateArgs {
+        template_path: TemplatePath {
+            path: Some(template.display().to_string()),
+            ..Default::default()
+        },
+        name: Some("placeholder".to_string()),
+        destination: Some(destination.to_path_buf()),
+        vcs: None,
+        init: true,
+        define: vec![
+            format!("year={year}"),
+            format!("day={day}"),
+            format!("parsing={parsing}"),
+        ],
+        ..Default::default()
+    };
+
+    generate(generate_args)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::{PartExample, PuzzleExamples, prefill_tests};
+    use std::fs;
+
+    #[test]
+    fn template_names_round_trip() {
+        for template in [
+            DayTemplate::Blank,
+            DayTemplate::Grid,
+            DayTemplate::Lines,
+            DayTemplate::Sections,
+            DayTemplate::Instructions,
+        ] {
+            assert_eq!(template, template.to_string().parse().unwrap());
+        }
+        assert!("matrix".parse::<DayTemplate>().is_err());
+    }
+
+    #[test]
+    fn prefilling_rendered_templates() {
+        let destination =
+            std::env::temp_dir().join(format!("aoc-init-{}-rendering", std::process::id()));
+        let _ = fs::remove_dir_all(&destination);
+        fs::create_dir_all(&destination).unwrap();
+        let template = Path::new(env!("CARGO_MANIFEST_DIR")).join("template");
+        let examples = PuzzleExamples {
+            part1: PartExample {
+                input: Some("1\n2".to_string()),
+                answer: Some("3".to_string()),
+            },
+            part2: PartExample {
+                input: None,
+                answer: Some("4".to_string()),
+            },
+        };
+
+        for (day, parsing) in [
+            DayTemplate::Blank,
+            DayTemplate::Grid,
+            DayTemplate::Lines,
+            DayTemplate::Sections,
+            DayTemplate::Instructions,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let day = format!("{:02}", day + 1);
+            generate_day(&template, &destination, "2024", &day, parsing).unwrap();
+            let lib = destination.join(format!("2024/day{day}/src/lib.rs"));
+            let generated = fs::read_to_string(lib).unwrap();
+            assert_eq!(2, generated.matches("#[ignore").count(), "{parsing}");
+
+            // every placeholder of the skeleton gets filled in
+            let prefilled = prefill_tests(&generated, &examples);
+            assert!(
+                prefilled.contains("const SAMPLE_INPUT: &str = r#\"1\n2\"#;"),
+                "{parsing}"
+            );
+            assert!(prefilled.contains("let expected = 3;"), "{parsing}");
+            assert!(prefilled.contains("let expected = 4;"), "{parsing}");
+            assert!(!prefilled.contains("#[ignore"), "{parsing}");
+        }
+
+        fs::remove_dir_all(&destination).unwrap();
+    }
+}
@@ -0,0 +1,733 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registration of the day crates in the workspace: its root manifest, and the dependencies, features and
+//! the list of solutions of the solution runner.
+
+use anyhow::{Context, anyhow};
+use cargo_edit::LocalManifest;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, InlineTable, Value};
+
+const SOLUTIONS_END_TAG: &str = "AUTOGENERATED SOLUTIONS END";
+
+/// Crate of a single day of the event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DayCrate {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl DayCrate {
+    /// Parses the name of the package, e.g. `day05_2024`.
+    pub fn from_package_name(name: &str) -> Option<Self> {
+        let (day, year) = name.strip_prefix("day")?.split_once('_')?;
+        Some(DayCrate {
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+        })
+    }
+
+    /// Parses the location of the crate, relative to the root of the workspace, e.g. `2024/day05`.
+    pub fn from_member_path(path: &str) -> Option<Self> {
+        let (year, day) = path.split_once('/')?;
+        Some(DayCrate {
+            year: year.parse().ok()?,
+            day: day.strip_prefix("day")?.parse().ok()?,
+        })
+    }
+
+    /// Parses the type registered by the `define_solution!` line, e.g. `day05_2024::Day05`.
+    pub fn from_registration(line: &str) -> Option<Self> {
+        let arguments = line.trim().strip_prefix("define_solution!(")?;
+        let solver = arguments.trim_end_matches(");").rsplit(',').next()?.trim();
+        let (package, _) = solver.split_once("::")?;
+        DayCrate::from_package_name(package)
+    }
+
+    pub fn package_name(&self) -> String {
+        format!("day{:02}_{}", self.day, self.year)
+    }
+
+    /// Location of the crate, relative to the root of the workspace.
+    pub fn member_path(&self) -> String {
+        format!("{}/day{:02}", self.year, self.day)
+    }
+
+    pub fn year_feature(&self) -> String {
+        format!("year{}", self.year)
+    }
+
+    /// Line gating the registration of the solution behind the year feature.
+    pub fn solution_feature_gate(&self) -> String {
+        format!("    #[cfg(feature = \"{}\")]", self.year_feature())
+    }
+
+    /// Line registering the solution in the solution runner.
+    pub fn solution_definition(&self) -> String {
+        format!(
+            "    define_solution!(solutions, {year}, {day}, \"inputs/{year}/day{day:02}\", {package}::Day{day:02});",
+            year = self.year,
+            day = self.day,
+            package = self.package_name()
+        )
+    }
+}
+
+pub fn runner_manifest_path(root: &Path) -> PathBuf {
+    root.join("solution-runner").join("Cargo.toml")
+}
+
+pub fn runner_solutions_path(root: &Path) -> PathBuf {
+    root.join("solution-runner")
+        .join("src")
+        .join("solutions.rs")
+}
+
+/// Files touched while registering the day.
+pub fn registration_files(root: &Path) -> Vec<PathBuf> {
+    vec![
+        root.join("Cargo.toml"),
+        runner_manifest_path(root),
+        runner_solutions_path(root),
+    ]
+}
+
+fn workspace_members(manifest: &mut LocalManifest) -> anyhow::Result<&mut Array> {
+    manifest
+        .manifest
+        .data
+        .get_mut("workspace")
+        .context("no workspace")?
+        .get_mut("members")
+        .context("no members")?
+        .as_array_mut()
+        .context("members are not an array")
+}
+
+/// Lists the members of the workspace, in the order they're defined in.
+pub fn workspace_member_paths(root: &Path) -> anyhow::Result<Vec<String>> {
+    let mut manifest = LocalManifest::find(Some(root))?;
+    workspace_members(&mut manifest)?
+        .iter()
+        .map(|member| {
+            member
+                .as_str()
+                .map(String::from)
+                .context("workspace member is not a string")
+        })
+        .collect()
+}
+
+/// Adds the crate to the members of the workspace, unless it's already there.
+/// Returns whether the manifest has been changed.
+pub fn add_to_workspace(root: &Path, day: DayCrate) -> anyhow::Result<bool> {
+    let package_path = day.member_path();
+    let mut manifest = LocalManifest::find(Some(root))?;
+    let members = workspace_members(&mut manifest)?;
+
+    for member in members.iter() {
+        let member_str = member
+            .as_str()
+            .context("workspace member is not a string")?;
+
+        if package_path == member_str {
+            return Ok(false);
+        }
+    }
+
+    // update the formatting info
+    let first_decor = members.iter().next().context("no members")?.decor().clone();
+    let last_decor = members.iter().last().context("no members")?.decor();
+
+    let mut new_entry = Value::from(package_path);
+    let new_entry_decor = new_entry.decor_mut();
+    if let Some(prefix) = last_decor.prefix() {
+        new_entry_decor.set_prefix(prefix.clone())
+    }
+    if let Some(suffix) = last_decor.suffix() {
+        new_entry_decor.set_suffix(suffix.clone())
+    }
+
+    let last_member = members.iter_mut().last().context("no members")?;
+    let old_last_decor = last_member.decor_mut();
+    if let Some(prefix) = first_decor.prefix() {
+        old_last_decor.set_prefix(prefix.clone())
+    }
+    if let Some(suffix) = first_decor.suffix() {
+        old_last_decor.set_suffix(suffix.clone())
+    }
+
+    members.push_formatted(new_entry);
+    manifest.write()?;
+
+    Ok(true)
+}
+
+/// Removes the crate from the members of the workspace. Returns whether it was there in the first place.
+pub fn remove_from_workspace(root: &Path, day: DayCrate) -> anyhow::Result<bool> {
+    remove_workspace_member(root, &day.member_path())
+}
+
+/// Removes the member from the workspace. Returns whether it was there in the first place.
+pub fn remove_workspace_member(root: &Path, package_path: &str) -> anyhow::Result<bool> {
+    let mut manifest = LocalManifest::find(Some(root))?;
+    let members = workspace_members(&mut manifest)?;
+
+    let Some(index) = members
+        .iter()
+        .position(|member| member.as_str() == Some(package_path))
+    else {
+        return Ok(false);
+    };
+
+    let removed = members.remove(index);
+    // the last entry is formatted differently, e.g. it's followed by the line break before the closing bracket
+    if index == members.len()
+        && let Some(last_member) = members.iter_mut().last()
+    {
+        *last_member.decor_mut() = removed.decor().clone();
+    }
+
+    manifest.write()?;
+    Ok(true)
+}
+
+/// Sorts the day crates among the members of the workspace by their year and day, leaving the other members in place.
+/// Returns whether the order has changed.
+pub fn sort_workspace_members(root: &Path) -> anyhow::Result<bool> {
+    let mut manifest = LocalManifest::find(Some(root))?;
+    let members = workspace_members(&mut manifest)?;
+
+    let days = members
+        .iter()
+        .enumerate()
+        .filter_map(|(index, member)| {
+            let path = member.as_str()?;
+            DayCrate::from_member_path(path).map(|day| (index, day, path.to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut sorted = days.clone();
+    sorted.sort_by_key(|(_, day, _)| (day.year, day.day));
+    if sorted == days {
+        return Ok(false);
+    }
+
+    // the entries are moved between the slots, which keep their formatting
+    for ((index, _, _), (_, _, path)) in days.iter().zip(sorted) {
+        members.replace(*index, path);
+    }
+    manifest.write()?;
+    Ok(true)
+}
+
+/// Rewrites the registration of the solution of the day, so that its year, day, input path and type
+/// all match the crate. Returns whether anything has been changed.
+pub fn fix_registration(root: &Path, day: DayCrate) -> anyhow::Result<bool> {
+    let runner_solutions = runner_solutions_path(root);
+    let content = fs::read_to_string(&runner_solutions)?;
+    let mut content_lines = content.lines().map(String::from).collect::<Vec<_>>();
+
+    let package = format!("{}::", day.package_name());
+    let Some(index) = content_lines
+        .iter()
+        .position(|line| line.contains("define_solution!") && line.contains(&package))
+    else {
+        return Ok(false);
+    };
+
+    let original = content_lines.clone();
+    content_lines[index] = day.solution_definition();
+    if index > 0
+        && content_lines[index - 1]
+            .trim_start()
+            .starts_with("#[cfg(feature")
+    {
+        content_lines[index - 1] = day.solution_feature_gate();
+    } else {
+        content_lines.insert(index, day.solution_feature_gate());
+    }
+    if content_lines == original {
+        return Ok(false);
+    }
+
+    let lines = content_lines.iter().map(String::as_str).collect::<Vec<_>>();
+    fs::write(&runner_solutions, join_lines(&lines, &content))?;
+    Ok(true)
+}
+
+/// Moves the registrations of the solutions, along with their feature gates, into the chronological order.
+/// Returns whether anything has been changed.
+pub fn sort_registrations(root: &Path) -> anyhow::Result<bool> {
+    let runner_solutions = runner_solutions_path(root);
+    let content = fs::read_to_string(&runner_solutions)?;
+    let content_lines = content.lines().collect::<Vec<_>>();
+
+    // every registration occupies a slot of either one or two lines, depending on whether it's gated
+    let mut slots = Vec::new();
+    for (index, line) in content_lines.iter().enumerate() {
+        let Some(day) = DayCrate::from_registration(line) else {
+            continue;
+        };
+        let gated = index > 0
+            && content_lines[index - 1]
+                .trim_start()
+                .starts_with("#[cfg(feature");
+        let start = if gated { index - 1 } else { index };
+        slots.push((start..index + 1, day));
+    }
+
+    let mut sorted = slots.clone();
+    sorted.sort_by_key(|(_, day)| (day.year, day.day));
+    if sorted == slots {
+        return Ok(false);
+    }
+
+    let mut sorted_lines = Vec::with_capacity(content_lines.len());
+    let mut next_line = 0;
+    for ((slot, _), (lines, _)) in slots.iter().zip(sorted) {
+        sorted_lines.extend_from_slice(&content_lines[next_line..slot.start]);
+        sorted_lines.extend_from_slice(&content_lines[lines]);
+        next_line = slot.end;
+    }
+    sorted_lines.extend_from_slice(&content_lines[next_line..]);
+
+    fs::write(&runner_solutions, join_lines(&sorted_lines, &content))?;
+    Ok(true)
+}
+
+fn push_formatted_entry(array: &mut Array, entry: &str) {
+    // make sure the new entry is placed in its own line, like the existing ones
+    let decor = array.iter().last().map(|last| last.decor().clone());
+
+    let mut new_entry = Value::from(entry);
+    if let Some(decor) = decor {
+        *new_entry.decor_mut() = decor;
+    }
+    array.push_formatted(new_entry);
+}
+
+/// Gates the new dependency behind the `year{year}` feature of the solution runner,
+/// creating (and enabling by default) the feature if it's the first day of the event.
+fn add_to_year_feature(manifest: &mut LocalManifest, day: DayCrate) -> anyhow::Result<bool> {
+    let feature_name = day.year_feature();
+    let features = manifest
+        .manifest
+        .data
+        .get_mut("features")
+        .context("no features")?
+        .as_table_like_mut()
+        .context("features are not a table")?;
+
+    if features.get(&feature_name).is_none() {
+        let default = features
+            .get_mut("default")
+            .context("no default features")?
+            .as_array_mut()
+            .context("default features are not an array")?;
+        default.push(feature_name.as_str());
+
+        let mut year_feature = Array::new();
+        year_feature.set_trailing_comma(true);
+        year_feature.set_trailing("\n");
+        features.insert(&feature_name, toml_edit::value(year_feature));
+    }
+
+    let year_feature = features
+        .get_mut(&feature_name)
+        .context("no year feature")?
+        .as_array_mut()
+        .context("year feature is not an array")?;
+
+    let dependency = format!("dep:{}", day.package_name());
+    if year_feature
+        .iter()
+        .any(|entry| entry.as_str() == Some(&dependency))
+    {
+        return Ok(false);
+    }
+
+    if year_feature.is_empty() {
+        let mut new_entry = Value::from(dependency);
+        new_entry.decor_mut().set_prefix("\n    ");
+        year_feature.push_formatted(new_entry);
+    } else {
+        push_formatted_entry(year_feature, &dependency);
+    }
+
+    Ok(true)
+}
+
+/// Removes the dependency from its `year{year}` feature, along with the feature itself if it's no longer gating anything.
+fn remove_from_year_feature(manifest: &mut LocalManifest, day: DayCrate) -> anyhow::Result<bool> {
+    let feature_name = day.year_feature();
+    let features = manifest
+        .manifest
+        .data
+        .get_mut("features")
+        .context("no features")?
+        .as_table_like_mut()
+        .context("features are not a table")?;
+
+    let Some(year_feature) = features
+        .get_mut(&feature_name)
+        .and_then(|feature| feature.as_array_mut())
+    else {
+        return Ok(false);
+    };
+
+    let dependency = format!("dep:{}", day.package_name());
+    let before = year_feature.len();
+    year_feature.retain(|entry| entry.as_str() != Some(&dependency));
+    let removed = year_feature.len() != before;
+
+    if year_feature.is_empty() {
+        // features other than the default one, such as `legacy`, might still refer to it
+        let referenced = features.iter().any(|(name, feature)| {
+            name != "default"
+                && feature.as_array().is_some_and(|entries| {
+                    entries.iter().any(|e| e.as_str() == Some(&feature_name))
+                })
+        });
+        if !referenced {
+            features.remove(&feature_name);
+            if let Some(default) = features
+                .get_mut("default")
+                .and_then(|default| default.as_array_mut())
+            {
+                default.retain(|entry| entry.as_str() != Some(&feature_name));
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Adds the crate to the dependencies of the solution runner and registers its solution,
+/// skipping whatever is already in place. Returns whether anything has been changed.
+pub fn add_to_solution_runner(root: &Path, day: DayCrate) -> anyhow::Result<bool> {
+    let package_name = day.package_name();
+    let package_path = format!("../{}", day.member_path());
+
+    let mut manifest = LocalManifest::find(Some(root.join("solution-runner").as_path()))?;
+    let dependencies = manifest
+        .manifest
+        .data
+        .get_mut("dependencies")
+        .context("no dependencies")?
+        .as_table_like_mut()
+        .context("dependencies are not a table")?;
+
+    let mut changed = false;
+    if !dependencies.contains_key(&package_name) {
+        let mut table = InlineTable::new();
+        table.insert("path", package_path.into());
+        table.insert("optional", true.into());
+
+        dependencies.insert(&package_name, toml_edit::value(Value::InlineTable(table)));
+        changed = true;
+    }
+    changed |= add_to_year_feature(&mut manifest, day)?;
+    if changed {
+        manifest.write()?;
+    }
+
+    // is this extremely naive and fragile?
+    // yes.
+    // does it work (for now?)
+    // sure.
+    let runner_solutions = runner_solutions_path(root);
+    let content = fs::read_to_string(&runner_solutions)?;
+    let definition = day.solution_definition();
+    if content.lines().any(|line| line == definition) {
+        return Ok(changed);
+    }
+
+    let mut content_lines = content.lines().collect::<Vec<_>>();
+    let end_index = content_lines
+        .iter()
+        .enumerate()
+        .rfind(|(_, l)| l.contains(SOLUTIONS_END_TAG))
+        .map(|(i, _)| i)
+        .ok_or(anyhow!("AUTOGENERATED tags missing"))?;
+
+    let feature = day.solution_feature_gate();
+    content_lines.insert(end_index, &definition);
+    content_lines.insert(end_index, &feature);
+
+    fs::write(&runner_solutions, join_lines(&content_lines, &content))?;
+
+    Ok(true)
+}
+
+/// Removes the crate from the dependencies and the features of the solution runner, and unregisters its solution.
+/// Returns whether anything has been changed.
+pub fn remove_from_solution_runner(root: &Path, day: DayCrate) -> anyhow::Result<bool> {
+    let package_name = day.package_name();
+
+    let mut manifest = LocalManifest::find(Some(root.join("solution-runner").as_path()))?;
+    let dependencies = manifest
+        .manifest
+        .data
+        .get_mut("dependencies")
+        .context("no dependencies")?
+        .as_table_like_mut()
+        .context("dependencies are not a table")?;
+
+    let mut changed = dependencies.remove(&package_name).is_some();
+    changed |= remove_from_year_feature(&mut manifest, day)?;
+    if changed {
+        manifest.write()?;
+    }
+
+    let runner_solutions = runner_solutions_path(root);
+    let content = fs::read_to_string(&runner_solutions)?;
+    let mut content_lines = content.lines().collect::<Vec<_>>();
+
+    // the solution is registered by its type, the remaining arguments might have been edited by hand
+    let solver = format!("{package_name}::Day{:02})", day.day);
+    let Some(index) = content_lines
+        .iter()
+        .position(|line| line.contains("define_solution!") && line.contains(&solver))
+    else {
+        return Ok(changed);
+    };
+
+    content_lines.remove(index);
+    if index > 0
+        && content_lines[index - 1]
+            .trim_start()
+            .starts_with("#[cfg(feature")
+    {
+        content_lines.remove(index - 1);
+    }
+    fs::write(&runner_solutions, join_lines(&content_lines, &content))?;
+
+    Ok(true)
+}
+
+fn join_lines(lines: &[&str], original: &str) -> String {
+    let mut joined = lines.join("\n");
+    if original.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+/// Snapshot of the files and directories modified while scaffolding the day, which makes it possible
+/// to undo all the steps if any of them fails.
+#[derive(Debug, Default)]
+pub struct Transaction {
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+    created_directories: Vec<PathBuf>,
+}
+
+impl Transaction {
+    /// Remembers the current content of the file, or the lack of it.
+    pub fn track_file(&mut self, path: PathBuf) -> anyhow::Result<()> {
+        let content = match fs::read(&path) {
+            Ok(content) => Some(content),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", path.display()));
+            }
+        };
+        self.files.push((path, content));
+        Ok(())
+    }
+
+    /// Remembers to remove the directory on rollback, unless it already exists.
+    pub fn track_directory(&mut self, path: PathBuf) {
+        if !path.exists() {
+            self.created_directories.push(path)
+        }
+    }
+
+    /// Restores all the tracked files and removes the created directories.
+    pub fn rollback(self) -> anyhow::Result<()> {
+        for (path, content) in self.files {
+            match content {
+                Some(content) => fs::write(&path, content)
+                    .with_context(|| format!("failed to restore {}", path.display()))?,
+                None => {
+                    if path.exists() {
+                        fs::remove_file(&path)
+                            .with_context(|| format!("failed to remove {}", path.display()))?
+                    }
+                }
+            }
+        }
+        for directory in self.created_directories {
+            if directory.exists() {
+                fs::remove_dir_all(&directory)
+                    .with_context(|| format!("failed to remove {}", directory.display()))?
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const ROOT_MANIFEST: &str = r#"[workspace]
+resolver = "2"
+
+members = [
+    "solution-runner",
+    "2024/day01"
+]
+"#;
+
+    const RUNNER_MANIFEST: &str = r#"[package]
+name = "solution-runner"
+
+[features]
+default = ["legacy", "year2019", "year2024"]
+
+legacy = ["year2019"]
+
+year2019 = [
+    "dep:day01_2019",
+]
+
+year2024 = [
+    "dep:day01_2024",
+]
+
+[dependencies]
+day01_2019 = { path = "../2019/day01", optional = true }
+day01_2024 = { path = "../2024/day01", optional = true }
+"#;
+
+    const SOLUTIONS: &str = r#"pub fn registered_solutions() -> Vec<Solution> {
+    // AUTOGENERATED SOLUTIONS START
+    #[cfg(feature = "year2019")]
+    define_solution!(solutions, 2019, 1, "inputs/2019/day01", day01_2019::Day01);
+    #[cfg(feature = "year2024")]
+    define_solution!(solutions, 2024, 1, "inputs/2024/day01", day01_2024::Day01);
+    // AUTOGENERATED SOLUTIONS END
+}
+"#;
+
+    /// Minimal workspace with a day of 2024, and a legacy day of 2019 that's only registered in the runner.
+    pub(crate) struct TestWorkspace(pub(crate) PathBuf);
+
+    impl TestWorkspace {
+        pub(crate) fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("aoc-init-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("solution-runner/src")).unwrap();
+            fs::write(root.join("Cargo.toml"), ROOT_MANIFEST).unwrap();
+            fs::write(runner_manifest_path(&root), RUNNER_MANIFEST).unwrap();
+            fs::write(runner_solutions_path(&root), SOLUTIONS).unwrap();
+            TestWorkspace(root)
+        }
+
+        pub(crate) fn contents(&self) -> Vec<String> {
+            registration_files(&self.0)
+                .iter()
+                .map(|path| fs::read_to_string(path).unwrap())
+                .collect()
+        }
+    }
+
+    impl Drop for TestWorkspace {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn registering_and_removing_days() {
+        let workspace = TestWorkspace::new("registering");
+        let root = &workspace.0;
+        let original = workspace.contents();
+        let day = DayCrate { year: 2024, day: 2 };
+
+        assert!(add_to_workspace(root, day).unwrap());
+        assert!(add_to_solution_runner(root, day).unwrap());
+        let registered = workspace.contents();
+        assert!(registered[0].contains("    \"2024/day01\",\n    \"2024/day02\"\n]"));
+        assert!(registered[1].contains("    \"dep:day01_2024\",\n    \"dep:day02_2024\",\n]"));
+        assert!(
+            registered[1].contains("day02_2024 = { path = \"../2024/day02\", optional = true }")
+        );
+        assert!(registered[2].contains(
+            "    #[cfg(feature = \"year2024\")]\n    define_solution!(solutions, 2024, 2, \"inputs/2024/day02\", day02_2024::Day02);\n    // AUTOGENERATED SOLUTIONS END\n}\n"
+        ));
+
+        // registering again is a no-op
+        assert!(!add_to_workspace(root, day).unwrap());
+        assert!(!add_to_solution_runner(root, day).unwrap());
+        assert_eq!(registered, workspace.contents());
+
+        assert!(remove_from_workspace(root, day).unwrap());
+        assert!(remove_from_solution_runner(root, day).unwrap());
+        assert_eq!(original, workspace.contents());
+        assert!(!remove_from_workspace(root, day).unwrap());
+        assert!(!remove_from_solution_runner(root, day).unwrap());
+    }
+
+    #[test]
+    fn removing_the_last_day_of_the_event() {
+        let workspace = TestWorkspace::new("removing-last");
+        let root = &workspace.0;
+        let day = DayCrate { year: 2024, day: 1 };
+
+        assert!(remove_from_workspace(root, day).unwrap());
+        assert!(remove_from_solution_runner(root, day).unwrap());
+        let contents = workspace.contents();
+        assert!(contents[0].contains("members = [\n    \"solution-runner\"\n]"));
+        assert!(contents[1].contains("default = [\"legacy\", \"year2019\"]"));
+        assert!(!contents[1].contains("year2024"));
+        assert!(!contents[2].contains("2024"));
+
+        // the legacy events are still referred to by their own feature
+        let legacy = DayCrate { year: 2019, day: 1 };
+        assert!(remove_from_solution_runner(root, legacy).unwrap());
+        let contents = workspace.contents();
+        assert!(contents[1].contains("default = [\"legacy\", \"year2019\"]"));
+        assert!(contents[1].contains("year2019 = [\n]"));
+    }
+
+    #[test]
+    fn rolling_back_transactions() {
+        let workspace = TestWorkspace::new("rolling-back");
+        let root = &workspace.0;
+        let original = workspace.contents();
+        let day_directory = root.join("2024/day02");
+
+        let mut transaction = Transaction::default();
+        for file in registration_files(root) {
+            transaction.track_file(file).unwrap();
+        }
+        transaction.track_file(root.join("new-file")).unwrap();
+        transaction.track_directory(day_directory.clone());
+
+        let day = DayCrate { year: 2024, day: 2 };
+        add_to_workspace(root, day).unwrap();
+        add_to_solution_runner(root, day).unwrap();
+        fs::create_dir_all(day_directory.join("src")).unwrap();
+        fs::write(root.join("new-file"), "new").unwrap();
+
+        transaction.rollback().unwrap();
+        assert_eq!(original, workspace.contents());
+        assert!(!day_directory.exists());
+        assert!(!root.join("new-file").exists());
+    }
+}
@@ -11,3 +11,126 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+{%- case parsing %}
+{%- when "grid" %}
+
+use aoc_common::types::{Grid, ParsableGridItem};
+use winnow::combinator::alt;
+use winnow::token::literal;
+use winnow::{ModalResult, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tile {
+    Empty,
+    Wall,
+}
+
+impl ParsableGridItem for Tile {
+    const PARSER: fn(&mut &str) -> ModalResult<Self> = |input| {
+        alt((
+            literal('.').value(Tile::Empty),
+            literal('#').value(Tile::Wall),
+        ))
+        .parse_next(input)
+    };
+}
+
+pub type Map = Grid<Tile>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_parsing() {
+        assert_eq!(Tile::PARSER(&mut ".").unwrap(), Tile::Empty);
+        assert_eq!(Tile::PARSER(&mut "#").unwrap(), Tile::Wall);
+        assert!("..\n.#".parse::<Map>().is_ok());
+    }
+}
+{%- when "lines" %}
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub raw: String,
+}
+
+impl FromStr for Line {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Line { raw: s.to_string() })
+    }
+}
+{%- when "sections" %}
+
+use aoc_common::parsing::impls::parse_input_lines;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub lines: Vec<String>,
+}
+
+impl FromStr for Section {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Section {
+            lines: parse_input_lines(s)?,
+        })
+    }
+}
+{%- when "instructions" %}
+
+use aoc_common::parsing::combinators::parse_number;
+use aoc_solution::parser::AocInputParser;
+use winnow::ascii::line_ending;
+use winnow::combinator::{alt, preceded, separated};
+use winnow::{ModalResult, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instruction {
+    Add(usize),
+    Noop,
+}
+
+fn instruction_parser(input: &mut &str) -> ModalResult<Instruction> {
+    alt((
+        preceded("add ", parse_number).map(Instruction::Add),
+        "noop".value(Instruction::Noop),
+    ))
+    .parse_next(input)
+}
+
+pub(crate) struct InstructionsParser;
+
+impl AocInputParser for InstructionsParser {
+    type Output = Vec<Instruction>;
+
+    fn parse_input(raw: &str) -> anyhow::Result<Self::Output> {
+        separated(0.., instruction_parser, line_ending)
+            .parse(raw.trim())
+            .map_err(|err| anyhow::format_err!("{err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instruction_parsing() {
+        assert_eq!(
+            instruction_parser(&mut "add 42").unwrap(),
+            Instruction::Add(42)
+        );
+        assert_eq!(
+            InstructionsParser::parse_input("noop\nadd 1\n").unwrap(),
+            vec![Instruction::Noop, Instruction::Add(1)]
+        );
+    }
+}
+{%- endcase %}
@@ -12,38 +12,122 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+{% case parsing -%}
+{% when "grid" -%}
+use crate::common::Map;
+use aoc_common::parsing::FromStrParser;
+{% when "lines" -%}
+use crate::common::Line;
+use aoc_common::parsing::LineParser;
+{% when "sections" -%}
+use crate::common::Section;
+use aoc_common::parsing::GroupsParser;
+{% when "instructions" -%}
+use crate::common::{Instruction, InstructionsParser};
+{% endcase -%}
 use aoc_solution::Aoc;
 
 mod common;
 
 #[derive(Aoc)]
+{% case parsing -%}
+{% when "grid" -%}
+#[aoc(input = Map)]
+#[aoc(parser = FromStrParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2))]
+{% when "lines" -%}
+#[aoc(input = Vec<Line>)]
+#[aoc(parser = LineParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2))]
+{% when "sections" -%}
+#[aoc(input = Vec<Section>)]
+#[aoc(parser = GroupsParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2))]
+{% when "instructions" -%}
+#[aoc(input = Vec<Instruction>)]
+#[aoc(parser = InstructionsParser)]
+#[aoc(part1(output = usize, runner = part1))]
+#[aoc(part2(output = usize, runner = part2))]
+{% endcase -%}
 pub struct Day{{day}};
 
-pub fn part1(input: ()) -> ! {
+{% case parsing -%}
+{% when "grid" -%}
+{% assign input_type = "Map" -%}
+{% when "lines" -%}
+{% assign input_type = "Vec<Line>" -%}
+{% when "sections" -%}
+{% assign input_type = "Vec<Section>" -%}
+{% when "instructions" -%}
+{% assign input_type = "Vec<Instruction>" -%}
+{% else -%}
+{% assign input_type = "()" -%}
+{% endcase -%}
+pub fn part1(_input: {{input_type}}) -> usize {
     panic!("unimplemented")
 }
 
-pub fn part2(input: ()) -> ! {
+pub fn part2(_input: {{input_type}}) -> usize {
     panic!("unimplemented")
 }
 
 #[cfg(test)]
+{%- if input_type == "()" %}
+// the input stays `()` until its type gets chosen
+#[allow(clippy::unit_arg)]
+{%- endif %}
 mod tests {
     use super::*;
+{%- case parsing %}
+{%- when "grid", "lines", "sections", "instructions" %}
+    use aoc_solution::parser::AocInputParser;
+{%- else %}
+    use aoc_solution::AocSolution;
+{%- endcase %}
 
-    fn sample_input() -> ! {
-        todo!()
+    const SAMPLE_INPUT: &str = r#""#;
+{%- case parsing %}
+{%- when "grid" %}
+
+    fn sample_input() -> Map {
+        FromStrParser::<Map>::parse_input(SAMPLE_INPUT).unwrap()
+    }
+{%- when "lines" %}
+
+    fn sample_input() -> Vec<Line> {
+        LineParser::<Line>::parse_input(SAMPLE_INPUT).unwrap()
+    }
+{%- when "sections" %}
+
+    fn sample_input() -> Vec<Section> {
+        GroupsParser::<Section>::parse_input(SAMPLE_INPUT).unwrap()
     }
+{%- when "instructions" %}
+
+    fn sample_input() -> Vec<Instruction> {
+        InstructionsParser::parse_input(SAMPLE_INPUT).unwrap()
+    }
+{%- else %}
+
+    fn sample_input() {
+        Day{{day}}::parse_input(SAMPLE_INPUT).unwrap()
+    }
+{%- endcase %}
 
     #[test]
+    #[ignore = "the expected answer of the sample is yet to be filled in"]
     fn part1_sample_input() {
-        let expected = ();
+        let expected: usize = 0;
         assert_eq!(expected, part1(sample_input()))
     }
 
     #[test]
+    #[ignore = "the expected answer of the sample is yet to be filled in"]
     fn part2_sample_input() {
-        let expected = ();
+        let expected: usize = 0;
         assert_eq!(expected, part2(sample_input()))
     }
-}
\ No newline at end of file
+}
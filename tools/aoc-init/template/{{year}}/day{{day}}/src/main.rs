@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use aoc_common::helpers::root_path;
+use aoc_common::helpers::input_path;
 use aoc_solution::AocSolutionSolver;
 use day{{day}}_{{year}}::Day{{day}};
 
 #[cfg(not(tarpaulin_include))]
-fn main() {
-    Day{{day}}::try_solve_from_file(root_path("inputs/{{year}}/day{{day}}"))
+fn main() -> anyhow::Result<()> {
+    Day{{day}}::try_solve_from_file(input_path({{year}}, {{day_number}})?);
+    Ok(())
 }
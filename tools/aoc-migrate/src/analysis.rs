@@ -0,0 +1,688 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Static analysis of the legacy crates, i.e. how their `main.rs` reads the input and solves the parts,
+//! and what the signatures of the corresponding functions of `lib.rs` are.
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::HashMap;
+use syn::punctuated::Punctuated;
+use syn::visit::Visit;
+use syn::{
+    Expr, FnArg, GenericArgument, Item, Lit, Pat, PathArguments, ReturnType, Stmt, Token, Type,
+};
+
+const LEGACY_EXECUTORS: &[&str] = &["execute_vec", "execute_slice", "execute_struct"];
+
+/// Parsers of `aoc_common::parsing` equivalent to the legacy input readers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputParser {
+    Line,
+    StringGroups,
+    Groups,
+    CommaSeparated,
+    FromStr,
+}
+
+impl InputParser {
+    pub fn from_reader(reader: &str) -> Option<Self> {
+        match reader {
+            "read_line_input" | "read_parsed_line_input" | "read_input_lines" => {
+                Some(InputParser::Line)
+            }
+            "read_into_string_groups" => Some(InputParser::StringGroups),
+            "read_parsed_groups" => Some(InputParser::Groups),
+            "read_parsed_comma_separated_values" => Some(InputParser::CommaSeparated),
+            "read_parsed" | "read_to_string" => Some(InputParser::FromStr),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputParser::Line => "LineParser",
+            InputParser::StringGroups => "StringGroupsParser",
+            InputParser::Groups => "GroupsParser",
+            InputParser::CommaSeparated => "CommaSeparatedParser",
+            InputParser::FromStr => "FromStrParser",
+        }
+    }
+
+    /// Checks whether the parser can produce the provided input type.
+    pub fn produces(&self, input: &str) -> bool {
+        match self {
+            InputParser::StringGroups => input == "Vec<String>",
+            InputParser::FromStr => true,
+            _ => input.starts_with("Vec<"),
+        }
+    }
+}
+
+/// Renders the tokens the way they'd be formatted by hand, e.g. `Vec<(usize, String)>` rather than `Vec < (usize , String) >`.
+pub fn render(tokens: TokenStream) -> String {
+    let mut rendered = String::new();
+    let mut previous_word = false;
+    for tree in tokens {
+        match tree {
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if previous_word {
+                    rendered.push(' ')
+                }
+                rendered.push_str(&tree.to_string());
+                previous_word = true;
+            }
+            TokenTree::Punct(punct) => {
+                match punct.as_char() {
+                    ',' => rendered.push_str(", "),
+                    ';' => rendered.push_str("; "),
+                    c => rendered.push(c),
+                }
+                previous_word = false;
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                // e.g. `&mut [u8]`, but `Fn(usize)`
+                if previous_word && group.delimiter() != Delimiter::Parenthesis {
+                    rendered.push(' ')
+                }
+                rendered.push_str(open);
+                rendered.push_str(render(group.stream()).trim_end());
+                rendered.push_str(close);
+                previous_word = false;
+            }
+        }
+    }
+    rendered.replace("->", " -> ")
+}
+
+fn render_type(ty: &Type) -> String {
+    render(ty.to_token_stream())
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Str(lit) => Some(lit.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Name of the called function, e.g. `read_line_input` for `input_read::read_line_input`.
+fn function_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// The way the legacy `main.rs` solves the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyMain {
+    pub input_path: String,
+    /// Name of the function of `aoc_common::legacy::input_read` the input is read with
+    pub reader: String,
+    pub part1: String,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MainAnalysis {
+    /// The crate already uses `AocSolutionSolver`
+    Migrated,
+    Legacy(LegacyMain),
+    /// The `main.rs` doesn't follow any of the recognised patterns, with the reason why
+    Unsupported(String),
+}
+
+// strips the error handling of the reader call, i.e. `.expect(...)`, `.unwrap()` and `?`
+fn strip_error_handling(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::MethodCall(call) if call.method == "expect" || call.method == "unwrap" => {
+            strip_error_handling(&call.receiver)
+        }
+        Expr::Try(expr) => strip_error_handling(&expr.expr),
+        other => other,
+    }
+}
+
+fn refers_to(expr: &Expr, variable: &str) -> bool {
+    match expr {
+        Expr::Path(path) => path.path.is_ident(variable),
+        Expr::MethodCall(call) => call.method == "clone" && refers_to(&call.receiver, variable),
+        Expr::Reference(reference) => refers_to(&reference.expr, variable),
+        _ => false,
+    }
+}
+
+/// Collects the calls of the functions that are given the input, in order.
+struct InputCalls<'a> {
+    input: &'a str,
+    calls: Vec<(String, usize)>,
+}
+
+impl<'ast> Visit<'ast> for InputCalls<'_> {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*call.func
+            && let Some(name) = path.path.get_ident()
+            && call
+                .args
+                .first()
+                .is_some_and(|arg| refers_to(arg, self.input))
+        {
+            self.calls.push((name.to_string(), call.args.len()))
+        }
+        syn::visit::visit_expr_call(self, call)
+    }
+
+    // the answers are quite often printed directly, e.g. `println!("{}", part1(input.clone()))`
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            for arg in &args {
+                self.visit_expr(arg)
+            }
+        }
+    }
+}
+
+fn legacy_executor(stmts: &[Stmt]) -> Option<MainAnalysis> {
+    for stmt in stmts {
+        let Stmt::Expr(Expr::Call(call), _) = stmt else {
+            continue;
+        };
+        let Some(executor) = function_name(&call.func) else {
+            continue;
+        };
+        if !LEGACY_EXECUTORS.contains(&executor.as_str()) {
+            continue;
+        }
+
+        let args = call.args.iter().collect::<Vec<_>>();
+        let analysis = match args.as_slice() {
+            [input, reader, part1, part2] => {
+                match (
+                    string_literal(input),
+                    function_name(reader),
+                    function_name(part1),
+                    function_name(part2),
+                ) {
+                    (Some(input_path), Some(reader), Some(part1), Some(part2)) => {
+                        MainAnalysis::Legacy(LegacyMain {
+                            input_path,
+                            reader,
+                            part1,
+                            part2: Some(part2),
+                        })
+                    }
+                    _ => MainAnalysis::Unsupported(format!(
+                        "the arguments of {executor} are not plain paths"
+                    )),
+                }
+            }
+            _ => {
+                MainAnalysis::Unsupported(format!("{executor} is called with unexpected arguments"))
+            }
+        };
+        return Some(analysis);
+    }
+    None
+}
+
+fn hand_rolled(block: &syn::Block) -> Option<MainAnalysis> {
+    let (input, reader, input_path) = block.stmts.iter().find_map(|stmt| {
+        let Stmt::Local(local) = stmt else {
+            return None;
+        };
+        let Pat::Ident(variable) = &local.pat else {
+            return None;
+        };
+        let Expr::Call(call) = strip_error_handling(&local.init.as_ref()?.expr) else {
+            return None;
+        };
+        let reader = function_name(&call.func)?;
+        InputParser::from_reader(&reader)?;
+        let input_path = string_literal(call.args.first()?)?;
+        Some((variable.ident.to_string(), reader, input_path))
+    })?;
+
+    let mut calls = InputCalls {
+        input: &input,
+        calls: Vec::new(),
+    };
+    calls.visit_block(block);
+
+    if let Some((name, _)) = calls.calls.iter().find(|(_, args)| *args > 1) {
+        return Some(MainAnalysis::Unsupported(format!(
+            "{name} is called with arguments other than the input"
+        )));
+    }
+    let mut runners = calls.calls.into_iter().map(|(name, _)| name);
+    let Some(part1) = runners.next() else {
+        return Some(MainAnalysis::Unsupported(
+            "the input is not passed to any of the parts".to_string(),
+        ));
+    };
+    Some(MainAnalysis::Legacy(LegacyMain {
+        input_path,
+        reader,
+        part1,
+        part2: runners.next(),
+    }))
+}
+
+/// Figures out how the legacy `main.rs` reads the input and which functions solve the parts.
+pub fn analyse_main(source: &str) -> anyhow::Result<MainAnalysis> {
+    if source.contains("try_solve_from_file") || source.contains("try_solve(") {
+        return Ok(MainAnalysis::Migrated);
+    }
+
+    let file = syn::parse_file(source)?;
+    let Some(main) = file.items.iter().find_map(|item| match item {
+        Item::Fn(function) if function.sig.ident == "main" => Some(function),
+        _ => None,
+    }) else {
+        return Ok(MainAnalysis::Unsupported(
+            "there is no main function".to_string(),
+        ));
+    };
+
+    Ok(legacy_executor(&main.block.stmts)
+        .or_else(|| hand_rolled(&main.block))
+        .unwrap_or_else(|| {
+            MainAnalysis::Unsupported(
+                "the input is not read with any of the recognised legacy readers".to_string(),
+            )
+        }))
+}
+
+/// Return type of the function solving a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    Plain(String),
+    Fallible { ok: String, error: String },
+    Optional(String),
+    Unit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartSignature {
+    /// Types of all the parameters
+    pub parameters: Vec<String>,
+    pub output: Output,
+}
+
+fn generic_arguments(ty: &Type) -> Option<(String, Vec<String>)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    let PathArguments::AngleBracketed(arguments) = &last.arguments else {
+        return None;
+    };
+    let arguments = arguments
+        .args
+        .iter()
+        .filter_map(|argument| match argument {
+            GenericArgument::Type(ty) => Some(render_type(ty)),
+            _ => None,
+        })
+        .collect();
+
+    let mut name = render(path.path.to_token_stream());
+    if let Some(generics) = name.find('<') {
+        name.truncate(generics)
+    }
+    Some((name, arguments))
+}
+
+fn output(return_type: &ReturnType, anyhow_result: bool) -> Output {
+    let ReturnType::Type(_, ty) = return_type else {
+        return Output::Unit;
+    };
+    match generic_arguments(ty) {
+        Some((name, arguments)) if name.ends_with("Option") && arguments.len() == 1 => {
+            Output::Optional(arguments[0].clone())
+        }
+        Some((name, arguments)) if name.ends_with("Result") => match arguments.as_slice() {
+            [ok, error] => Output::Fallible {
+                ok: ok.clone(),
+                error: error.clone(),
+            },
+            [ok] if name == "anyhow::Result" || anyhow_result => Output::Fallible {
+                ok: ok.clone(),
+                error: "anyhow::Error".to_string(),
+            },
+            _ => Output::Plain(render_type(ty)),
+        },
+        _ => Output::Plain(render_type(ty)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibAnalysis {
+    /// Type deriving `Aoc`
+    pub solver: Option<String>,
+    /// Whether the solver already has any `#[aoc(...)]` attributes
+    pub configured: bool,
+    /// Signatures of all the top level functions
+    pub functions: HashMap<String, PartSignature>,
+}
+
+fn derives_aoc(attributes: &[syn::Attribute]) -> bool {
+    attributes.iter().any(|attribute| {
+        attribute.path().is_ident("derive")
+            && attribute
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .is_ok_and(|derived| {
+                    derived
+                        .iter()
+                        .any(|path| path.segments.last().is_some_and(|s| s.ident == "Aoc"))
+                })
+    })
+}
+
+/// Finds the solver type and the signatures of the functions that might be solving the parts.
+pub fn analyse_lib(source: &str) -> anyhow::Result<LibAnalysis> {
+    let file = syn::parse_file(source)?;
+    // `Result<T>` is most likely the alias of anyhow, but it might as well be the one of `io`
+    let anyhow_result = source.contains("anyhow::Result") || source.contains("anyhow::{");
+
+    let mut analysis = LibAnalysis {
+        solver: None,
+        configured: false,
+        functions: HashMap::new(),
+    };
+    for item in &file.items {
+        match item {
+            Item::Struct(item) if derives_aoc(&item.attrs) => {
+                analysis.solver = Some(item.ident.to_string());
+                analysis.configured = item.attrs.iter().any(|attr| attr.path().is_ident("aoc"));
+            }
+            Item::Fn(function) => {
+                let parameters = function
+                    .sig
+                    .inputs
+                    .iter()
+                    .map(|input| match input {
+                        FnArg::Typed(typed) => render_type(&typed.ty),
+                        FnArg::Receiver(_) => "self".to_string(),
+                    })
+                    .collect();
+                analysis.functions.insert(
+                    function.sig.ident.to_string(),
+                    PartSignature {
+                        parameters,
+                        output: output(&function.sig.output, anyhow_result),
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(analysis)
+}
+
+/// Contents of the `#[aoc(...)]` attributes of the migrated solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocAttributes {
+    pub input: String,
+    pub parser: InputParser,
+    pub error: Option<String>,
+    /// Runner and the output type of each of the parts
+    pub part1: (String, String),
+    pub part2: Option<(String, String)>,
+}
+
+impl AocAttributes {
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("#[aoc(input = {})]", self.input),
+            format!("#[aoc(parser = {})]", self.parser.name()),
+        ];
+        if let Some(error) = &self.error {
+            lines.push(format!("#[aoc(error = {error})]"))
+        }
+        let (runner, output) = &self.part1;
+        lines.push(format!(
+            "#[aoc(part1(output = {output}, runner = {runner}))]"
+        ));
+        if let Some((runner, output)) = &self.part2 {
+            lines.push(format!(
+                "#[aoc(part2(output = {output}, runner = {runner}))]"
+            ))
+        }
+        lines
+    }
+}
+
+/// Works out the attributes of the solver, or all the reasons why it can't be done automatically.
+pub fn plan(main: &LegacyMain, lib: &LibAnalysis) -> Result<AocAttributes, Vec<String>> {
+    let mut problems = Vec::new();
+    let Some(parser) = InputParser::from_reader(&main.reader) else {
+        return Err(vec![format!(
+            "{} has no equivalent among the parsers of aoc_common::parsing",
+            main.reader
+        )]);
+    };
+
+    let mut parts = Vec::new();
+    for runner in std::iter::once(&main.part1).chain(&main.part2) {
+        let Some(signature) = lib.functions.get(runner) else {
+            problems.push(format!(
+                "{runner} is not defined at the top level of lib.rs"
+            ));
+            continue;
+        };
+        match signature.parameters.as_slice() {
+            [input] if input.starts_with('&') => problems.push(format!(
+                "{runner} takes its input by reference ({input}), while the runners take it by value"
+            )),
+            [_] => {}
+            _ => problems.push(format!("{runner} doesn't take the input as its only parameter")),
+        }
+        match &signature.output {
+            Output::Optional(ok) => problems.push(format!(
+                "{runner} returns Option<{ok}>, which has to be turned into a Result or a plain value"
+            )),
+            Output::Unit => problems.push(format!("{runner} doesn't return the answer")),
+            Output::Fallible { error, .. } if error != "anyhow::Error" => problems.push(format!(
+                "{runner} fails with {error}, rather than anyhow::Error"
+            )),
+            _ => {}
+        }
+        parts.push((runner.clone(), signature));
+    }
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let input = parts[0].1.parameters[0].clone();
+    if parts
+        .iter()
+        .any(|(_, signature)| signature.parameters[0] != input)
+    {
+        problems.push("the parts take different input types".to_string())
+    }
+    if !parser.produces(&input) {
+        problems.push(format!(
+            "{} can't produce {input}, which is what the parts take",
+            parser.name()
+        ))
+    }
+    let fallible = parts
+        .iter()
+        .filter(|(_, signature)| matches!(signature.output, Output::Fallible { .. }))
+        .count();
+    if fallible != 0 && fallible != parts.len() {
+        problems.push("only some of the parts are fallible".to_string())
+    }
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let mut parts = parts.into_iter().map(|(runner, signature)| {
+        let output = match &signature.output {
+            Output::Plain(output) | Output::Fallible { ok: output, .. } => output.clone(),
+            Output::Optional(_) | Output::Unit => String::new(),
+        };
+        (runner, output)
+    });
+    let part1 = parts
+        .next()
+        .ok_or_else(|| vec!["there is no part 1".to_string()])?;
+    Ok(AocAttributes {
+        input,
+        parser,
+        error: (fallible != 0).then(|| "anyhow::Error".to_string()),
+        part1,
+        part2: parts.next(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendering_types() {
+        let ty: Type = syn::parse_str("Vec < ( usize , & 'a str ) >").unwrap();
+        assert_eq!("Vec<(usize, &'a str)>", render_type(&ty));
+        let ty: Type = syn::parse_str("Box<dyn Fn(&mut [u8; 4]) -> usize>").unwrap();
+        assert_eq!("Box<dyn Fn(&mut [u8; 4]) -> usize>", render_type(&ty));
+    }
+
+    #[test]
+    fn analysing_legacy_executors() {
+        let main = r#"
+use aoc_common::legacy::{execute_vec, input_read};
+use day01_2021::{part1, part2};
+
+fn main() {
+    execute_vec(
+        "inputs/2021/day01",
+        input_read::read_parsed_line_input,
+        part1,
+        part2,
+    )
+}
+"#;
+        assert_eq!(
+            MainAnalysis::Legacy(LegacyMain {
+                input_path: "inputs/2021/day01".to_string(),
+                reader: "read_parsed_line_input".to_string(),
+                part1: "part1".to_string(),
+                part2: Some("part2".to_string()),
+            }),
+            analyse_main(main).unwrap()
+        );
+    }
+
+    #[test]
+    fn analysing_hand_rolled_mains() {
+        let main = r#"
+fn main() {
+    let input =
+        input_read::read_line_input("inputs/2020/day08").expect("failed to read input file");
+
+    let part1_result = part1(input.clone());
+    println!("Part 1 result is {}", part1_result);
+
+    let part2_result = part2(input).expect("failed to solve part2");
+    println!("Part 2 result is {}", part2_result);
+}
+"#;
+        assert_eq!(
+            MainAnalysis::Legacy(LegacyMain {
+                input_path: "inputs/2020/day08".to_string(),
+                reader: "read_line_input".to_string(),
+                part1: "part1".to_string(),
+                part2: Some("part2".to_string()),
+            }),
+            analyse_main(main).unwrap()
+        );
+
+        let extra_arguments = main.replace("part1(input.clone())", "part1(input.clone(), 25)");
+        assert!(matches!(
+            analyse_main(&extra_arguments).unwrap(),
+            MainAnalysis::Unsupported(_)
+        ));
+
+        let bespoke = "fn main() { let tape = Tape::new(read_input_file(\"inputs/2019/day05\")); run_machine(tape); }";
+        assert!(matches!(
+            analyse_main(bespoke).unwrap(),
+            MainAnalysis::Unsupported(_)
+        ));
+
+        let migrated = "fn main() -> anyhow::Result<()> { Day01::try_solve_from_file(input_path(2022, 1)?); Ok(()) }";
+        assert_eq!(MainAnalysis::Migrated, analyse_main(migrated).unwrap());
+    }
+
+    #[test]
+    fn planning_attributes() {
+        let main = LegacyMain {
+            input_path: "inputs/2020/day08".to_string(),
+            reader: "read_line_input".to_string(),
+            part1: "part1".to_string(),
+            part2: Some("part2".to_string()),
+        };
+        let lib = r#"
+use anyhow::Result;
+
+#[derive(Aoc)]
+pub struct Day08;
+
+pub fn part1(input: Vec<String>) -> Result<isize> { todo!() }
+pub fn part2(input: Vec<String>) -> anyhow::Result<isize> { todo!() }
+"#;
+        let analysis = analyse_lib(lib).unwrap();
+        assert_eq!(Some("Day08".to_string()), analysis.solver);
+        assert!(!analysis.configured);
+
+        let attributes = plan(&main, &analysis).unwrap();
+        assert_eq!(
+            vec![
+                "#[aoc(input = Vec<String>)]",
+                "#[aoc(parser = LineParser)]",
+                "#[aoc(error = anyhow::Error)]",
+                "#[aoc(part1(output = isize, runner = part1))]",
+                "#[aoc(part2(output = isize, runner = part2))]",
+            ],
+            attributes.lines()
+        );
+
+        let optional = lib.replace("-> Result<isize>", "-> Option<isize>");
+        let problems = plan(&main, &analyse_lib(&optional).unwrap()).unwrap_err();
+        assert_eq!(1, problems.len());
+        assert!(problems[0].contains("Option<isize>"));
+
+        let by_reference = lib.replace("Vec<String>", "&[String]");
+        let problems = plan(&main, &analyse_lib(&by_reference).unwrap()).unwrap_err();
+        assert_eq!(2, problems.len());
+
+        let groups = LegacyMain {
+            reader: "read_into_string_groups".to_string(),
+            ..main
+        };
+        let numbers = lib.replace("Vec<String>", "Vec<usize>");
+        let problems = plan(&groups, &analyse_lib(&numbers).unwrap()).unwrap_err();
+        assert!(problems[0].contains("StringGroupsParser can't produce Vec<usize>"));
+    }
+}
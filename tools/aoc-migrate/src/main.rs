@@ -0,0 +1,412 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use analysis::{MainAnalysis, analyse_lib, analyse_main, plan};
+use anyhow::{Context, bail};
+use clap::Parser;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+mod analysis;
+mod rewrite;
+
+const LEGACY_YEARS: [u16; 3] = [2019, 2020, 2021];
+
+/// Migrates the legacy days, i.e. the ones still solved through `aoc_common::legacy`, to `#[derive(Aoc)]` based solvers.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Crates to migrate, relative to the workspace root, e.g. '2020/day08'. Defaults to all the days of the legacy years
+    crates: Vec<PathBuf>,
+
+    /// Only migrate the days of the specified year
+    #[arg(short, long, conflicts_with = "crates")]
+    year: Option<u16>,
+
+    /// Report what would have been migrated without changing any of the files
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Root of the workspace, defaults to the current directory
+    #[arg(long)]
+    workspace_root: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+enum Outcome {
+    AlreadyMigrated,
+    Migrated { notes: Vec<String> },
+    NeedsManualWork { reasons: Vec<String> },
+}
+
+impl Outcome {
+    /// Describes the outcome, which, in case of a dry run, must not claim that any of the files got written.
+    fn describe(&self, dry_run: bool) -> String {
+        let (summary, details) = match self {
+            Outcome::AlreadyMigrated => ("already migrated", Vec::new()),
+            Outcome::Migrated { notes } => (
+                if dry_run { "would migrate" } else { "migrated" },
+                notes.iter().map(|note| format!("note: {note}")).collect(),
+            ),
+            Outcome::NeedsManualWork { reasons } => (
+                "needs manual migration",
+                reasons.iter().map(|reason| format!("- {reason}")).collect(),
+            ),
+        };
+
+        let mut description = summary.to_string();
+        for detail in details {
+            description.push_str("\n    ");
+            description.push_str(&detail);
+        }
+        description
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.describe(false))
+    }
+}
+
+/// Result of the migration of a single crate, along with the files it would write.
+struct Migration {
+    outcome: Outcome,
+    files: Vec<(PathBuf, String)>,
+}
+
+impl Migration {
+    fn manual(reasons: Vec<String>) -> Self {
+        Migration {
+            outcome: Outcome::NeedsManualWork { reasons },
+            files: Vec::new(),
+        }
+    }
+}
+
+fn legacy_crates(root: &Path, year: Option<u16>) -> anyhow::Result<Vec<PathBuf>> {
+    let mut crates = Vec::new();
+    for year in LEGACY_YEARS
+        .into_iter()
+        .filter(|y| year.is_none_or(|year| year == *y))
+    {
+        let year_dir = root.join(year.to_string());
+        if !year_dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&year_dir)? {
+            let path = entry?.path();
+            if path.join("Cargo.toml").is_file() {
+                crates.push(
+                    PathBuf::from(year.to_string())
+                        .join(path.file_name().context("unnamed crate directory")?),
+                )
+            }
+        }
+    }
+    crates.sort();
+    Ok(crates)
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn package_name(manifest: &str) -> anyhow::Result<String> {
+    let document = manifest.parse::<toml_edit::Document>()?;
+    match document
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+    {
+        Some(name) => Ok(name.to_string()),
+        None => bail!("the manifest doesn't specify the package name"),
+    }
+}
+
+/// Year and day of the crate, i.e. of its `<year>/dayXX` directory.
+fn day_of(member: &Path) -> anyhow::Result<(u16, u8)> {
+    let year = member
+        .parent()
+        .and_then(|year| year.to_str())
+        .and_then(|year| year.parse().ok());
+    let day = member
+        .file_name()
+        .and_then(|day| day.to_str())
+        .and_then(|day| day.strip_prefix("day"))
+        .and_then(|day| day.parse().ok());
+    match (year, day) {
+        (Some(year), Some(day)) => Ok((year, day)),
+        _ => bail!("{} is not a '<year>/dayXX' directory", member.display()),
+    }
+}
+
+/// Works out all the changes needed to migrate the crate, without applying them.
+fn migrate(root: &Path, member: &Path) -> anyhow::Result<Migration> {
+    let dir = root.join(member);
+    let manifest_path = dir.join("Cargo.toml");
+    let main_path = dir.join("src").join("main.rs");
+    let lib_path = dir.join("src").join("lib.rs");
+
+    let manifest = read(&manifest_path)?;
+    let package = package_name(&manifest)?;
+    let main = read(&main_path)?;
+    let legacy = match analyse_main(&main)
+        .with_context(|| format!("failed to parse {}", main_path.display()))?
+    {
+        MainAnalysis::Migrated => {
+            return Ok(Migration {
+                outcome: Outcome::AlreadyMigrated,
+                files: Vec::new(),
+            });
+        }
+        MainAnalysis::Unsupported(reason) => return Ok(Migration::manual(vec![reason])),
+        MainAnalysis::Legacy(legacy) => legacy,
+    };
+
+    let lib = read(&lib_path)?;
+    let analysis =
+        analyse_lib(&lib).with_context(|| format!("failed to parse {}", lib_path.display()))?;
+    let Some(solver) = &analysis.solver else {
+        return Ok(Migration::manual(vec![
+            "lib.rs doesn't define any type deriving Aoc".to_string(),
+        ]));
+    };
+
+    let mut notes = Vec::new();
+    let mut files = Vec::new();
+    // quite a few of the legacy solvers have already been configured, it's only their main.rs that's left
+    if !analysis.configured {
+        match plan(&legacy, &analysis) {
+            Ok(attributes) => files.push((
+                lib_path,
+                rewrite::configure_solver(&lib, solver, &attributes)?,
+            )),
+            Err(reasons) => return Ok(Migration::manual(reasons)),
+        }
+    }
+
+    let (year, day) = day_of(member)?;
+    let input_path = format!("inputs/{}", member.display()).replace('\\', "/");
+    if legacy.input_path != input_path {
+        notes.push(format!(
+            "main.rs used to read '{}' rather than '{input_path}'",
+            legacy.input_path
+        ))
+    }
+
+    let years = rewrite::copyright_years(&main);
+    files.push((
+        main_path,
+        rewrite::main_source(years, &package, solver, year, day),
+    ));
+    let benchmarks_path = dir.join("benches").join("benchmarks.rs");
+    if !benchmarks_path.exists() {
+        files.push((
+            benchmarks_path,
+            rewrite::benchmarks_source(years, &package, solver, year, day),
+        ));
+    }
+    files.push((manifest_path, rewrite::add_benchmarks_target(&manifest)?));
+
+    for entry in fs::read_dir(dir.join("src"))? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if name != "main.rs" && name.ends_with(".rs") && read(&path)?.contains("aoc_common::legacy")
+        {
+            notes.push(format!("{name} still uses aoc_common::legacy"))
+        }
+    }
+
+    Ok(Migration {
+        outcome: Outcome::Migrated { notes },
+        files,
+    })
+}
+
+fn apply(migration: &mut Migration) -> anyhow::Result<()> {
+    for (path, content) in &migration.files {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))?;
+    }
+
+    // the imports are inserted without regard for their order, so let rustfmt sort them out
+    let sources = migration
+        .files
+        .iter()
+        .map(|(path, _)| path)
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .collect::<Vec<_>>();
+    if sources.is_empty() {
+        return Ok(());
+    }
+    let formatted = Command::new("rustfmt")
+        .args(["--edition", "2024"])
+        .args(sources)
+        .status()
+        .is_ok_and(|status| status.success());
+    if !formatted && let Outcome::Migrated { notes } = &mut migration.outcome {
+        notes.push("failed to run rustfmt on the migrated files".to_string())
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let root = args.workspace_root.unwrap_or(env::current_dir()?);
+
+    let crates = if args.crates.is_empty() {
+        legacy_crates(&root, args.year)?
+    } else {
+        args.crates
+    };
+
+    let mut migrated = 0;
+    let mut manual = 0;
+    for member in crates {
+        let mut migration = migrate(&root, &member)?;
+        match migration.outcome {
+            Outcome::AlreadyMigrated => continue,
+            Outcome::Migrated { .. } => migrated += 1,
+            Outcome::NeedsManualWork { .. } => manual += 1,
+        }
+        if !args.dry_run {
+            apply(&mut migration)?;
+        }
+        println!(
+            "{}: {}",
+            member.display(),
+            migration.outcome.describe(args.dry_run)
+        );
+    }
+
+    let verb = if args.dry_run {
+        "would migrate"
+    } else {
+        "migrated"
+    };
+    println!("{verb} {migrated} days, {manual} need to be migrated by hand");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describing_dry_runs() {
+        let outcome = Outcome::Migrated {
+            notes: vec!["failed to run rustfmt on the migrated files".to_string()],
+        };
+        assert_eq!(
+            "migrated\n    note: failed to run rustfmt on the migrated files",
+            outcome.to_string()
+        );
+        assert_eq!(
+            "would migrate\n    note: failed to run rustfmt on the migrated files",
+            outcome.describe(true)
+        );
+
+        let outcome = Outcome::NeedsManualWork {
+            reasons: vec!["unsupported main".to_string()],
+        };
+        assert_eq!(outcome.to_string(), outcome.describe(true));
+    }
+
+    #[test]
+    fn migrating_legacy_day() -> anyhow::Result<()> {
+        let root = env::temp_dir().join(format!("aoc-migrate-{}", std::process::id()));
+        let dir = root.join("2020").join("day08");
+        fs::create_dir_all(dir.join("src"))?;
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"day08_2020\"\n\n[lints]\nworkspace = true\n",
+        )?;
+        fs::write(
+            dir.join("src").join("main.rs"),
+            r#"// Copyright 2020 Jedrzej Stuczynski
+
+use aoc_common::legacy::input_read;
+use day08_2020::{part1, part2};
+
+fn main() {
+    let input = input_read::read_line_input("inputs/2020/day8").unwrap();
+    println!("{}", part1(input.clone()));
+    println!("{}", part2(input));
+}
+"#,
+        )?;
+        let lib = r#"use aoc_solution::Aoc;
+
+#[derive(Aoc)]
+pub struct Day08;
+
+pub fn part1(input: Vec<String>) -> isize {
+    0
+}
+
+pub fn part2(input: Vec<String>) -> Option<isize> {
+    None
+}
+"#;
+        fs::write(dir.join("src").join("lib.rs"), lib)?;
+
+        let member = PathBuf::from("2020/day08");
+        let migration = migrate(&root, &member)?;
+        let Outcome::NeedsManualWork { reasons } = migration.outcome else {
+            bail!("the optional output should have been flagged")
+        };
+        assert_eq!(1, reasons.len());
+
+        fs::write(
+            dir.join("src").join("lib.rs"),
+            lib.replace("Option<isize>", "isize").replace("None", "1"),
+        )?;
+        assert_eq!(vec![member.clone()], legacy_crates(&root, Some(2020))?);
+        let migration = migrate(&root, &member)?;
+        let Outcome::Migrated { notes } = &migration.outcome else {
+            bail!("the day should have been migrated: {}", migration.outcome)
+        };
+        assert_eq!(
+            &vec!["main.rs used to read 'inputs/2020/day8' rather than 'inputs/2020/day08'"],
+            notes
+        );
+        assert_eq!(4, migration.files.len());
+        assert!(
+            migration
+                .files
+                .iter()
+                .any(|(path, content)| path.ends_with("src/main.rs")
+                    && content.contains("Day08::try_solve_from_file(input_path(2020, 8)?);"))
+        );
+
+        fs::create_dir_all(dir.join("benches"))?;
+        for (path, content) in migration.files {
+            fs::write(path, content)?;
+        }
+        assert!(matches!(
+            migrate(&root, &member)?.outcome,
+            Outcome::AlreadyMigrated
+        ));
+
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2023 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Textual rewrites of the legacy crates. The edits are deliberately line based rather than
+//! re-printing the syntax trees so that the comments and formatting of the solutions are preserved.
+
+use crate::analysis::AocAttributes;
+use anyhow::{Context, bail};
+use toml_edit::{ArrayOfTables, Document, Item, Table, value};
+
+const FALLBACK_YEARS: &str = "2023";
+
+/// Copyright line of the original file, so that the years of the original solution are preserved.
+pub fn copyright_years(source: &str) -> &str {
+    source
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("// Copyright "))
+        .and_then(|line| line.strip_suffix(" Jedrzej Stuczynski"))
+        .unwrap_or(FALLBACK_YEARS)
+}
+
+fn license_header(years: &str) -> String {
+    format!(
+        r#"// Copyright {years} Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+"#
+    )
+}
+
+/// Adds the `#[aoc(...)]` attributes to the solver, along with the import of the parser.
+pub fn configure_solver(
+    source: &str,
+    solver: &str,
+    attributes: &AocAttributes,
+) -> anyhow::Result<String> {
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+    let definition = format!("pub struct {solver};");
+    let Some(position) = lines.iter().position(|line| line.trim() == definition) else {
+        bail!("could not find the unit struct definition of {solver}")
+    };
+    if !lines[..position]
+        .last()
+        .is_some_and(|line| line.starts_with("#[derive(") && line.contains("Aoc"))
+    {
+        bail!("{solver} has to be directly preceded by its derive attribute")
+    }
+    lines.splice(position..position, attributes.lines());
+
+    let import = format!("use aoc_common::parsing::{};", attributes.parser.name());
+    if !lines.contains(&import) {
+        let Some(position) = lines.iter().position(|line| line.starts_with("use ")) else {
+            bail!("lib.rs doesn't have any imports")
+        };
+        lines.insert(position, import)
+    }
+
+    let mut rewritten = lines.join("\n");
+    rewritten.push('\n');
+    Ok(rewritten)
+}
+
+/// `main.rs` of the migrated crate, identical to the one created by `aoc-init`.
+pub fn main_source(years: &str, package: &str, solver: &str, year: u16, day: u8) -> String {
+    format!(
+        r#"{header}
+use aoc_common::helpers::input_path;
+use aoc_solution::AocSolutionSolver;
+use {package}::{solver};
+
+#[cfg(not(tarpaulin_include))]
+fn main() -> anyhow::Result<()> {{
+    {solver}::try_solve_from_file(input_path({year}, {day})?);
+    Ok(())
+}}
+"#,
+        header = license_header(years)
+    )
+}
+
+/// `benches/benchmarks.rs` of the migrated crate, identical to the one created by `aoc-init`.
+pub fn benchmarks_source(years: &str, package: &str, solver: &str, year: u16, day: u8) -> String {
+    format!(
+        r#"{header}
+use aoc_common::define_aoc_benchmark;
+use {package}::{solver};
+
+define_aoc_benchmark!({year}, {day}, {solver});
+"#,
+        header = license_header(years)
+    )
+}
+
+/// Adds the criterion dev-dependency and the benchmark target to the manifest, unless they're already there.
+pub fn add_benchmarks_target(manifest: &str) -> anyhow::Result<String> {
+    let mut document = manifest
+        .parse::<Document>()
+        .context("failed to parse the manifest")?;
+
+    let dev_dependencies = document
+        .entry("dev-dependencies")
+        .or_insert_with(|| Item::Table(Table::new()));
+    let Some(dev_dependencies) = dev_dependencies.as_table_like_mut() else {
+        bail!("dev-dependencies is not a table")
+    };
+    if !dev_dependencies.contains_key("criterion") {
+        let mut criterion = toml_edit::InlineTable::new();
+        criterion.insert("workspace", true.into());
+        dev_dependencies.insert("criterion", value(criterion));
+    }
+
+    let benches = document
+        .entry("bench")
+        .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()));
+    let Some(benches) = benches.as_array_of_tables_mut() else {
+        bail!("bench is not an array of tables")
+    };
+    if !benches
+        .iter()
+        .any(|bench| bench.get("name").and_then(Item::as_str) == Some("benchmarks"))
+    {
+        let mut bench = Table::new();
+        bench.insert("name", value("benchmarks"));
+        bench.insert("harness", value(false));
+        benches.push(bench);
+    }
+
+    // keep `[lints]` as the last section, the way it is in the rest of the workspace
+    if let Some(lints) = document.remove("lints") {
+        document.insert("lints", lints);
+    }
+
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::InputParser;
+
+    #[test]
+    fn configuring_solver() {
+        let lib = r#"// Copyright 2020 Jedrzej Stuczynski
+
+use anyhow::bail;
+use aoc_solution::Aoc;
+
+#[derive(Aoc)]
+pub struct Day08;
+
+pub fn part1(input: Vec<String>) -> isize {
+    0
+}
+"#;
+        let attributes = AocAttributes {
+            input: "Vec<String>".to_string(),
+            parser: InputParser::Line,
+            error: None,
+            part1: ("part1".to_string(), "isize".to_string()),
+            part2: None,
+        };
+
+        let expected = r#"// Copyright 2020 Jedrzej Stuczynski
+
+use aoc_common::parsing::LineParser;
+use anyhow::bail;
+use aoc_solution::Aoc;
+
+#[derive(Aoc)]
+#[aoc(input = Vec<String>)]
+#[aoc(parser = LineParser)]
+#[aoc(part1(output = isize, runner = part1))]
+pub struct Day08;
+
+pub fn part1(input: Vec<String>) -> isize {
+    0
+}
+"#;
+        assert_eq!(
+            expected,
+            configure_solver(lib, "Day08", &attributes).unwrap()
+        );
+        assert_eq!("2020", copyright_years(lib));
+        assert!(configure_solver(lib, "Day09", &attributes).is_err());
+    }
+
+    #[test]
+    fn adding_benchmarks_target() {
+        let manifest = r#"[package]
+name = "day01_2021"
+
+[dependencies]
+anyhow = { workspace = true }
+
+[lints]
+workspace = true
+"#;
+        let expected = r#"[package]
+name = "day01_2021"
+
+[dependencies]
+anyhow = { workspace = true }
+
+[dev-dependencies]
+criterion = { workspace = true }
+
+[[bench]]
+name = "benchmarks"
+harness = false
+
+[lints]
+workspace = true
+"#;
+        let migrated = add_benchmarks_target(manifest).unwrap();
+        assert_eq!(expected, migrated);
+        assert_eq!(expected, add_benchmarks_target(&migrated).unwrap());
+    }
+}